@@ -93,6 +93,29 @@ else
     sudo mv "$TMP_FILE" "$INSTALL_DIR/$BIN_NAME"
 fi
 
+# Shell completions and man page (set NO_COMPLETIONS=1 to skip)
+if [ -z "$NO_COMPLETIONS" ]; then
+    SHARE_DIR="/usr/local/share"
+    SUDO=""
+    if [ ! -w "$SHARE_DIR" ]; then
+        SUDO="sudo"
+    fi
+
+    install_generated() {
+        # $1: destination file, remaining arguments: cascolor subcommand
+        DEST="$1"
+        shift
+        $SUDO mkdir -p "$(dirname "$DEST")"
+        "$INSTALL_DIR/$BIN_NAME" "$@" | $SUDO tee "$DEST" > /dev/null
+    }
+
+    echo "Installing shell completions and man page..."
+    install_generated "$SHARE_DIR/bash-completion/completions/$BIN_NAME" completions bash
+    install_generated "$SHARE_DIR/zsh/site-functions/_$BIN_NAME" completions zsh
+    install_generated "$SHARE_DIR/fish/vendor_completions.d/$BIN_NAME.fish" completions fish
+    install_generated "$SHARE_DIR/man/man1/$BIN_NAME.1" manpage
+fi
+
 echo -e "${GREEN}✓ Installation complete!${NC}"
 echo ""
 echo "Run: $BIN_NAME --help"
@@ -24,3 +24,13 @@ chmod +x "$TMP_FILE"
 
 sudo mv "$TMP_FILE" "$INSTALL_DIR/$BIN_NAME"
 echo "✓ Installed to $INSTALL_DIR/$BIN_NAME"
+
+# Shell completions and man page
+SHARE_DIR="/usr/local/share"
+sudo mkdir -p "$SHARE_DIR/bash-completion/completions" "$SHARE_DIR/zsh/site-functions" \
+    "$SHARE_DIR/fish/vendor_completions.d" "$SHARE_DIR/man/man1"
+"$INSTALL_DIR/$BIN_NAME" completions bash | sudo tee "$SHARE_DIR/bash-completion/completions/$BIN_NAME" > /dev/null
+"$INSTALL_DIR/$BIN_NAME" completions zsh | sudo tee "$SHARE_DIR/zsh/site-functions/_$BIN_NAME" > /dev/null
+"$INSTALL_DIR/$BIN_NAME" completions fish | sudo tee "$SHARE_DIR/fish/vendor_completions.d/$BIN_NAME.fish" > /dev/null
+"$INSTALL_DIR/$BIN_NAME" manpage | sudo tee "$SHARE_DIR/man/man1/$BIN_NAME.1" > /dev/null
+echo "✓ Installed shell completions and man page"
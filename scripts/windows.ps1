@@ -29,5 +29,16 @@ Invoke-WebRequest -Uri $DOWNLOAD_URL -OutFile $TMP_FILE
 Move-Item -Path $TMP_FILE -Destination "$INSTALL_DIR\$BIN_NAME" -Force
 
 Write-Host "✓ Installed to $INSTALL_DIR\$BIN_NAME" -ForegroundColor Green
+
+# PowerShell completions, loaded from the user's profile
+$COMPLETION_FILE = "$INSTALL_DIR\cascolor-completion.ps1"
+& "$INSTALL_DIR\$BIN_NAME" completions powershell | Out-File -FilePath $COMPLETION_FILE -Encoding utf8
+if (!(Test-Path $PROFILE)) {
+    New-Item -ItemType File -Path $PROFILE -Force | Out-Null
+}
+if (!(Select-String -Path $PROFILE -Pattern "cascolor-completion.ps1" -Quiet)) {
+    Add-Content -Path $PROFILE -Value ". `"$COMPLETION_FILE`""
+}
+Write-Host "✓ Installed PowerShell completions to $COMPLETION_FILE" -ForegroundColor Green
 Write-Host ""
 Write-Host "Add to PATH: `$env:PATH += `";$INSTALL_DIR`"" -ForegroundColor Yellow
@@ -0,0 +1,315 @@
+// Batch conversion of color lists (plain lines, CSV or TSV) into tables
+
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use crate::color::{nearest_name, parse_color, CasColor, ColorFormat};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchColumn {
+    Input,
+    Format(ColorFormat),
+    Name,
+    Luminance,
+    ContrastWhite,
+    ContrastBlack,
+}
+
+impl BatchColumn {
+    pub fn key(&self) -> &'static str {
+        match self {
+            BatchColumn::Input => "input",
+            BatchColumn::Format(format) => format.key(),
+            BatchColumn::Name => "name",
+            BatchColumn::Luminance => "luminance",
+            BatchColumn::ContrastWhite => "contrast-white",
+            BatchColumn::ContrastBlack => "contrast-black",
+        }
+    }
+
+    fn value(&self, row: &BatchRow) -> CellValue {
+        let white = CasColor::from_rgb(255, 255, 255);
+        let black = CasColor::from_rgb(0, 0, 0);
+
+        match self {
+            BatchColumn::Input => CellValue::Text(row.input.clone()),
+            BatchColumn::Format(format) => CellValue::Text(row.color.format(*format)),
+            BatchColumn::Name => CellValue::Text(nearest_name(&row.color).0.name.to_string()),
+            BatchColumn::Luminance => CellValue::Number(row.color.relative_luminance(), 4),
+            BatchColumn::ContrastWhite => CellValue::Number(row.color.contrast_ratio(&white), 2),
+            BatchColumn::ContrastBlack => CellValue::Number(row.color.contrast_ratio(&black), 2),
+        }
+    }
+}
+
+impl fmt::Display for BatchColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for BatchColumn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        match s.as_str() {
+            "input" => Ok(BatchColumn::Input),
+            "name" => Ok(BatchColumn::Name),
+            "luminance" => Ok(BatchColumn::Luminance),
+            "contrast-white" => Ok(BatchColumn::ContrastWhite),
+            "contrast-black" => Ok(BatchColumn::ContrastBlack),
+            _ => s
+                .parse::<ColorFormat>()
+                .map(BatchColumn::Format)
+                .map_err(|_| format!("Unknown column: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Csv,
+    Tsv,
+    Jsonl,
+    Markdown,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "csv" => Ok(OutputFormat::Csv),
+            "tsv" => Ok(OutputFormat::Tsv),
+            "jsonl" | "ndjson" => Ok(OutputFormat::Jsonl),
+            "markdown" | "md" => Ok(OutputFormat::Markdown),
+            other => Err(format!("Unknown output format: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnSelector {
+    // Zero-based column index
+    Index(usize),
+    // Header name, matched case-insensitively
+    Name(String),
+}
+
+impl FromStr for ColumnSelector {
+    type Err = String;
+
+    // Numbers are one-based on the command line, like cut(1)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<usize>() {
+            Ok(0) => Err("Column numbers start at 1".to_string()),
+            Ok(n) => Ok(ColumnSelector::Index(n - 1)),
+            Err(_) => Ok(ColumnSelector::Name(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchInput {
+    // Field delimiter; None treats each whole line as one color
+    pub delimiter: Option<char>,
+    pub column: ColumnSelector,
+    pub has_header: bool,
+}
+
+impl Default for BatchInput {
+    fn default() -> Self {
+        Self {
+            delimiter: None,
+            column: ColumnSelector::Index(0),
+            has_header: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchRow {
+    // One-based line number in the input
+    pub line: usize,
+    pub input: String,
+    pub color: CasColor,
+}
+
+#[derive(Debug)]
+pub enum BatchError {
+    IoError(io::Error),
+    MissingColumn(String),
+    InvalidColor { line: usize, message: String },
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchError::IoError(e) => write!(f, "IO error: {}", e),
+            BatchError::MissingColumn(name) => write!(f, "Column not found in header: {}", name),
+            BatchError::InvalidColor { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+impl From<io::Error> for BatchError {
+    fn from(e: io::Error) -> Self {
+        BatchError::IoError(e)
+    }
+}
+
+// Read colors from `reader`. Blank lines and comment lines ("# ..." or
+// "// ...") are skipped; unparseable colors are returned as errors alongside
+// the rows that did parse.
+pub fn read_colors<R: BufRead>(reader: R, input: &BatchInput) -> Result<(Vec<BatchRow>, Vec<BatchError>), BatchError> {
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    let mut column = match &input.column {
+        ColumnSelector::Index(index) => Some(*index),
+        ColumnSelector::Name(_) => None,
+    };
+    let mut header_pending = input.has_header || column.is_none();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line_number = index + 1;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with("# ") || trimmed.starts_with("//") {
+            continue;
+        }
+
+        let fields = match input.delimiter {
+            Some(delimiter) => split_fields(&line, delimiter),
+            None => vec![trimmed.to_string()],
+        };
+
+        if header_pending {
+            header_pending = false;
+            if let ColumnSelector::Name(name) = &input.column {
+                let position = fields.iter().position(|f| f.trim().eq_ignore_ascii_case(name.trim()));
+                column = Some(position.ok_or_else(|| BatchError::MissingColumn(name.clone()))?);
+            }
+            continue;
+        }
+
+        let value = fields.get(column.unwrap_or(0)).map(|f| f.trim()).unwrap_or("");
+        match parse_color(value) {
+            Ok(color) => rows.push(BatchRow { line: line_number, input: value.to_string(), color }),
+            Err(message) => errors.push(BatchError::InvalidColor { line: line_number, message }),
+        }
+    }
+
+    Ok((rows, errors))
+}
+
+// Split one CSV/TSV record, honouring double-quoted fields with "" escapes
+fn split_fields(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+
+    fields.push(field);
+    fields
+}
+
+enum CellValue {
+    Text(String),
+    // Value and number of decimal places
+    Number(f32, usize),
+}
+
+impl CellValue {
+    fn to_text(&self) -> String {
+        match self {
+            CellValue::Text(text) => text.clone(),
+            CellValue::Number(value, places) => format!("{:.*}", places, value),
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            CellValue::Text(text) => serde_json::Value::String(text.clone()),
+            CellValue::Number(..) => self
+                .to_text()
+                .parse::<serde_json::Number>()
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+        }
+    }
+}
+
+pub fn write_table<W: Write>(
+    writer: &mut W,
+    rows: &[BatchRow],
+    columns: &[BatchColumn],
+    format: OutputFormat,
+) -> io::Result<()> {
+    let header: Vec<&str> = columns.iter().map(|c| c.key()).collect();
+
+    match format {
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let delimiter = if format == OutputFormat::Csv { ',' } else { '\t' };
+            let join = |cells: Vec<String>| {
+                cells
+                    .iter()
+                    .map(|cell| quote_field(cell, delimiter))
+                    .collect::<Vec<_>>()
+                    .join(&delimiter.to_string())
+            };
+
+            writeln!(writer, "{}", join(header.iter().map(|h| h.to_string()).collect()))?;
+            for row in rows {
+                writeln!(writer, "{}", join(columns.iter().map(|c| c.value(row).to_text()).collect()))?;
+            }
+        }
+        OutputFormat::Jsonl => {
+            // Built by hand so keys keep the requested column order
+            for row in rows {
+                let fields: Vec<String> = columns
+                    .iter()
+                    .map(|c| format!("{}:{}", serde_json::Value::from(c.key()), c.value(row).to_json()))
+                    .collect();
+                writeln!(writer, "{{{}}}", fields.join(","))?;
+            }
+        }
+        OutputFormat::Markdown => {
+            writeln!(writer, "| {} |", header.join(" | "))?;
+            writeln!(writer, "|{}", "---|".repeat(columns.len()))?;
+            for row in rows {
+                let cells: Vec<String> = columns
+                    .iter()
+                    .map(|c| c.value(row).to_text().replace('|', "\\|"))
+                    .collect();
+                writeln!(writer, "| {} |", cells.join(" | "))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn quote_field(field: &str, delimiter: char) -> String {
+    if field.contains(delimiter) || field.contains('"') || field.contains('\n') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,551 @@
+// Command-line interface definitions
+//
+// Help text, shell completions and the man page are all generated from
+// these definitions, so they stay in sync with the parser.
+
+use clap::builder::{PossibleValuesParser, RangedU64ValueParser, TypedValueParser};
+use clap::{Parser, Subcommand};
+use clap_complete::Shell;
+use std::path::PathBuf;
+
+use crate::batch::{BatchColumn, ColumnSelector, OutputFormat};
+use crate::color::{ColorFormat, ColorSyntax, Harmony};
+use crate::display::UiMode;
+use crate::exit::ErrorFormat;
+use crate::export::{ExportFormat, NameCase, PaletteOutput};
+use crate::extract::{Averaging, ExtractMethod, DEFAULT_COUNT, DEFAULT_MIN_ALPHA, DEFAULT_MIN_DISTANCE};
+use crate::palette::PaletteFormat;
+use crate::sheet::SheetFormat;
+use crate::theme::{ThemeFormat, DEFAULT_MIN_CONTRAST};
+
+const LONG_ABOUT: &str = "\
+A beautiful color picker with support for HEX, RGB, HSL, HSV, CMYK, Lab,
+LCh, XYZ, Oklab, Oklch and CSS named colors. Features eyedropper tool,
+color history, and cross-platform support.
+
+Automatically detects display environment (override with --gui, --tui,
+--headless or `mode` in the [ui] config section):
+  - GUI mode: When a display connection succeeds (X11/Wayland/Windows/macOS,
+    including X forwarding over SSH)
+  - TUI mode: When there is no display but stdout is a terminal
+  - Headless: Neither; no UI is started
+
+Color previews use 24-bit color when COLORTERM=truecolor, falling back to
+256 or 16 colors, and plain text when stdout is not a terminal or NO_COLOR
+is set.";
+
+const AFTER_HELP: &str = "\
+Exit codes:
+  0  success                 5  clipboard unavailable
+  1  unexpected failure      6  network error
+  2  usage error             7  terminal UI error
+  3  parse error             8  update available (--update)
+  4  config error
+
+Config:
+  Linux/BSD:  ~/.config/casapps/cascolor/config.toml
+  macOS:      ~/Library/Application Support/casapps/cascolor/config.toml
+  Windows:    %APPDATA%\\casapps\\cascolor\\config.toml
+  Elsewhere:  --config <PATH>, or config.toml in $CASCOLOR_CONFIG_DIR
+  Portable:   with a cascolor.portable file next to the executable, config,
+              history and palettes are kept in cascolor-data beside it
+
+History:
+  history.jsonl in the platform data directory (cascolor history path)
+
+For more information, visit: https://github.com/casapps/cascolor";
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "cascolor",
+    version,
+    about = "Beautiful cross-platform color picker",
+    long_about = LONG_ABOUT,
+    after_help = AFTER_HELP,
+    disable_version_flag = true
+)]
+pub struct Cli {
+    /// Show version information
+    #[arg(long)]
+    pub version: bool,
+
+    /// Check for updates (stable, beta, daily)
+    #[arg(long, value_name = "CHANNEL", num_args = 0..=1, default_missing_value = "stable")]
+    pub update: Option<String>,
+
+    /// Force the terminal UI
+    #[arg(long, conflicts_with_all = ["gui", "headless"])]
+    pub tui: bool,
+
+    /// Force the graphical UI
+    #[arg(long, conflicts_with_all = ["tui", "headless"])]
+    pub gui: bool,
+
+    /// Do not start any UI
+    #[arg(long, conflicts_with_all = ["tui", "gui"])]
+    pub headless: bool,
+
+    /// Palette file, or an image to extract colors from, to show in the UI instead of the built-in grid
+    #[arg(long, value_name = "FILE")]
+    pub palette: Option<PathBuf>,
+
+    /// Emit machine-readable JSON instead of text
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// How to print errors on stderr (text, json)
+    #[arg(
+        long,
+        global = true,
+        value_name = "FORMAT",
+        default_value = "text",
+        value_parser = PossibleValuesParser::new(["text", "json"]).map(|s| s.parse::<ErrorFormat>().unwrap())
+    )]
+    pub error_format: ErrorFormat,
+
+    /// Use this config file instead of the default one
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Override a config key for this run (e.g. --set ui.mode=tui); repeatable
+    #[arg(long = "set", global = true, value_name = "KEY=VALUE", value_parser = key_value)]
+    pub set: Vec<(String, String)>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl Cli {
+    // UI mode forced on the command line, if any
+    pub fn requested_mode(&self) -> Option<UiMode> {
+        if self.tui {
+            Some(UiMode::Tui)
+        } else if self.gui {
+            Some(UiMode::Gui)
+        } else if self.headless {
+            Some(UiMode::Headless)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Show every representation of a color
+    Info {
+        /// Color in any supported format (e.g. '#ff5733', 'rgb(255, 87, 51)', tomato)
+        color: String,
+    },
+
+    /// Rewrite every color literal in text read from stdin
+    Filter {
+        /// Target format
+        #[arg(long, value_parser = format_parser())]
+        to: ColorFormat,
+
+        /// Input syntaxes to rewrite, comma separated (default: all except named)
+        #[arg(long = "match", value_name = "SYNTAXES", value_delimiter = ',', value_parser = syntax_parser())]
+        syntaxes: Vec<ColorSyntax>,
+    },
+
+    /// Convert a list of colors into a table
+    Batch {
+        /// Input file, one color per line or CSV/TSV (default: stdin)
+        file: Option<PathBuf>,
+
+        /// Column holding the color: 1-based number or header name
+        #[arg(long)]
+        column: Option<ColumnSelector>,
+
+        /// Field delimiter (default: ',' for .csv, tab for .tsv, whole line otherwise)
+        #[arg(long)]
+        delimiter: Option<char>,
+
+        /// Skip the first record as a header row
+        #[arg(long)]
+        header: bool,
+
+        /// Output columns, comma separated: input, hex, rgb, hsl, hsv, cmyk, lab,
+        /// lch, xyz, oklab, oklch, name, luminance, contrast-white, contrast-black
+        #[arg(long, value_delimiter = ',', default_value = "input,hex,rgb,name")]
+        columns: Vec<BatchColumn>,
+
+        /// Output format
+        #[arg(
+            long,
+            default_value = "csv",
+            value_parser = PossibleValuesParser::new(["csv", "tsv", "jsonl", "markdown"])
+                .map(|s| s.parse::<OutputFormat>().unwrap())
+        )]
+        output: OutputFormat,
+    },
+
+    /// Preview colors as swatches (the built-in palette grid without arguments)
+    Palette {
+        /// Colors in any supported format
+        colors: Vec<String>,
+
+        /// Preview the colors of a palette file
+        #[arg(long, value_name = "FILE", conflicts_with = "colors")]
+        file: Option<PathBuf>,
+    },
+
+    /// Convert a palette file to another format (chosen by extension)
+    Convert {
+        /// Palette to read, or - for stdin
+        input: PathBuf,
+
+        /// Palette to write, or - for stdout
+        output: PathBuf,
+
+        /// Input format (default: from the input extension)
+        #[arg(long, value_parser = palette_format_parser())]
+        from: Option<PaletteFormat>,
+
+        /// Output format (default: from the output extension)
+        #[arg(long, value_parser = palette_format_parser())]
+        to: Option<PaletteFormat>,
+    },
+
+    /// Export a palette file or the color history as code
+    Export {
+        /// Palette file to export (.gpl, .ase, .aco, .tokens.json)
+        #[arg(required_unless_present = "history")]
+        file: Option<PathBuf>,
+
+        /// Export the color history instead of a palette file
+        #[arg(long, conflicts_with = "file")]
+        history: bool,
+
+        /// Output format
+        #[arg(long, short = 'f', value_parser = export_format_parser())]
+        format: ExportFormat,
+
+        /// Naming scheme for variables (default: the format's convention)
+        #[arg(long, value_parser = naming_parser())]
+        naming: Option<NameCase>,
+
+        /// Prefix added to every variable name
+        #[arg(long, default_value = "")]
+        prefix: String,
+
+        /// Format of the color values (stylesheet and JavaScript formats)
+        #[arg(long, default_value = "hex", value_parser = format_parser())]
+        color_format: ColorFormat,
+
+        /// Write to a file instead of stdout (a directory for xcassets)
+        #[arg(long, short = 'o', value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Generate a terminal color scheme from a 16-color palette file
+    Theme {
+        /// Palette with the 16 ANSI colors, plus optional background, foreground, cursor and selection
+        file: PathBuf,
+
+        /// Output format
+        #[arg(long, short = 'f', value_parser = theme_format_parser())]
+        format: ThemeFormat,
+
+        /// Scheme name (default: the palette's name)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Minimum contrast ratio of the ANSI colors against the background
+        #[arg(long, value_name = "RATIO", default_value_t = DEFAULT_MIN_CONTRAST)]
+        min_contrast: f32,
+
+        /// Fail instead of warning when a color is below the minimum contrast or left unused
+        #[arg(long)]
+        strict: bool,
+
+        /// Write to a file instead of stdout
+        #[arg(long, short = 'o', value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Render a palette, color harmony or scale as a PNG or SVG swatch sheet
+    Sheet {
+        /// Palette file to render
+        #[arg(required_unless_present_any = ["harmony", "scale"], conflicts_with_all = ["harmony", "scale"])]
+        file: Option<PathBuf>,
+
+        /// Render this harmony of --base instead of a palette file
+        #[arg(long, requires = "base", conflicts_with = "scale", value_parser = harmony_parser())]
+        harmony: Option<Harmony>,
+
+        /// Base color for --harmony
+        #[arg(long, value_name = "COLOR", requires = "harmony")]
+        base: Option<String>,
+
+        /// Render a scale between two colors instead of a palette file
+        #[arg(long, num_args = 2, value_names = ["FROM", "TO"])]
+        scale: Vec<String>,
+
+        /// Number of colors in the --scale
+        #[arg(long, default_value_t = 9, value_parser = RangedU64ValueParser::<usize>::new().range(2..=64))]
+        steps: usize,
+
+        /// Swatches per row (default: 4)
+        #[arg(long, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        columns: Option<usize>,
+
+        /// Swatch width in pixels
+        #[arg(long, default_value_t = 264, value_parser = clap::value_parser!(u32).range(16..=4096))]
+        swatch_width: u32,
+
+        /// Swatch height in pixels
+        #[arg(long, default_value_t = 120, value_parser = clap::value_parser!(u32).range(16..=4096))]
+        swatch_height: u32,
+
+        /// Space between swatches in pixels
+        #[arg(long, default_value_t = 16, value_parser = clap::value_parser!(u32).range(0..=512))]
+        gap: u32,
+
+        /// Text size as a multiple of the 5x7 pixel font
+        #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u32).range(1..=8))]
+        text_scale: u32,
+
+        /// Add WCAG contrast badges for white and black text on each swatch
+        #[arg(long)]
+        contrast: bool,
+
+        /// Image format (default: from the output extension)
+        #[arg(long, short = 'f', value_parser = sheet_format_parser())]
+        format: Option<SheetFormat>,
+
+        /// File to write (.png or .svg), or - for stdout
+        #[arg(long, short = 'o', value_name = "FILE")]
+        output: PathBuf,
+    },
+
+    /// Extract the dominant colors of an image as a palette
+    Extract {
+        /// Image to read (PNG, JPEG, GIF, BMP, WebP, TIFF, TGA)
+        image: PathBuf,
+
+        /// Maximum number of colors
+        #[arg(long, short = 'n', default_value_t = DEFAULT_COUNT, value_parser = RangedU64ValueParser::<usize>::new().range(1..=256))]
+        count: usize,
+
+        /// Clustering method
+        #[arg(long, default_value = "kmeans", value_parser = extract_method_parser())]
+        method: ExtractMethod,
+
+        /// Ignore pixels with a lower alpha (0 counts transparent pixels too)
+        #[arg(long, value_name = "ALPHA", default_value_t = DEFAULT_MIN_ALPHA)]
+        min_alpha: u8,
+
+        /// Merge colors closer than this Oklab distance (0 keeps near-duplicates)
+        #[arg(long, value_name = "DISTANCE", default_value_t = DEFAULT_MIN_DISTANCE)]
+        min_distance: f32,
+
+        /// Palette or export format (default: a swatch list, or from the output extension)
+        #[arg(long, short = 'f', value_parser = palette_output_parser())]
+        format: Option<PaletteOutput>,
+
+        /// Write to a file instead of stdout
+        #[arg(long, short = 'o', value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Read the color at a pixel of an image, or its average over a rectangle or circle
+    Sample {
+        /// Image to read (PNG, JPEG, GIF, BMP, WebP, TIFF, TGA)
+        image: PathBuf,
+
+        /// Pixel to read, counted from the top-left corner
+        #[arg(
+            long,
+            value_name = "X,Y",
+            value_parser = numbers::<2>,
+            required_unless_present_any = ["rect", "circle"],
+            conflicts_with_all = ["rect", "circle"]
+        )]
+        at: Option<[u32; 2]>,
+
+        /// Average a rectangle, clipped to the image
+        #[arg(long, value_name = "X,Y,W,H", value_parser = numbers::<4>, conflicts_with = "circle")]
+        rect: Option<[u32; 4]>,
+
+        /// Average the pixels within a radius of a center pixel
+        #[arg(long, value_name = "X,Y,R", value_parser = numbers::<3>)]
+        circle: Option<[u32; 3]>,
+
+        /// Average in linear light or on the encoded sRGB values
+        #[arg(long, default_value = "linear", value_parser = averaging_parser())]
+        average: Averaging,
+
+        /// Output format
+        #[arg(long, default_value = "hex", value_parser = format_parser())]
+        to: ColorFormat,
+    },
+
+    /// Inspect and validate the configuration file
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+
+    /// List, pin and clear the color history
+    History {
+        #[command(subcommand)]
+        command: HistoryCommand,
+    },
+
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Target shell
+        shell: Shell,
+    },
+
+    /// Print the man page (roff) to stdout
+    Manpage,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Report invalid values and unknown keys with their line and column
+    Check {
+        /// Config file to check (default: the user config)
+        file: Option<PathBuf>,
+    },
+
+    /// Print the value of a key (e.g. ui.default_color_format), defaults included
+    Get {
+        /// Dotted key, or a section name for all of its keys
+        key: String,
+    },
+
+    /// Set a key, keeping the file's comments and formatting
+    Set {
+        /// Dotted key (e.g. general.theme)
+        key: String,
+        /// New value (e.g. light)
+        value: String,
+    },
+
+    /// Open the config file in $VISUAL or $EDITOR and check it on save
+    Edit,
+
+    /// Print the effective config after every layer is applied
+    Show {
+        /// Also print where each value came from (default, system, user, project, env or cli)
+        #[arg(long)]
+        origin: bool,
+    },
+
+    /// Print the config file location
+    Path,
+
+    /// Restore defaults, for one key or the whole file (the old file is kept as config.toml.bak)
+    Reset {
+        /// Dotted key or section to reset (default: everything)
+        key: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum HistoryCommand {
+    /// Print the history, newest first
+    List {
+        /// Only pinned colors
+        #[arg(long)]
+        pinned: bool,
+    },
+
+    /// Add a color, as if it had been picked
+    Add {
+        /// Color in any supported format
+        color: String,
+    },
+
+    /// Keep a color when the history is trimmed or cleared
+    Pin {
+        /// Color in any supported format
+        color: String,
+    },
+
+    /// Let a pinned color be trimmed and cleared again
+    Unpin {
+        /// Color in any supported format
+        color: String,
+    },
+
+    /// Remove every color except the pinned ones
+    Clear {
+        /// Remove pinned colors too
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Import the color_history array of a config file from an older version
+    Import {
+        /// Config file (default: the user config)
+        file: Option<PathBuf>,
+    },
+
+    /// Print the history file location
+    Path,
+}
+
+fn key_value(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => Ok((key.trim().to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VALUE, got '{}'", s)),
+    }
+}
+
+fn format_parser() -> impl TypedValueParser<Value = ColorFormat> {
+    PossibleValuesParser::new(ColorFormat::ALL.map(|f| f.key())).map(|s| s.parse::<ColorFormat>().unwrap())
+}
+
+fn syntax_parser() -> impl TypedValueParser<Value = ColorSyntax> {
+    PossibleValuesParser::new(ColorSyntax::ALL.map(|s| s.key())).map(|s| s.parse::<ColorSyntax>().unwrap())
+}
+
+fn palette_format_parser() -> impl TypedValueParser<Value = PaletteFormat> {
+    PossibleValuesParser::new(PaletteFormat::ALL.map(|f| f.key())).map(|s| s.parse::<PaletteFormat>().unwrap())
+}
+
+fn export_format_parser() -> impl TypedValueParser<Value = ExportFormat> {
+    PossibleValuesParser::new(ExportFormat::ALL.map(|f| f.key())).map(|s| s.parse::<ExportFormat>().unwrap())
+}
+
+fn theme_format_parser() -> impl TypedValueParser<Value = ThemeFormat> {
+    PossibleValuesParser::new(ThemeFormat::ALL.map(|f| f.key())).map(|s| s.parse::<ThemeFormat>().unwrap())
+}
+
+fn harmony_parser() -> impl TypedValueParser<Value = Harmony> {
+    PossibleValuesParser::new(Harmony::ALL.map(|h| h.key())).map(|s| s.parse::<Harmony>().unwrap())
+}
+
+fn sheet_format_parser() -> impl TypedValueParser<Value = SheetFormat> {
+    PossibleValuesParser::new(SheetFormat::ALL.map(|f| f.key())).map(|s| s.parse::<SheetFormat>().unwrap())
+}
+
+fn extract_method_parser() -> impl TypedValueParser<Value = ExtractMethod> {
+    PossibleValuesParser::new(ExtractMethod::ALL.map(|m| m.key())).map(|s| s.parse::<ExtractMethod>().unwrap())
+}
+
+fn palette_output_parser() -> impl TypedValueParser<Value = PaletteOutput> {
+    PossibleValuesParser::new(PaletteOutput::keys()).map(|s| s.parse::<PaletteOutput>().unwrap())
+}
+
+fn averaging_parser() -> impl TypedValueParser<Value = Averaging> {
+    PossibleValuesParser::new(Averaging::ALL.map(|a| a.key())).map(|s| s.parse::<Averaging>().unwrap())
+}
+
+// N comma-separated non-negative integers, e.g. "120,45"
+fn numbers<const N: usize>(s: &str) -> Result<[u32; N], String> {
+    let values: Vec<u32> = s
+        .split(',')
+        .map(|part| part.trim().parse::<u32>().map_err(|_| format!("{:?} is not a non-negative integer", part.trim())))
+        .collect::<Result<_, _>>()?;
+    values.try_into().map_err(|values: Vec<u32>| format!("expected {} comma-separated numbers, got {}", N, values.len()))
+}
+
+fn naming_parser() -> impl TypedValueParser<Value = NameCase> {
+    PossibleValuesParser::new(NameCase::ALL.map(|c| c.key())).map(|s| s.parse::<NameCase>().unwrap())
+}
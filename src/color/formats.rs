@@ -0,0 +1,231 @@
+// String formatting and parsing for all supported color formats
+
+use super::{names, CasColor, ColorFormat};
+
+impl CasColor {
+    pub fn format(&self, format: ColorFormat) -> String {
+        match format {
+            ColorFormat::Hex => self.to_hex(),
+            ColorFormat::Rgb => {
+                let (r, g, b) = self.to_rgb();
+                format!("rgb({}, {}, {})", r, g, b)
+            }
+            ColorFormat::Hsl => {
+                let (h, s, l) = self.to_hsl();
+                format!("hsl({:.0}, {:.0}%, {:.0}%)", h, s * 100.0, l * 100.0)
+            }
+            ColorFormat::Hsv => {
+                let (h, s, v) = self.to_hsv();
+                format!("hsv({:.0}, {:.0}%, {:.0}%)", h, s * 100.0, v * 100.0)
+            }
+            ColorFormat::Cmyk => {
+                let (c, m, y, k) = self.to_cmyk();
+                format!("cmyk({:.0}%, {:.0}%, {:.0}%, {:.0}%)", c * 100.0, m * 100.0, y * 100.0, k * 100.0)
+            }
+            ColorFormat::Lab => {
+                let (l, a, b) = self.to_lab();
+                format!("lab({:.2}% {:.2} {:.2})", l, a, b)
+            }
+            ColorFormat::Lch => {
+                let (l, c, h) = self.to_lch();
+                format!("lch({:.2}% {:.2} {:.2})", l, c, h)
+            }
+            ColorFormat::Xyz => {
+                let (x, y, z) = self.to_xyz();
+                format!("color(xyz-d65 {:.4} {:.4} {:.4})", x, y, z)
+            }
+            ColorFormat::Oklab => {
+                let (l, a, b) = self.to_oklab();
+                format!("oklab({:.2}% {:.4} {:.4})", l * 100.0, a, b)
+            }
+            ColorFormat::Oklch => {
+                let (l, c, h) = self.to_oklch();
+                format!("oklch({:.2}% {:.4} {:.2})", l * 100.0, c, h)
+            }
+        }
+    }
+
+    // Format with an alpha channel. Opaque colors format exactly like format().
+    pub fn format_with_alpha(&self, format: ColorFormat, alpha: f32) -> String {
+        if alpha >= 1.0 {
+            return self.format(format);
+        }
+
+        match format {
+            ColorFormat::Hex => format!("{}{:02X}", self.to_hex(), (alpha * 255.0).round() as u8),
+            ColorFormat::Rgb => {
+                let (r, g, b) = self.to_rgb();
+                format!("rgba({}, {}, {}, {})", r, g, b, format_alpha(alpha))
+            }
+            ColorFormat::Hsl => {
+                let (h, s, l) = self.to_hsl();
+                format!("hsla({:.0}, {:.0}%, {:.0}%, {})", h, s * 100.0, l * 100.0, format_alpha(alpha))
+            }
+            _ => {
+                let opaque = self.format(format);
+                format!("{} / {})", opaque.trim_end_matches(')'), format_alpha(alpha))
+            }
+        }
+    }
+}
+
+// Alpha with at most three decimals and no trailing zeros (0.5, not 0.500)
+fn format_alpha(alpha: f32) -> String {
+    let text = format!("{:.3}", alpha.clamp(0.0, 1.0));
+    match text.trim_end_matches('0').trim_end_matches('.') {
+        "" => "0".to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
+
+// Parse any color string cascolor can print: hex, CSS functional notation
+// (legacy comma or modern space separated) and CSS named colors.
+pub fn parse_color(input: &str) -> Result<CasColor, String> {
+    parse_color_alpha(input).map(|(color, _)| color)
+}
+
+// Like parse_color, but also returns the alpha channel (0.0-1.0) when the
+// input carries one, e.g. #FF573380, rgba(255, 87, 51, 0.5), oklch(... / 50%).
+pub fn parse_color_alpha(input: &str) -> Result<(CasColor, Option<f32>), String> {
+    let input = input.trim();
+
+    if let Some(hex) = input.strip_prefix('#') {
+        return parse_hex_alpha(hex).ok_or_else(|| "Invalid hex color".to_string());
+    }
+
+    if let Some(open) = input.find('(') {
+        if !input.ends_with(')') {
+            return Err(format!("Unterminated color function: {}", input));
+        }
+        let name = input[..open].trim().to_ascii_lowercase();
+        let inner = &input[open + 1..input.len() - 1];
+        let color = if name == "color" {
+            parse_color_space(inner)
+        } else {
+            parse_function(&name, &parse_args(inner))
+        };
+        return color.ok_or_else(|| format!("Invalid color: {}", input));
+    }
+
+    if let Some(named) = names::lookup(input) {
+        return Ok((named, None));
+    }
+
+    // Bare hex without the leading '#'
+    if matches!(input.len(), 3 | 6) && input.chars().all(|c| c.is_ascii_hexdigit()) {
+        return CasColor::from_hex(input).map(|color| (color, None));
+    }
+
+    Err(format!("Unrecognized color: {}", input))
+}
+
+fn parse_hex_alpha(hex: &str) -> Option<(CasColor, Option<f32>)> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let (rgb, alpha) = match hex.len() {
+        3 | 6 => (hex, None),
+        4 => (&hex[..3], Some(hex[3..].repeat(2))),
+        8 => (&hex[..6], Some(hex[6..].to_string())),
+        _ => return None,
+    };
+
+    let color = CasColor::from_hex(rgb).ok()?;
+    let alpha = match alpha {
+        Some(a) => Some(u8::from_str_radix(&a, 16).ok()? as f32 / 255.0),
+        None => None,
+    };
+    Some((color, alpha))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Arg {
+    value: f32,
+    percent: bool,
+}
+
+impl Arg {
+    // Resolve to a plain number where 100% maps to `full`
+    fn scaled(&self, full: f32) -> f32 {
+        if self.percent { self.value / 100.0 * full } else { self.value }
+    }
+}
+
+fn parse_args(inner: &str) -> Vec<Option<Arg>> {
+    inner
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|token| {
+            let token = token.to_ascii_lowercase();
+            let (number, percent) = match token.strip_suffix('%') {
+                Some(n) => (n.to_string(), true),
+                None => (token.trim_end_matches("deg").to_string(), false),
+            };
+            number.parse::<f32>().ok().map(|value| Arg { value, percent })
+        })
+        .collect()
+}
+
+fn parse_function(name: &str, args: &[Option<Arg>]) -> Option<(CasColor, Option<f32>)> {
+    let args: Vec<Arg> = args.iter().copied().collect::<Option<Vec<_>>>()?;
+    let channel = |arg: &Arg| arg.scaled(255.0).round().clamp(0.0, 255.0) as u8;
+    let unit = |arg: &Arg| if arg.percent { arg.value / 100.0 } else { arg.value };
+    let alpha = |index: usize| args.get(index).map(|a| unit(a).clamp(0.0, 1.0));
+
+    let color = match (name, args.as_slice()) {
+        ("rgb" | "rgba", [r, g, b, ..]) => CasColor::from_rgb(channel(r), channel(g), channel(b)),
+        ("hsl" | "hsla", [h, s, l, ..]) => CasColor::from_hsl(
+            h.value.rem_euclid(360.0),
+            (s.value / 100.0).clamp(0.0, 1.0),
+            (l.value / 100.0).clamp(0.0, 1.0),
+        ),
+        ("hsv" | "hsb", [h, s, v, ..]) => CasColor::from_hsv(
+            h.value.rem_euclid(360.0),
+            (s.value / 100.0).clamp(0.0, 1.0),
+            (v.value / 100.0).clamp(0.0, 1.0),
+        ),
+        ("cmyk" | "device-cmyk", [c, m, y, k, ..]) => {
+            return Some((CasColor::from_cmyk(unit(c), unit(m), unit(y), unit(k)), alpha(4)));
+        }
+        ("lab", [l, a, b, ..]) => CasColor::from_lab(l.value, a.scaled(125.0), b.scaled(125.0)),
+        ("lch", [l, c, h, ..]) => CasColor::from_lch(l.value, c.scaled(150.0), h.value),
+        ("oklab", [l, a, b, ..]) => CasColor::from_oklab(unit(l), a.scaled(0.4), b.scaled(0.4)),
+        ("oklch", [l, c, h, ..]) => CasColor::from_oklch(unit(l), c.scaled(0.4), h.value),
+        _ => return None,
+    };
+
+    Some((color, alpha(3)))
+}
+
+// color(<space> c1 c2 c3 [/ alpha]), e.g. color(xyz-d65 0.41 0.21 0.02)
+fn parse_color_space(inner: &str) -> Option<(CasColor, Option<f32>)> {
+    let (components, alpha) = match inner.split_once('/') {
+        Some((components, alpha)) => (components, Some(alpha.trim())),
+        None => (inner, None),
+    };
+
+    let mut tokens = components.split_whitespace();
+    let space = tokens.next()?.to_ascii_lowercase();
+    let values: Vec<f32> = tokens.map(|t| t.parse().ok()).collect::<Option<Vec<_>>>()?;
+
+    let color = match (space.as_str(), values.as_slice()) {
+        ("xyz" | "xyz-d65", [x, y, z]) => CasColor::from_xyz(*x, *y, *z),
+        ("srgb", [r, g, b]) => CasColor::from_rgb(
+            (r * 255.0).round().clamp(0.0, 255.0) as u8,
+            (g * 255.0).round().clamp(0.0, 255.0) as u8,
+            (b * 255.0).round().clamp(0.0, 255.0) as u8,
+        ),
+        ("srgb-linear", [r, g, b]) => CasColor::from_linear_rgb(*r, *g, *b),
+        _ => return None,
+    };
+
+    let alpha = match alpha {
+        Some(a) => Some(match a.strip_suffix('%') {
+            Some(p) => p.parse::<f32>().ok()? / 100.0,
+            None => a.parse::<f32>().ok()?,
+        }),
+        None => None,
+    };
+    Some((color, alpha.map(|a| a.clamp(0.0, 1.0))))
+}
@@ -0,0 +1,84 @@
+// Color harmonies and scales, computed in OKLCH/Oklab so lightness stays even
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::CasColor;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Harmony {
+    Complementary,
+    Analogous,
+    Triadic,
+    SplitComplementary,
+    Tetradic,
+}
+
+impl Harmony {
+    pub const ALL: [Harmony; 5] = [
+        Harmony::Complementary,
+        Harmony::Analogous,
+        Harmony::Triadic,
+        Harmony::SplitComplementary,
+        Harmony::Tetradic,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Harmony::Complementary => "complementary",
+            Harmony::Analogous => "analogous",
+            Harmony::Triadic => "triadic",
+            Harmony::SplitComplementary => "split-complementary",
+            Harmony::Tetradic => "tetradic",
+        }
+    }
+
+    // Hue offsets in degrees; the base color is always first
+    fn offsets(&self) -> &'static [f32] {
+        match self {
+            Harmony::Complementary => &[0.0, 180.0],
+            Harmony::Analogous => &[0.0, -30.0, 30.0],
+            Harmony::Triadic => &[0.0, 120.0, 240.0],
+            Harmony::SplitComplementary => &[0.0, 150.0, 210.0],
+            Harmony::Tetradic => &[0.0, 90.0, 180.0, 270.0],
+        }
+    }
+
+    // Rotates the OKLCH hue; lightness and chroma are kept, so colors that
+    // fall outside sRGB are clipped
+    pub fn colors(&self, base: &CasColor) -> Vec<CasColor> {
+        let (l, c, h) = base.to_oklch();
+        self.offsets()
+            .iter()
+            .map(|offset| if *offset == 0.0 { *base } else { CasColor::from_oklch(l, c, (h + offset).rem_euclid(360.0)) })
+            .collect()
+    }
+}
+
+impl fmt::Display for Harmony {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for Harmony {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        Harmony::ALL
+            .iter()
+            .copied()
+            .find(|harmony| harmony.key() == s)
+            .ok_or_else(|| format!("Unknown harmony: {}", s))
+    }
+}
+
+// `steps` colors evenly spaced in Oklab from `from` to `to`, both included
+pub fn scale(from: &CasColor, to: &CasColor, steps: usize) -> Vec<CasColor> {
+    match steps {
+        0 => Vec::new(),
+        1 => vec![*from],
+        _ => (0..steps).map(|i| from.mix(to, i as f32 / (steps - 1) as f32)).collect(),
+    }
+}
@@ -1,14 +1,65 @@
 // Color conversion and manipulation
 
+mod formats;
+mod harmony;
+mod names;
+mod report;
+mod scan;
+
+pub use formats::{parse_color, parse_color_alpha};
+pub use harmony::{scale, Harmony};
+pub use names::{nearest_name, NamedColor, CSS_NAMED_COLORS};
+pub use report::{ColorReport, SCHEMA_VERSION};
+pub use scan::{find_colors, rewrite_colors, ColorMatch, ColorSyntax};
+
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ColorFormat {
     Hex,
     Rgb,
     Hsl,
     Hsv,
     Cmyk,
+    Lab,
+    Lch,
+    Xyz,
+    Oklab,
+    Oklch,
+}
+
+impl ColorFormat {
+    pub const ALL: [ColorFormat; 10] = [
+        ColorFormat::Hex,
+        ColorFormat::Rgb,
+        ColorFormat::Hsl,
+        ColorFormat::Hsv,
+        ColorFormat::Cmyk,
+        ColorFormat::Lab,
+        ColorFormat::Lch,
+        ColorFormat::Xyz,
+        ColorFormat::Oklab,
+        ColorFormat::Oklch,
+    ];
+
+    // Lowercase identifier used in config files, CLI arguments and JSON keys
+    pub fn key(&self) -> &'static str {
+        match self {
+            ColorFormat::Hex => "hex",
+            ColorFormat::Rgb => "rgb",
+            ColorFormat::Hsl => "hsl",
+            ColorFormat::Hsv => "hsv",
+            ColorFormat::Cmyk => "cmyk",
+            ColorFormat::Lab => "lab",
+            ColorFormat::Lch => "lch",
+            ColorFormat::Xyz => "xyz",
+            ColorFormat::Oklab => "oklab",
+            ColorFormat::Oklch => "oklch",
+        }
+    }
 }
 
 impl fmt::Display for ColorFormat {
@@ -19,11 +70,49 @@ impl fmt::Display for ColorFormat {
             ColorFormat::Hsl => write!(f, "HSL"),
             ColorFormat::Hsv => write!(f, "HSV"),
             ColorFormat::Cmyk => write!(f, "CMYK"),
+            ColorFormat::Lab => write!(f, "Lab"),
+            ColorFormat::Lch => write!(f, "LCh"),
+            ColorFormat::Xyz => write!(f, "XYZ"),
+            ColorFormat::Oklab => write!(f, "Oklab"),
+            ColorFormat::Oklch => write!(f, "Oklch"),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+impl FromStr for ColorFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        ColorFormat::ALL
+            .iter()
+            .copied()
+            .find(|f| f.key() == s)
+            .ok_or_else(|| format!("Unknown color format: {}", s))
+    }
+}
+
+// Built-in palette grid shown by the TUI and `cascolor palette`: one hue
+// per row, lightness increasing across the columns
+pub const GRID_COLS: usize = 10;
+pub const GRID_ROWS: usize = 30;
+
+pub fn grid_color(row: usize, col: usize) -> CasColor {
+    let hue = (row as f32 / GRID_ROWS as f32) * 360.0;
+    let lightness = 0.2 + (col as f32 / GRID_COLS as f32) * 0.6;
+    let saturation = 0.8;
+
+    CasColor::from_hsl(hue, saturation, lightness)
+}
+
+// D65 reference white used by the Lab/LCh conversions
+const WHITE_D65: (f32, f32, f32) = (0.95047, 1.0, 1.08883);
+// D50 reference white used by ICC/Adobe Lab values
+const WHITE_D50: (f32, f32, f32) = (0.96422, 1.0, 0.82521);
+const LAB_EPSILON: f32 = 216.0 / 24389.0;
+const LAB_KAPPA: f32 = 24389.0 / 27.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct CasColor {
     r: u8,
     g: u8,
@@ -37,10 +126,17 @@ impl CasColor {
 
     pub fn from_hex(hex: &str) -> Result<Self, String> {
         let hex = hex.trim_start_matches('#');
-        if hex.len() != 6 {
+        if !hex.is_ascii() {
             return Err("Invalid hex color".to_string());
         }
 
+        // Expand shorthand (#F53 -> #FF5533)
+        let hex = match hex.len() {
+            3 => hex.chars().flat_map(|c| [c, c]).collect::<String>(),
+            6 => hex.to_string(),
+            _ => return Err("Invalid hex color".to_string()),
+        };
+
         let r = u8::from_str_radix(&hex[0..2], 16).map_err(|_| "Invalid hex")?;
         let g = u8::from_str_radix(&hex[2..4], 16).map_err(|_| "Invalid hex")?;
         let b = u8::from_str_radix(&hex[4..6], 16).map_err(|_| "Invalid hex")?;
@@ -179,4 +275,189 @@ impl CasColor {
 
         (c, m, y, k)
     }
+
+    pub fn from_cmyk(c: f32, m: f32, y: f32, k: f32) -> Self {
+        let channel = |v: f32| (255.0 * (1.0 - v) * (1.0 - k)).round().clamp(0.0, 255.0) as u8;
+        Self::from_rgb(channel(c), channel(m), channel(y))
+    }
+
+    pub fn from_linear_rgb(r: f32, g: f32, b: f32) -> Self {
+        Self::from_rgb(encode_srgb(r), encode_srgb(g), encode_srgb(b))
+    }
+
+    pub fn from_xyz(x: f32, y: f32, z: f32) -> Self {
+        let r = 3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z;
+        let g = -0.969_266 * x + 1.876_010_8 * y + 0.041_556 * z;
+        let b = 0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z;
+        Self::from_linear_rgb(r, g, b)
+    }
+
+    pub fn from_lab(l: f32, a: f32, b: f32) -> Self {
+        let (x, y, z) = lab_to_xyz(l, a, b, WHITE_D65);
+        Self::from_xyz(x, y, z)
+    }
+
+    // Lab relative to D50, as stored by Adobe swatch files
+    pub fn from_lab_d50(l: f32, a: f32, b: f32) -> Self {
+        let (x, y, z) = lab_to_xyz(l, a, b, WHITE_D50);
+        Self::from_xyz_d50(x, y, z)
+    }
+
+    // XYZ relative to D50, adapted to D65 with the Bradford transform
+    pub fn from_xyz_d50(x: f32, y: f32, z: f32) -> Self {
+        Self::from_xyz(
+            0.955_576_6 * x - 0.023_039_3 * y + 0.063_163_6 * z,
+            -0.028_289_5 * x + 1.009_941_6 * y + 0.021_007_7 * z,
+            0.012_298_2 * x - 0.020_483 * y + 1.329_909_8 * z,
+        )
+    }
+
+    pub fn from_lch(l: f32, c: f32, h: f32) -> Self {
+        let (a, b) = polar_to_cartesian(c, h);
+        Self::from_lab(l, a, b)
+    }
+
+    pub fn from_oklab(l: f32, a: f32, b: f32) -> Self {
+        let l_ = l + 0.396_337_78 * a + 0.215_803_76 * b;
+        let m_ = l - 0.105_561_346 * a - 0.063_854_17 * b;
+        let s_ = l - 0.089_484_18 * a - 1.291_485_5 * b;
+
+        let (l, m, s) = (l_ * l_ * l_, m_ * m_ * m_, s_ * s_ * s_);
+
+        Self::from_linear_rgb(
+            4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+            -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+            -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+        )
+    }
+
+    pub fn from_oklch(l: f32, c: f32, h: f32) -> Self {
+        let (a, b) = polar_to_cartesian(c, h);
+        Self::from_oklab(l, a, b)
+    }
+
+    pub fn to_linear_rgb(&self) -> (f32, f32, f32) {
+        (decode_srgb(self.r), decode_srgb(self.g), decode_srgb(self.b))
+    }
+
+    pub fn to_xyz(&self) -> (f32, f32, f32) {
+        let (r, g, b) = self.to_linear_rgb();
+
+        (
+            0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b,
+            0.212_672_9 * r + 0.715_152_2 * g + 0.072_175 * b,
+            0.019_333_9 * r + 0.119_192 * g + 0.950_304_1 * b,
+        )
+    }
+
+    pub fn to_lab(&self) -> (f32, f32, f32) {
+        let (x, y, z) = self.to_xyz();
+
+        let f = |t: f32| {
+            if t > LAB_EPSILON { t.cbrt() } else { (LAB_KAPPA * t + 16.0) / 116.0 }
+        };
+        let fx = f(x / WHITE_D65.0);
+        let fy = f(y / WHITE_D65.1);
+        let fz = f(z / WHITE_D65.2);
+
+        (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+    }
+
+    pub fn to_lch(&self) -> (f32, f32, f32) {
+        let (l, a, b) = self.to_lab();
+        let (c, h) = cartesian_to_polar(a, b);
+        (l, c, h)
+    }
+
+    pub fn to_oklab(&self) -> (f32, f32, f32) {
+        let (r, g, b) = self.to_linear_rgb();
+
+        let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+
+        (
+            0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        )
+    }
+
+    pub fn to_oklch(&self) -> (f32, f32, f32) {
+        let (l, a, b) = self.to_oklab();
+        let (c, h) = cartesian_to_polar(a, b);
+        (l, c, h)
+    }
+
+    // WCAG 2.x relative luminance
+    pub fn relative_luminance(&self) -> f32 {
+        let (r, g, b) = self.to_linear_rgb();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    // WCAG 2.x contrast ratio, from 1.0 (identical) to 21.0 (black on white)
+    pub fn contrast_ratio(&self, other: &CasColor) -> f32 {
+        let a = self.relative_luminance();
+        let b = other.relative_luminance();
+        let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+        (lighter + 0.05) / (darker + 0.05)
+    }
+
+    // Perceptual distance (Euclidean distance in Oklab)
+    pub fn distance(&self, other: &CasColor) -> f32 {
+        let (l1, a1, b1) = self.to_oklab();
+        let (l2, a2, b2) = other.to_oklab();
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+
+    // Interpolate in Oklab: 0.0 is self, 1.0 is other
+    pub fn mix(&self, other: &CasColor, amount: f32) -> Self {
+        let (l1, a1, b1) = self.to_oklab();
+        let (l2, a2, b2) = other.to_oklab();
+        let t = amount.clamp(0.0, 1.0);
+        Self::from_oklab(l1 + (l2 - l1) * t, a1 + (a2 - a1) * t, b1 + (b2 - b1) * t)
+    }
+}
+
+fn lab_to_xyz(l: f32, a: f32, b: f32, white: (f32, f32, f32)) -> (f32, f32, f32) {
+    let fy = (l + 16.0) / 116.0;
+    let fx = fy + a / 500.0;
+    let fz = fy - b / 200.0;
+
+    let finv = |t: f32| {
+        let t3 = t * t * t;
+        if t3 > LAB_EPSILON { t3 } else { (116.0 * t - 16.0) / LAB_KAPPA }
+    };
+    let y = if l > LAB_KAPPA * LAB_EPSILON { fy * fy * fy } else { l / LAB_KAPPA };
+
+    (finv(fx) * white.0, y * white.1, finv(fz) * white.2)
+}
+
+fn decode_srgb(channel: u8) -> f32 {
+    let c = channel as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn encode_srgb(linear: f32) -> u8 {
+    let c = if linear <= 0.003_130_8 {
+        12.92 * linear
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    };
+    (c * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+fn cartesian_to_polar(a: f32, b: f32) -> (f32, f32) {
+    let c = (a * a + b * b).sqrt();
+    let h = b.atan2(a).to_degrees();
+    (c, if h < 0.0 { h + 360.0 } else { h })
+}
+
+fn polar_to_cartesian(c: f32, h: f32) -> (f32, f32) {
+    let rad = h.to_radians();
+    (c * rad.cos(), c * rad.sin())
 }
@@ -0,0 +1,186 @@
+// CSS named colors (CSS Color Module Level 4)
+
+use super::CasColor;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NamedColor {
+    pub name: &'static str,
+    pub rgb: (u8, u8, u8),
+}
+
+impl NamedColor {
+    pub fn color(&self) -> CasColor {
+        let (r, g, b) = self.rgb;
+        CasColor::from_rgb(r, g, b)
+    }
+}
+
+pub const CSS_NAMED_COLORS: [NamedColor; 148] = [
+    NamedColor { name: "aliceblue", rgb: (0xf0, 0xf8, 0xff) },
+    NamedColor { name: "antiquewhite", rgb: (0xfa, 0xeb, 0xd7) },
+    NamedColor { name: "aqua", rgb: (0x00, 0xff, 0xff) },
+    NamedColor { name: "aquamarine", rgb: (0x7f, 0xff, 0xd4) },
+    NamedColor { name: "azure", rgb: (0xf0, 0xff, 0xff) },
+    NamedColor { name: "beige", rgb: (0xf5, 0xf5, 0xdc) },
+    NamedColor { name: "bisque", rgb: (0xff, 0xe4, 0xc4) },
+    NamedColor { name: "black", rgb: (0x00, 0x00, 0x00) },
+    NamedColor { name: "blanchedalmond", rgb: (0xff, 0xeb, 0xcd) },
+    NamedColor { name: "blue", rgb: (0x00, 0x00, 0xff) },
+    NamedColor { name: "blueviolet", rgb: (0x8a, 0x2b, 0xe2) },
+    NamedColor { name: "brown", rgb: (0xa5, 0x2a, 0x2a) },
+    NamedColor { name: "burlywood", rgb: (0xde, 0xb8, 0x87) },
+    NamedColor { name: "cadetblue", rgb: (0x5f, 0x9e, 0xa0) },
+    NamedColor { name: "chartreuse", rgb: (0x7f, 0xff, 0x00) },
+    NamedColor { name: "chocolate", rgb: (0xd2, 0x69, 0x1e) },
+    NamedColor { name: "coral", rgb: (0xff, 0x7f, 0x50) },
+    NamedColor { name: "cornflowerblue", rgb: (0x64, 0x95, 0xed) },
+    NamedColor { name: "cornsilk", rgb: (0xff, 0xf8, 0xdc) },
+    NamedColor { name: "crimson", rgb: (0xdc, 0x14, 0x3c) },
+    NamedColor { name: "cyan", rgb: (0x00, 0xff, 0xff) },
+    NamedColor { name: "darkblue", rgb: (0x00, 0x00, 0x8b) },
+    NamedColor { name: "darkcyan", rgb: (0x00, 0x8b, 0x8b) },
+    NamedColor { name: "darkgoldenrod", rgb: (0xb8, 0x86, 0x0b) },
+    NamedColor { name: "darkgray", rgb: (0xa9, 0xa9, 0xa9) },
+    NamedColor { name: "darkgreen", rgb: (0x00, 0x64, 0x00) },
+    NamedColor { name: "darkgrey", rgb: (0xa9, 0xa9, 0xa9) },
+    NamedColor { name: "darkkhaki", rgb: (0xbd, 0xb7, 0x6b) },
+    NamedColor { name: "darkmagenta", rgb: (0x8b, 0x00, 0x8b) },
+    NamedColor { name: "darkolivegreen", rgb: (0x55, 0x6b, 0x2f) },
+    NamedColor { name: "darkorange", rgb: (0xff, 0x8c, 0x00) },
+    NamedColor { name: "darkorchid", rgb: (0x99, 0x32, 0xcc) },
+    NamedColor { name: "darkred", rgb: (0x8b, 0x00, 0x00) },
+    NamedColor { name: "darksalmon", rgb: (0xe9, 0x96, 0x7a) },
+    NamedColor { name: "darkseagreen", rgb: (0x8f, 0xbc, 0x8f) },
+    NamedColor { name: "darkslateblue", rgb: (0x48, 0x3d, 0x8b) },
+    NamedColor { name: "darkslategray", rgb: (0x2f, 0x4f, 0x4f) },
+    NamedColor { name: "darkslategrey", rgb: (0x2f, 0x4f, 0x4f) },
+    NamedColor { name: "darkturquoise", rgb: (0x00, 0xce, 0xd1) },
+    NamedColor { name: "darkviolet", rgb: (0x94, 0x00, 0xd3) },
+    NamedColor { name: "deeppink", rgb: (0xff, 0x14, 0x93) },
+    NamedColor { name: "deepskyblue", rgb: (0x00, 0xbf, 0xff) },
+    NamedColor { name: "dimgray", rgb: (0x69, 0x69, 0x69) },
+    NamedColor { name: "dimgrey", rgb: (0x69, 0x69, 0x69) },
+    NamedColor { name: "dodgerblue", rgb: (0x1e, 0x90, 0xff) },
+    NamedColor { name: "firebrick", rgb: (0xb2, 0x22, 0x22) },
+    NamedColor { name: "floralwhite", rgb: (0xff, 0xfa, 0xf0) },
+    NamedColor { name: "forestgreen", rgb: (0x22, 0x8b, 0x22) },
+    NamedColor { name: "fuchsia", rgb: (0xff, 0x00, 0xff) },
+    NamedColor { name: "gainsboro", rgb: (0xdc, 0xdc, 0xdc) },
+    NamedColor { name: "ghostwhite", rgb: (0xf8, 0xf8, 0xff) },
+    NamedColor { name: "gold", rgb: (0xff, 0xd7, 0x00) },
+    NamedColor { name: "goldenrod", rgb: (0xda, 0xa5, 0x20) },
+    NamedColor { name: "gray", rgb: (0x80, 0x80, 0x80) },
+    NamedColor { name: "green", rgb: (0x00, 0x80, 0x00) },
+    NamedColor { name: "greenyellow", rgb: (0xad, 0xff, 0x2f) },
+    NamedColor { name: "grey", rgb: (0x80, 0x80, 0x80) },
+    NamedColor { name: "honeydew", rgb: (0xf0, 0xff, 0xf0) },
+    NamedColor { name: "hotpink", rgb: (0xff, 0x69, 0xb4) },
+    NamedColor { name: "indianred", rgb: (0xcd, 0x5c, 0x5c) },
+    NamedColor { name: "indigo", rgb: (0x4b, 0x00, 0x82) },
+    NamedColor { name: "ivory", rgb: (0xff, 0xff, 0xf0) },
+    NamedColor { name: "khaki", rgb: (0xf0, 0xe6, 0x8c) },
+    NamedColor { name: "lavender", rgb: (0xe6, 0xe6, 0xfa) },
+    NamedColor { name: "lavenderblush", rgb: (0xff, 0xf0, 0xf5) },
+    NamedColor { name: "lawngreen", rgb: (0x7c, 0xfc, 0x00) },
+    NamedColor { name: "lemonchiffon", rgb: (0xff, 0xfa, 0xcd) },
+    NamedColor { name: "lightblue", rgb: (0xad, 0xd8, 0xe6) },
+    NamedColor { name: "lightcoral", rgb: (0xf0, 0x80, 0x80) },
+    NamedColor { name: "lightcyan", rgb: (0xe0, 0xff, 0xff) },
+    NamedColor { name: "lightgoldenrodyellow", rgb: (0xfa, 0xfa, 0xd2) },
+    NamedColor { name: "lightgray", rgb: (0xd3, 0xd3, 0xd3) },
+    NamedColor { name: "lightgreen", rgb: (0x90, 0xee, 0x90) },
+    NamedColor { name: "lightgrey", rgb: (0xd3, 0xd3, 0xd3) },
+    NamedColor { name: "lightpink", rgb: (0xff, 0xb6, 0xc1) },
+    NamedColor { name: "lightsalmon", rgb: (0xff, 0xa0, 0x7a) },
+    NamedColor { name: "lightseagreen", rgb: (0x20, 0xb2, 0xaa) },
+    NamedColor { name: "lightskyblue", rgb: (0x87, 0xce, 0xfa) },
+    NamedColor { name: "lightslategray", rgb: (0x77, 0x88, 0x99) },
+    NamedColor { name: "lightslategrey", rgb: (0x77, 0x88, 0x99) },
+    NamedColor { name: "lightsteelblue", rgb: (0xb0, 0xc4, 0xde) },
+    NamedColor { name: "lightyellow", rgb: (0xff, 0xff, 0xe0) },
+    NamedColor { name: "lime", rgb: (0x00, 0xff, 0x00) },
+    NamedColor { name: "limegreen", rgb: (0x32, 0xcd, 0x32) },
+    NamedColor { name: "linen", rgb: (0xfa, 0xf0, 0xe6) },
+    NamedColor { name: "magenta", rgb: (0xff, 0x00, 0xff) },
+    NamedColor { name: "maroon", rgb: (0x80, 0x00, 0x00) },
+    NamedColor { name: "mediumaquamarine", rgb: (0x66, 0xcd, 0xaa) },
+    NamedColor { name: "mediumblue", rgb: (0x00, 0x00, 0xcd) },
+    NamedColor { name: "mediumorchid", rgb: (0xba, 0x55, 0xd3) },
+    NamedColor { name: "mediumpurple", rgb: (0x93, 0x70, 0xdb) },
+    NamedColor { name: "mediumseagreen", rgb: (0x3c, 0xb3, 0x71) },
+    NamedColor { name: "mediumslateblue", rgb: (0x7b, 0x68, 0xee) },
+    NamedColor { name: "mediumspringgreen", rgb: (0x00, 0xfa, 0x9a) },
+    NamedColor { name: "mediumturquoise", rgb: (0x48, 0xd1, 0xcc) },
+    NamedColor { name: "mediumvioletred", rgb: (0xc7, 0x15, 0x85) },
+    NamedColor { name: "midnightblue", rgb: (0x19, 0x19, 0x70) },
+    NamedColor { name: "mintcream", rgb: (0xf5, 0xff, 0xfa) },
+    NamedColor { name: "mistyrose", rgb: (0xff, 0xe4, 0xe1) },
+    NamedColor { name: "moccasin", rgb: (0xff, 0xe4, 0xb5) },
+    NamedColor { name: "navajowhite", rgb: (0xff, 0xde, 0xad) },
+    NamedColor { name: "navy", rgb: (0x00, 0x00, 0x80) },
+    NamedColor { name: "oldlace", rgb: (0xfd, 0xf5, 0xe6) },
+    NamedColor { name: "olive", rgb: (0x80, 0x80, 0x00) },
+    NamedColor { name: "olivedrab", rgb: (0x6b, 0x8e, 0x23) },
+    NamedColor { name: "orange", rgb: (0xff, 0xa5, 0x00) },
+    NamedColor { name: "orangered", rgb: (0xff, 0x45, 0x00) },
+    NamedColor { name: "orchid", rgb: (0xda, 0x70, 0xd6) },
+    NamedColor { name: "palegoldenrod", rgb: (0xee, 0xe8, 0xaa) },
+    NamedColor { name: "palegreen", rgb: (0x98, 0xfb, 0x98) },
+    NamedColor { name: "paleturquoise", rgb: (0xaf, 0xee, 0xee) },
+    NamedColor { name: "palevioletred", rgb: (0xdb, 0x70, 0x93) },
+    NamedColor { name: "papayawhip", rgb: (0xff, 0xef, 0xd5) },
+    NamedColor { name: "peachpuff", rgb: (0xff, 0xda, 0xb9) },
+    NamedColor { name: "peru", rgb: (0xcd, 0x85, 0x3f) },
+    NamedColor { name: "pink", rgb: (0xff, 0xc0, 0xcb) },
+    NamedColor { name: "plum", rgb: (0xdd, 0xa0, 0xdd) },
+    NamedColor { name: "powderblue", rgb: (0xb0, 0xe0, 0xe6) },
+    NamedColor { name: "purple", rgb: (0x80, 0x00, 0x80) },
+    NamedColor { name: "rebeccapurple", rgb: (0x66, 0x33, 0x99) },
+    NamedColor { name: "red", rgb: (0xff, 0x00, 0x00) },
+    NamedColor { name: "rosybrown", rgb: (0xbc, 0x8f, 0x8f) },
+    NamedColor { name: "royalblue", rgb: (0x41, 0x69, 0xe1) },
+    NamedColor { name: "saddlebrown", rgb: (0x8b, 0x45, 0x13) },
+    NamedColor { name: "salmon", rgb: (0xfa, 0x80, 0x72) },
+    NamedColor { name: "sandybrown", rgb: (0xf4, 0xa4, 0x60) },
+    NamedColor { name: "seagreen", rgb: (0x2e, 0x8b, 0x57) },
+    NamedColor { name: "seashell", rgb: (0xff, 0xf5, 0xee) },
+    NamedColor { name: "sienna", rgb: (0xa0, 0x52, 0x2d) },
+    NamedColor { name: "silver", rgb: (0xc0, 0xc0, 0xc0) },
+    NamedColor { name: "skyblue", rgb: (0x87, 0xce, 0xeb) },
+    NamedColor { name: "slateblue", rgb: (0x6a, 0x5a, 0xcd) },
+    NamedColor { name: "slategray", rgb: (0x70, 0x80, 0x90) },
+    NamedColor { name: "slategrey", rgb: (0x70, 0x80, 0x90) },
+    NamedColor { name: "snow", rgb: (0xff, 0xfa, 0xfa) },
+    NamedColor { name: "springgreen", rgb: (0x00, 0xff, 0x7f) },
+    NamedColor { name: "steelblue", rgb: (0x46, 0x82, 0xb4) },
+    NamedColor { name: "tan", rgb: (0xd2, 0xb4, 0x8c) },
+    NamedColor { name: "teal", rgb: (0x00, 0x80, 0x80) },
+    NamedColor { name: "thistle", rgb: (0xd8, 0xbf, 0xd8) },
+    NamedColor { name: "tomato", rgb: (0xff, 0x63, 0x47) },
+    NamedColor { name: "turquoise", rgb: (0x40, 0xe0, 0xd0) },
+    NamedColor { name: "violet", rgb: (0xee, 0x82, 0xee) },
+    NamedColor { name: "wheat", rgb: (0xf5, 0xde, 0xb3) },
+    NamedColor { name: "white", rgb: (0xff, 0xff, 0xff) },
+    NamedColor { name: "whitesmoke", rgb: (0xf5, 0xf5, 0xf5) },
+    NamedColor { name: "yellow", rgb: (0xff, 0xff, 0x00) },
+    NamedColor { name: "yellowgreen", rgb: (0x9a, 0xcd, 0x32) },
+];
+
+pub(super) fn lookup(name: &str) -> Option<CasColor> {
+    let name = name.trim().to_ascii_lowercase();
+    CSS_NAMED_COLORS
+        .iter()
+        .find(|named| named.name == name)
+        .map(NamedColor::color)
+}
+
+// Closest CSS named color by perceptual (Oklab) distance. Aliases such as
+// gray/grey resolve to whichever spelling appears first in the table.
+pub fn nearest_name(color: &CasColor) -> (NamedColor, f32) {
+    CSS_NAMED_COLORS
+        .iter()
+        .map(|named| (*named, color.distance(&named.color())))
+        .fold((CSS_NAMED_COLORS[0], f32::MAX), |best, candidate| {
+            if candidate.1 < best.1 { candidate } else { best }
+        })
+}
@@ -0,0 +1,150 @@
+// Machine-readable color report used by `cascolor info --json`
+//
+// The layout is versioned by SCHEMA_VERSION. Fields may be added within a
+// schema version, but existing fields are never renamed, removed or retyped
+// without bumping it.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::{nearest_name, CasColor, ColorFormat};
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ColorReport {
+    pub schema_version: u32,
+    pub input: String,
+    pub hex: String,
+    pub rgb: Rgb,
+    pub hsl: Hsl,
+    pub hsv: Hsv,
+    pub cmyk: Cmyk,
+    pub lab: Lab,
+    pub lch: Lch,
+    pub xyz: Xyz,
+    pub oklab: Lab,
+    pub oklch: Lch,
+    pub formats: BTreeMap<&'static str, String>,
+    pub luminance: f32,
+    pub nearest_name: NearestName,
+    pub contrast: Contrast,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Hsl {
+    pub h: f32,
+    pub s: f32,
+    pub l: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Hsv {
+    pub h: f32,
+    pub s: f32,
+    pub v: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Cmyk {
+    pub c: f32,
+    pub m: f32,
+    pub y: f32,
+    pub k: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Lab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Lch {
+    pub l: f32,
+    pub c: f32,
+    pub h: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Xyz {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NearestName {
+    pub name: &'static str,
+    pub hex: String,
+    pub distance: f32,
+    pub exact: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Contrast {
+    pub black: f32,
+    pub white: f32,
+}
+
+impl ColorReport {
+    pub fn new(input: &str, color: &CasColor) -> Self {
+        let (r, g, b) = color.to_rgb();
+        let (h, s, l) = color.to_hsl();
+        let (hv, sv, v) = color.to_hsv();
+        let (c, m, y, k) = color.to_cmyk();
+        let (lab_l, lab_a, lab_b) = color.to_lab();
+        let (lch_l, lch_c, lch_h) = color.to_lch();
+        let (x, xyz_y, z) = color.to_xyz();
+        let (ok_l, ok_a, ok_b) = color.to_oklab();
+        let (okc_l, okc_c, okc_h) = color.to_oklch();
+        let (named, distance) = nearest_name(color);
+
+        let black = CasColor::from_rgb(0, 0, 0);
+        let white = CasColor::from_rgb(255, 255, 255);
+
+        Self {
+            schema_version: SCHEMA_VERSION,
+            input: input.to_string(),
+            hex: color.to_hex(),
+            rgb: Rgb { r, g, b },
+            hsl: Hsl { h: round(h, 2), s: round(s, 4), l: round(l, 4) },
+            hsv: Hsv { h: round(hv, 2), s: round(sv, 4), v: round(v, 4) },
+            cmyk: Cmyk { c: round(c, 4), m: round(m, 4), y: round(y, 4), k: round(k, 4) },
+            lab: Lab { l: round(lab_l, 4), a: round(lab_a, 4), b: round(lab_b, 4) },
+            lch: Lch { l: round(lch_l, 4), c: round(lch_c, 4), h: round(lch_h, 2) },
+            xyz: Xyz { x: round(x, 6), y: round(xyz_y, 6), z: round(z, 6) },
+            oklab: Lab { l: round(ok_l, 6), a: round(ok_a, 6), b: round(ok_b, 6) },
+            oklch: Lch { l: round(okc_l, 6), c: round(okc_c, 6), h: round(okc_h, 2) },
+            formats: ColorFormat::ALL
+                .iter()
+                .map(|format| (format.key(), color.format(*format)))
+                .collect(),
+            luminance: round(color.relative_luminance(), 6),
+            nearest_name: NearestName {
+                name: named.name,
+                hex: named.color().to_hex(),
+                distance: round(distance, 6),
+                exact: named.color() == *color,
+            },
+            contrast: Contrast {
+                black: round(color.contrast_ratio(&black), 2),
+                white: round(color.contrast_ratio(&white), 2),
+            },
+        }
+    }
+}
+
+// Fixed precision keeps the JSON output stable across platforms
+fn round(value: f32, places: i32) -> f32 {
+    let factor = 10f32.powi(places);
+    (value * factor).round() / factor
+}
@@ -0,0 +1,250 @@
+// Find and rewrite color literals embedded in arbitrary text (CSS, SCSS,
+// JSON, source code). Everything outside a recognized literal is kept
+// byte-for-byte.
+
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use super::{names, parse_color_alpha, CasColor, ColorFormat};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSyntax {
+    Hex,
+    Rgb,
+    Hsl,
+    Hsv,
+    Cmyk,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+    Color,
+    Named,
+}
+
+impl ColorSyntax {
+    pub const ALL: [ColorSyntax; 11] = [
+        ColorSyntax::Hex,
+        ColorSyntax::Rgb,
+        ColorSyntax::Hsl,
+        ColorSyntax::Hsv,
+        ColorSyntax::Cmyk,
+        ColorSyntax::Lab,
+        ColorSyntax::Lch,
+        ColorSyntax::Oklab,
+        ColorSyntax::Oklch,
+        ColorSyntax::Color,
+        ColorSyntax::Named,
+    ];
+
+    // Named colors are opt-in: words like "red" or "tan" are too common in
+    // prose and identifiers to rewrite by default.
+    pub const DEFAULT: [ColorSyntax; 10] = [
+        ColorSyntax::Hex,
+        ColorSyntax::Rgb,
+        ColorSyntax::Hsl,
+        ColorSyntax::Hsv,
+        ColorSyntax::Cmyk,
+        ColorSyntax::Lab,
+        ColorSyntax::Lch,
+        ColorSyntax::Oklab,
+        ColorSyntax::Oklch,
+        ColorSyntax::Color,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            ColorSyntax::Hex => "hex",
+            ColorSyntax::Rgb => "rgb",
+            ColorSyntax::Hsl => "hsl",
+            ColorSyntax::Hsv => "hsv",
+            ColorSyntax::Cmyk => "cmyk",
+            ColorSyntax::Lab => "lab",
+            ColorSyntax::Lch => "lch",
+            ColorSyntax::Oklab => "oklab",
+            ColorSyntax::Oklch => "oklch",
+            ColorSyntax::Color => "color",
+            ColorSyntax::Named => "named",
+        }
+    }
+
+    // The output format that writes this syntax, if any
+    pub fn format(&self) -> Option<ColorFormat> {
+        match self {
+            ColorSyntax::Hex => Some(ColorFormat::Hex),
+            ColorSyntax::Rgb => Some(ColorFormat::Rgb),
+            ColorSyntax::Hsl => Some(ColorFormat::Hsl),
+            ColorSyntax::Hsv => Some(ColorFormat::Hsv),
+            ColorSyntax::Cmyk => Some(ColorFormat::Cmyk),
+            ColorSyntax::Lab => Some(ColorFormat::Lab),
+            ColorSyntax::Lch => Some(ColorFormat::Lch),
+            ColorSyntax::Oklab => Some(ColorFormat::Oklab),
+            ColorSyntax::Oklch => Some(ColorFormat::Oklch),
+            ColorSyntax::Color | ColorSyntax::Named => None,
+        }
+    }
+
+    fn from_function(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "rgb" | "rgba" => Some(ColorSyntax::Rgb),
+            "hsl" | "hsla" => Some(ColorSyntax::Hsl),
+            "hsv" | "hsb" => Some(ColorSyntax::Hsv),
+            "cmyk" | "device-cmyk" => Some(ColorSyntax::Cmyk),
+            "lab" => Some(ColorSyntax::Lab),
+            "lch" => Some(ColorSyntax::Lch),
+            "oklab" => Some(ColorSyntax::Oklab),
+            "oklch" => Some(ColorSyntax::Oklch),
+            "color" => Some(ColorSyntax::Color),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ColorSyntax {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for ColorSyntax {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        ColorSyntax::ALL
+            .iter()
+            .copied()
+            .find(|syntax| syntax.key() == s)
+            .ok_or_else(|| format!("Unknown color syntax: {}", s))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorMatch {
+    pub range: Range<usize>,
+    pub syntax: ColorSyntax,
+    pub color: CasColor,
+    pub alpha: Option<f32>,
+}
+
+// Every color literal in `text` written in one of `syntaxes`, in order
+pub fn find_colors(text: &str, syntaxes: &[ColorSyntax]) -> Vec<ColorMatch> {
+    let bytes = text.as_bytes();
+    let mut matches = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let found = if bytes[i] == b'#' {
+            match_hex(text, i)
+        } else if is_ident_start(bytes[i]) && (i == 0 || !is_ident_byte(bytes[i - 1])) {
+            match_function(text, i).or_else(|| match_named(text, i))
+        } else {
+            None
+        };
+
+        match found {
+            Some(m) if syntaxes.contains(&m.syntax) => {
+                i = m.range.end;
+                matches.push(m);
+            }
+            Some(m) => i = m.range.end,
+            None => i += 1,
+        }
+    }
+
+    matches
+}
+
+// Rewrite every matched literal to `to`. Literals already written in the
+// target syntax are left untouched.
+pub fn rewrite_colors(text: &str, to: ColorFormat, syntaxes: &[ColorSyntax]) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+
+    for m in find_colors(text, syntaxes) {
+        if m.syntax.format() == Some(to) {
+            continue;
+        }
+        output.push_str(&text[last..m.range.start]);
+        output.push_str(&m.color.format_with_alpha(to, m.alpha.unwrap_or(1.0)));
+        last = m.range.end;
+    }
+
+    output.push_str(&text[last..]);
+    output
+}
+
+fn is_ident_start(b: u8) -> bool {
+    b.is_ascii_alphabetic()
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'-'
+}
+
+fn match_hex(text: &str, start: usize) -> Option<ColorMatch> {
+    let bytes = text.as_bytes();
+
+    // &#123; is an HTML character reference, not a color
+    if start > 0 && bytes[start - 1] == b'&' {
+        return None;
+    }
+
+    let digits = bytes[start + 1..]
+        .iter()
+        .take_while(|b| b.is_ascii_hexdigit())
+        .count();
+    let end = start + 1 + digits;
+
+    if !matches!(digits, 3 | 4 | 6 | 8) || bytes.get(end).is_some_and(|b| is_ident_byte(*b)) {
+        return None;
+    }
+
+    // An ID selector such as `#add {` looks exactly like a short hex color
+    let next = bytes[end..].iter().find(|b| !matches!(b, b' ' | b'\t'));
+    if next == Some(&b'{') {
+        return None;
+    }
+
+    let (color, alpha) = parse_color_alpha(&text[start..end]).ok()?;
+    Some(ColorMatch { range: start..end, syntax: ColorSyntax::Hex, color, alpha })
+}
+
+fn match_function(text: &str, start: usize) -> Option<ColorMatch> {
+    let bytes = text.as_bytes();
+    let name_len = bytes[start..].iter().take_while(|b| is_ident_byte(**b)).count();
+    let open = start + name_len;
+
+    if bytes.get(open) != Some(&b'(') {
+        return None;
+    }
+    let syntax = ColorSyntax::from_function(&text[start..open])?;
+
+    // Nested functions such as rgb(var(--r) ...) are not literals
+    let close = open + bytes[open..].iter().position(|b| *b == b')' || *b == b'\n')?;
+    if bytes[close] != b')' || text[open + 1..close].contains('(') {
+        return None;
+    }
+
+    let end = close + 1;
+    let (color, alpha) = parse_color_alpha(&text[start..end]).ok()?;
+    Some(ColorMatch { range: start..end, syntax, color, alpha })
+}
+
+fn match_named(text: &str, start: usize) -> Option<ColorMatch> {
+    let bytes = text.as_bytes();
+    let len = bytes[start..].iter().take_while(|b| b.is_ascii_alphabetic()).count();
+    let end = start + len;
+
+    // Skip parts of larger identifiers: --red, $red, .red, red-500, red_dark
+    if bytes.get(end).is_some_and(|b| is_ident_byte(*b) || *b == b'(') {
+        return None;
+    }
+    if start > 0 && matches!(bytes[start - 1], b'$' | b'@' | b'.' | b'#' | b'-' | b'_') {
+        return None;
+    }
+
+    let color = names::lookup(&text[start..end])?;
+    Some(ColorMatch { range: start..end, syntax: ColorSyntax::Named, color, alpha: None })
+}
@@ -0,0 +1,52 @@
+// `cascolor batch`: convert a list of colors into a table
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::batch::{self, BatchColumn, BatchInput, ColumnSelector, OutputFormat};
+use crate::exit::CliError;
+
+pub fn run(
+    file: Option<&Path>,
+    column: Option<ColumnSelector>,
+    delimiter: Option<char>,
+    header: bool,
+    columns: &[BatchColumn],
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    let extension = file
+        .and_then(|f| f.extension())
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase());
+
+    let delimiter = delimiter.or(match extension.as_deref() {
+        Some("csv") => Some(','),
+        Some("tsv") => Some('\t'),
+        // Picking a column implies a delimited file
+        _ if column.is_some() => Some(','),
+        _ => None,
+    });
+
+    let input = BatchInput {
+        delimiter,
+        column: column.unwrap_or(ColumnSelector::Index(0)),
+        has_header: header,
+    };
+
+    let reader: Box<dyn BufRead> = match file {
+        Some(path) if path != Path::new("-") => {
+            let file = File::open(path).map_err(|e| CliError::from(e).in_file(path))?;
+            Box::new(BufReader::new(file))
+        }
+        _ => Box::new(io::stdin().lock()),
+    };
+
+    let (rows, errors) = batch::read_colors(reader, &input)?;
+    for error in &errors {
+        eprintln!("Warning: skipped {}", error);
+    }
+
+    batch::write_table(&mut io::stdout().lock(), &rows, columns, output)?;
+    Ok(())
+}
@@ -0,0 +1,19 @@
+// `cascolor completions` and `cascolor manpage`: generated from the clap
+// definitions in cli.rs
+
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::io;
+
+use crate::cli::Cli;
+
+pub fn run_completions(shell: Shell) -> io::Result<()> {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, &mut io::stdout());
+    Ok(())
+}
+
+pub fn run_manpage() -> io::Result<()> {
+    clap_mangen::Man::new(Cli::command()).render(&mut io::stdout())
+}
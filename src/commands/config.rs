@@ -0,0 +1,277 @@
+// `cascolor config`: inspect, validate and edit the configuration file
+
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+use std::process::Command;
+
+use serde_json::json;
+
+use crate::config::{self, Config, ConfigError, Diagnostic, Origin, Severity, Sources, CONFIG_VERSION};
+use crate::exit::{CliError, ExitCode};
+use crate::history::History;
+
+pub fn run_check(file: Option<&Path>, json: bool) -> Result<(), CliError> {
+    let path = match file {
+        Some(path) => path.to_path_buf(),
+        None => Config::config_path(None)?,
+    };
+
+    if !path.exists() {
+        if file.is_some() {
+            return Err(CliError::new(ExitCode::Failure, "No such file").in_file(&path));
+        }
+        if json {
+            println!("{}", serde_json::to_string_pretty(&json!({ "file": path, "exists": false, "diagnostics": [] }))?);
+        } else {
+            println!("{}: not found, using defaults", path.display());
+        }
+        return Ok(());
+    }
+
+    // An older file is checked as it will be once upgraded on the next write
+    let contents = fs::read_to_string(&path).map_err(|e| CliError::from(e).in_file(&path))?;
+    let version = config::version(&contents);
+    let upgraded = config::upgrade(&contents);
+    let contents = upgraded.clone().unwrap_or(contents);
+    let (_, diagnostics) = Config::parse(&contents);
+    let errors = count_errors(&diagnostics);
+
+    if json {
+        let report = json!({
+            "file": path,
+            "exists": true,
+            "config_version": version,
+            "needs_upgrade": upgraded.is_some(),
+            "diagnostics": diagnostics,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        if let (Some(version), Some(_)) = (version, &upgraded) {
+            println!(
+                "note: {} is config version {}; checking it as upgraded to version {}{}",
+                path.display(),
+                version,
+                CONFIG_VERSION,
+                if diagnostics.is_empty() { "" } else { ", so positions refer to the upgraded file" }
+            );
+        }
+        if diagnostics.is_empty() {
+            println!("{}: OK", path.display());
+        } else {
+            report(&path, &contents, &diagnostics);
+        }
+    }
+
+    if errors > 0 {
+        return Err(has_errors(&path, errors));
+    }
+    Ok(())
+}
+
+pub fn run_get(key: &str, file: Option<&Path>, overrides: &[(String, String)], json: bool) -> Result<(), CliError> {
+    let value = Config::load(file, overrides)?.get(key)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&json!({ "key": key, "value": value }))?);
+        return Ok(());
+    }
+
+    match value {
+        toml::Value::String(s) => println!("{}", s),
+        toml::Value::Array(entries) => {
+            for entry in entries {
+                println!("{}", entry.as_str().map(str::to_string).unwrap_or_else(|| entry.to_string()));
+            }
+        }
+        toml::Value::Table(table) => print!("{}", toml::to_string(&table).map_err(ConfigError::from)?),
+        other => println!("{}", other),
+    }
+    Ok(())
+}
+
+pub fn run_show(origin: bool, file: Option<&Path>, overrides: &[(String, String)], json: bool) -> Result<(), CliError> {
+    let layered = Config::load_layered(&Sources::discover(file, overrides))?;
+    for problem in &layered.problems {
+        eprintln!("Warning: {}", problem);
+    }
+    let config = &layered.config;
+
+    if json {
+        let entries: Vec<serde_json::Value> = layered
+            .origins
+            .iter()
+            .map(|(key, origin)| {
+                let value = config.get(key).ok();
+                json!({ "key": key, "value": value, "layer": origin.layer.key(), "source": origin.source })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+    } else if origin {
+        let lines: Vec<(String, &Origin)> = layered
+            .origins
+            .iter()
+            .map(|(key, origin)| {
+                let value = config.get(key).map(|value| value.to_string()).unwrap_or_default();
+                (format!("{} = {}", key, value), origin)
+            })
+            .collect();
+        let width = lines.iter().map(|(line, _)| line.chars().count()).max().unwrap_or(0);
+        for (line, origin) in lines {
+            println!("{:<width$}  # {}", line, origin, width = width);
+        }
+    } else {
+        print!("{}", toml::to_string_pretty(config).map_err(ConfigError::from)?);
+    }
+    Ok(())
+}
+
+pub fn run_set(key: &str, value: &str, file: Option<&Path>) -> Result<(), CliError> {
+    let path = Config::config_path(file)?;
+    upgrade(&path)?;
+    config::modify(&path, |contents| config::set_key(contents, key, value)).map_err(|e| file_error(e, &path))
+}
+
+pub fn run_path(file: Option<&Path>, json: bool) -> Result<(), CliError> {
+    let path = Config::config_path(file)?;
+    if json {
+        let portable = file.is_none() && env::var_os(config::CONFIG_DIR_ENV).is_none() && config::portable_dir().is_some();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({ "path": path, "exists": path.exists(), "portable": portable }))?
+        );
+    } else {
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+pub fn run_reset(key: Option<&str>, file: Option<&Path>) -> Result<(), CliError> {
+    let path = Config::config_path(file)?;
+    upgrade(&path)?;
+    config::modify(&path, |contents| match key {
+        Some(key) => config::unset_key(contents, key),
+        None => {
+            if !contents.is_empty() {
+                let backup = config::sibling(&path, ".bak");
+                config::store(&backup, contents)?;
+                eprintln!("Saved the previous config to {}", backup.display());
+            }
+            Ok(Config::default_contents())
+        }
+    })
+    .map_err(|e| file_error(e, &path))
+}
+
+// Open the config in the user's editor until it has no errors or they give up
+pub fn run_edit(file: Option<&Path>) -> Result<(), CliError> {
+    let path = Config::config_path(file)?;
+    if !path.exists() {
+        config::store(&path, &Config::default_contents()).map_err(|e| CliError::from(e).in_file(&path))?;
+    }
+    upgrade(&path)?;
+
+    loop {
+        open_editor(&path)?;
+        let contents = fs::read_to_string(&path).map_err(|e| CliError::from(e).in_file(&path))?;
+        let (_, diagnostics) = Config::parse(&contents);
+        if diagnostics.is_empty() {
+            return Ok(());
+        }
+
+        report(&path, &contents, &diagnostics);
+        let errors = count_errors(&diagnostics);
+        if errors == 0 {
+            return Ok(());
+        }
+        if !io::stdin().is_terminal() || !ask("Edit again? [Y/n] ")? {
+            return Err(has_errors(&path, errors));
+        }
+    }
+}
+
+fn open_editor(path: &Path) -> Result<(), CliError> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "notepad" } else { "vi" }.to_string());
+
+    // Editors are often configured with arguments, e.g. "code --wait"
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let status = Command::new(program)
+        .args(words)
+        .arg(path)
+        .status()
+        .map_err(|e| CliError::new(ExitCode::Failure, format!("Could not start editor `{}`: {}", editor, e)))?;
+    if !status.success() {
+        return Err(CliError::new(ExitCode::Failure, format!("Editor `{}` exited with {}", editor, status)));
+    }
+    Ok(())
+}
+
+fn ask(question: &str) -> Result<bool, CliError> {
+    eprint!("{}", question);
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(!answer.trim().to_ascii_lowercase().starts_with('n'))
+}
+
+// Commands that change the file bring it to the current version first
+fn upgrade(path: &Path) -> Result<(), CliError> {
+    let history = History::default_path().ok();
+    config::upgrade_in_place(path, history.as_deref()).map_err(|e| CliError::from(e).in_file(path))
+}
+
+// Unknown keys and bad values come from the command line, not the file
+fn file_error(e: ConfigError, path: &Path) -> CliError {
+    match e {
+        ConfigError::UnknownKey(_) | ConfigError::InvalidValue(..) => e.into(),
+        _ => CliError::from(e).in_file(path),
+    }
+}
+
+fn report(path: &Path, contents: &str, diagnostics: &[Diagnostic]) {
+    for diagnostic in diagnostics {
+        println!("{}:{}", path.display(), diagnostic);
+        println!("{}", snippet(contents, diagnostic));
+    }
+    let errors = count_errors(diagnostics);
+    let warnings = diagnostics.len() - errors;
+    println!("{} error{}, {} warning{}", errors, plural(errors), warnings, plural(warnings));
+}
+
+fn count_errors(diagnostics: &[Diagnostic]) -> usize {
+    diagnostics.iter().filter(|d| d.severity == Severity::Error).count()
+}
+
+fn has_errors(path: &Path, errors: usize) -> CliError {
+    CliError::new(ExitCode::Config, format!("{} has {} error{}", path.display(), errors, plural(errors)))
+}
+
+// The offending line with the span underlined, in the style of compiler errors
+fn snippet(contents: &str, diagnostic: &Diagnostic) -> String {
+    let line = contents.lines().nth(diagnostic.line - 1).unwrap_or("");
+    let start = diagnostic.column - 1;
+    let span_text = contents.get(diagnostic.span.clone()).unwrap_or("");
+    let width = span_text.lines().next().unwrap_or("").chars().count().max(1);
+    let gutter = " ".repeat(diagnostic.line.to_string().len());
+
+    format!(
+        "{gutter} |\n{} | {}\n{gutter} | {}{}\n",
+        diagnostic.line,
+        line,
+        " ".repeat(start),
+        "^".repeat(width)
+    )
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
@@ -0,0 +1,53 @@
+// `cascolor convert`: convert between palette file formats
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use crate::exit::{CliError, ExitCode};
+use crate::palette::PaletteFormat;
+
+pub fn run(
+    input: &Path,
+    output: &Path,
+    from: Option<PaletteFormat>,
+    to: Option<PaletteFormat>,
+) -> Result<(), CliError> {
+    let from = resolve_format(input, from, "--from")?;
+    let to = resolve_format(output, to, "--to")?;
+
+    let bytes = if is_stdio(input) {
+        let mut bytes = Vec::new();
+        io::stdin().lock().read_to_end(&mut bytes)?;
+        bytes
+    } else {
+        fs::read(input).map_err(|e| CliError::from(e).in_file(input))?
+    };
+
+    let palette = from.read(&bytes).map_err(|e| CliError::from(e).in_file(input))?;
+    let data = to.write(&palette).map_err(|e| CliError::from(e).in_file(output))?;
+
+    if is_stdio(output) {
+        io::stdout().lock().write_all(&data)?;
+    } else {
+        fs::write(output, data).map_err(|e| CliError::from(e).in_file(output))?;
+    }
+
+    Ok(())
+}
+
+fn is_stdio(path: &Path) -> bool {
+    path == Path::new("-")
+}
+
+// An explicit format wins; otherwise go by extension, which stdin/stdout lack
+fn resolve_format(path: &Path, format: Option<PaletteFormat>, flag: &str) -> Result<PaletteFormat, CliError> {
+    match format {
+        Some(format) => Ok(format),
+        None if is_stdio(path) => Err(CliError::new(
+            ExitCode::Usage,
+            format!("{} is required when reading or writing \"-\"", flag),
+        )),
+        None => Ok(PaletteFormat::from_path(path)?),
+    }
+}
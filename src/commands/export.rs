@@ -0,0 +1,56 @@
+// `cascolor export`: write a palette or the color history as source code
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::config::Config;
+use crate::exit::{CliError, ExitCode};
+use crate::export::{self, ExportOptions};
+use crate::history::History;
+use crate::palette::Palette;
+
+pub fn run(
+    file: Option<&Path>,
+    history: bool,
+    options: &ExportOptions,
+    output: Option<&Path>,
+    config_file: Option<&Path>,
+    overrides: &[(String, String)],
+) -> Result<(), CliError> {
+    if !options.format.cases().contains(&options.case) {
+        let cases: Vec<&str> = options.format.cases().iter().map(|case| case.key()).collect();
+        return Err(CliError::new(
+            ExitCode::Usage,
+            format!("--naming {} is not valid for {}; use {}", options.case, options.format, cases.join(", ")),
+        ));
+    }
+
+    let palette = match file {
+        Some(path) if !history => Palette::load(path).map_err(|e| CliError::from(e).in_file(path))?,
+        _ => {
+            let history = History::open(&Config::load(config_file, overrides)?)?;
+            let entries = history.load().map_err(|e| CliError::from(e).in_file(&history.path))?;
+            export::history_palette(&entries)
+        }
+    };
+
+    write(&palette, options, output)
+}
+
+// Write the export to `output`, or stdout when it is missing or -
+pub fn write(palette: &Palette, options: &ExportOptions, output: Option<&Path>) -> Result<(), CliError> {
+    match output {
+        Some(path) if path != Path::new("-") => {
+            export::save(palette, options, path).map_err(|e| CliError::from(e).in_file(path))?;
+        }
+        _ if options.format.is_directory() => {
+            return Err(CliError::new(
+                ExitCode::Usage,
+                format!("{} export writes a directory; pass --output <DIR>", options.format),
+            ));
+        }
+        _ => io::stdout().lock().write_all(export::export(palette, options).as_bytes())?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+// `cascolor extract`: the dominant colors of an image as a palette
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::color::nearest_name;
+use crate::exit::{CliError, ExitCode};
+use crate::export::{ExportOptions, PaletteOutput};
+use crate::extract::{self, ExtractOptions, Swatch};
+use crate::term::{self, ColorSupport};
+
+pub fn run(
+    image: &Path,
+    options: &ExtractOptions,
+    format: Option<PaletteOutput>,
+    output: Option<&Path>,
+    json: bool,
+) -> Result<(), CliError> {
+    let swatches = extract::extract_file(image, options).map_err(|e| CliError::from(e).in_file(image))?;
+
+    let format = match (format, output) {
+        (Some(format), _) => format,
+        (None, None) => return print_swatches(&swatches, json),
+        (None, Some(path)) if path == Path::new("-") => {
+            return Err(CliError::new(ExitCode::Usage, "--format is required when writing \"-\""));
+        }
+        (None, Some(path)) => PaletteOutput::from_path(path)?,
+    };
+
+    let name = image.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let palette = extract::swatch_palette(name, &swatches);
+
+    match format {
+        PaletteOutput::Code(format) => super::export::write(&palette, &ExportOptions::new(format), output),
+        PaletteOutput::Palette(format) => {
+            let data = format.write(&palette)?;
+            match output {
+                Some(path) if path != Path::new("-") => {
+                    fs::write(path, data).map_err(|e| CliError::from(e).in_file(path))?
+                }
+                _ => io::stdout().lock().write_all(&data)?,
+            }
+            Ok(())
+        }
+    }
+}
+
+// One swatch per line with its hex value, share of the image and nearest CSS name
+fn print_swatches(swatches: &[Swatch], json: bool) -> Result<(), CliError> {
+    if json {
+        let report: Vec<_> = swatches
+            .iter()
+            .map(|s| json!({ "hex": s.color.to_hex(), "share": (s.share as f64 * 10000.0).round() / 10000.0 }))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let support = term::stdout_color_support();
+    for swatch in swatches {
+        let color = &swatch.color;
+        let (named, distance) = nearest_name(color);
+        let name = if distance == 0.0 { named.name.to_string() } else { format!("~{}", named.name) };
+        let share = format!("{:5.1}%", swatch.share * 100.0);
+        if support == ColorSupport::None {
+            println!("{}  {}  {}", color.to_hex(), share, name);
+        } else {
+            println!("{}  {}  {}  {}", term::swatch(color, support, 8), color.to_hex(), share, name);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,29 @@
+// `cascolor filter`: rewrite color literals in a stream of text
+
+use std::io::{self, BufRead, BufWriter, Write};
+
+use crate::color::{rewrite_colors, ColorFormat, ColorSyntax};
+
+pub fn run(to: ColorFormat, syntaxes: &[ColorSyntax]) -> io::Result<()> {
+    let syntaxes = if syntaxes.is_empty() { &ColorSyntax::DEFAULT[..] } else { syntaxes };
+
+    let mut input = io::stdin().lock();
+    let mut output = BufWriter::new(io::stdout().lock());
+    let mut line = Vec::new();
+
+    // Line by line so large inputs stream; color literals never span lines
+    loop {
+        line.clear();
+        if input.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+
+        // Lines that are not valid UTF-8 cannot hold a literal we parse; pass them through untouched
+        match std::str::from_utf8(&line) {
+            Ok(text) => output.write_all(rewrite_colors(text, to, syntaxes).as_bytes())?,
+            Err(_) => output.write_all(&line)?,
+        }
+    }
+
+    output.flush()
+}
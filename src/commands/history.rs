@@ -0,0 +1,82 @@
+// `cascolor history`: list, pin, clear and import the color history
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::cli::HistoryCommand;
+use crate::color::parse_color;
+use crate::config::Config;
+use crate::exit::{CliError, ExitCode};
+use crate::history::{self, History, HistoryEntry, Source};
+
+pub fn run(command: HistoryCommand, file: Option<&Path>, overrides: &[(String, String)], json: bool) -> Result<(), CliError> {
+    let config = Config::load(file, overrides)?;
+    let history = History::open(&config)?;
+    let in_file = |e| CliError::from(e).in_file(&history.path);
+
+    match command {
+        HistoryCommand::List { pinned } => {
+            let entries: Vec<HistoryEntry> =
+                history.load().map_err(in_file)?.into_iter().filter(|entry| entry.pinned || !pinned).collect();
+            if json {
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+            } else {
+                for entry in &entries {
+                    let format = entry.format.map(|format| format.key()).unwrap_or("-");
+                    let pin = if entry.pinned { "pinned" } else { "" };
+                    let line = format!("{}  {}  {:<10}  {:<5}  {}", entry.color, entry.time, entry.source.key(), format, pin);
+                    println!("{}", line.trim_end());
+                }
+            }
+        }
+        HistoryCommand::Add { color } => {
+            let parsed = parse_color(&color).map_err(CliError::parse)?;
+            let entry = HistoryEntry::new(&parsed, Source::Cli, history::input_format(&color));
+            history.record(entry).map_err(in_file)?;
+        }
+        HistoryCommand::Pin { color } => set_pinned(&history, &color, true)?,
+        HistoryCommand::Unpin { color } => set_pinned(&history, &color, false)?,
+        HistoryCommand::Clear { all } => {
+            let removed = history.clear(all).map_err(in_file)?;
+            eprintln!("Removed {} colors from the history", removed);
+        }
+        HistoryCommand::Import { file: source } => {
+            let source = match source {
+                Some(source) => source,
+                None => Config::config_path(file)?,
+            };
+            let contents = fs::read_to_string(&source).map_err(|e| CliError::from(e).in_file(&source))?;
+            let colors = history::legacy_colors(&contents);
+            if colors.is_empty() {
+                return Err(CliError::new(
+                    ExitCode::Failure,
+                    format!("{}: no color_history to import (an upgraded config keeps it in its .bak file)", source.display()),
+                ));
+            }
+            let added = history.import(&colors).map_err(in_file)?;
+            eprintln!("Imported {} of {} colors into {}", added, colors.len(), history.path.display());
+        }
+        HistoryCommand::Path => {
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&json!({ "path": history.path, "exists": history.path.exists() }))?
+                );
+            } else {
+                println!("{}", history.path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn set_pinned(history: &History, color: &str, pinned: bool) -> Result<(), CliError> {
+    let parsed = parse_color(color).map_err(CliError::parse)?;
+    let found = history.set_pinned(&parsed, pinned).map_err(|e| CliError::from(e).in_file(&history.path))?;
+    if !found {
+        return Err(CliError::new(ExitCode::Failure, format!("{} is not in the history", parsed.to_hex())));
+    }
+    Ok(())
+}
@@ -0,0 +1,35 @@
+// `cascolor info`: print every representation of a single color
+
+use crate::color::{self, ColorFormat, ColorReport};
+use crate::exit::CliError;
+use crate::term::{self, ColorSupport};
+
+pub fn run(input: &str, json: bool) -> Result<(), CliError> {
+    let color = color::parse_color(input).map_err(CliError::parse)?;
+    let report = ColorReport::new(input, &color);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let support = term::stdout_color_support();
+    if support != ColorSupport::None {
+        println!("{}", term::swatch(&color, support, 24));
+    }
+
+    for format in ColorFormat::ALL {
+        println!("{:10} {}", format.to_string(), color.format(format));
+    }
+    println!("{:10} {:.4}", "Luminance", report.luminance);
+    println!(
+        "{:10} {} ({}, distance {:.4})",
+        "Nearest", report.nearest_name.name, report.nearest_name.hex, report.nearest_name.distance
+    );
+    println!(
+        "{:10} {:.2}:1 on black, {:.2}:1 on white",
+        "Contrast", report.contrast.black, report.contrast.white
+    );
+
+    Ok(())
+}
@@ -0,0 +1,15 @@
+// CLI subcommand implementations
+
+pub mod batch;
+pub mod completions;
+pub mod config;
+pub mod convert;
+pub mod export;
+pub mod extract;
+pub mod filter;
+pub mod history;
+pub mod info;
+pub mod palette;
+pub mod sample;
+pub mod sheet;
+pub mod theme;
@@ -0,0 +1,77 @@
+// `cascolor palette`: preview colors as swatches in the terminal
+
+use std::path::Path;
+
+use crate::color::{self, nearest_name, CasColor};
+use crate::exit::CliError;
+use crate::palette::{Palette, PaletteEntry};
+use crate::term::{self, ColorSupport};
+
+pub fn run(inputs: &[String], file: Option<&Path>) -> Result<(), CliError> {
+    let support = term::stdout_color_support();
+
+    if let Some(path) = file {
+        let palette = Palette::load(path).map_err(|e| CliError::from(e).in_file(path))?;
+        print_entries(&palette.entries, support);
+        return Ok(());
+    }
+
+    if inputs.is_empty() {
+        print_grid(&Palette::builtin(), support);
+        return Ok(());
+    }
+
+    let colors = inputs
+        .iter()
+        .map(|input| color::parse_color(input))
+        .collect::<Result<Vec<CasColor>, String>>()
+        .map_err(CliError::parse)?;
+    let entries: Vec<PaletteEntry> = colors.into_iter().map(|color| PaletteEntry::new(color, None)).collect();
+
+    print_entries(&entries, support);
+    Ok(())
+}
+
+// One swatch per line with its hex value and name; unnamed colors show the
+// nearest CSS name, marked with ~ unless it is exact
+fn print_entries(entries: &[PaletteEntry], support: ColorSupport) {
+    for entry in entries {
+        let color = &entry.color;
+        let name = match &entry.name {
+            Some(name) => name.clone(),
+            None => {
+                let (named, distance) = nearest_name(color);
+                if distance == 0.0 {
+                    named.name.to_string()
+                } else {
+                    format!("~{}", named.name)
+                }
+            }
+        };
+
+        if support == ColorSupport::None {
+            println!("{}  {}", color.to_hex(), name);
+        } else {
+            println!("{}  {}  {}", term::swatch(color, support, 8), color.to_hex(), name);
+        }
+    }
+}
+
+// A palette laid out in its grid columns, e.g. the built-in grid with one row per hue
+fn print_grid(palette: &Palette, support: ColorSupport) {
+    for row in 0..palette.grid_rows() {
+        let cells: Vec<String> = (0..palette.grid_columns())
+            .filter_map(|col| palette.get(row, col))
+            .map(|entry| {
+                let color = entry.color;
+                match support {
+                    ColorSupport::None => color.to_hex(),
+                    _ => term::swatch(&color, support, 4),
+                }
+            })
+            .collect();
+
+        let separator = if support == ColorSupport::None { " " } else { "" };
+        println!("{}", cells.join(separator));
+    }
+}
@@ -0,0 +1,20 @@
+// `cascolor sample`: the color at a pixel, or averaged over a region, of an image file
+
+use std::path::Path;
+
+use crate::color::{ColorFormat, ColorReport};
+use crate::exit::CliError;
+use crate::extract::{self, Averaging, Region};
+
+pub fn run(image: &Path, region: Region, averaging: Averaging, to: ColorFormat, json: bool) -> Result<(), CliError> {
+    let color = extract::sample_file(image, region, averaging).map_err(|e| CliError::from(e).in_file(image))?;
+
+    if json {
+        let report = ColorReport::new(&region.to_string(), &color);
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", color.format(to));
+    }
+
+    Ok(())
+}
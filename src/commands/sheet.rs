@@ -0,0 +1,70 @@
+// `cascolor sheet`: render a palette, harmony or scale as a PNG or SVG swatch sheet
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::color::{parse_color, scale, CasColor, Harmony};
+use crate::exit::{CliError, ExitCode};
+use crate::palette::Palette;
+use crate::sheet::{self, SheetFormat, SheetOptions};
+
+// Where the sheet's colors come from
+pub enum SheetSource<'a> {
+    File(&'a Path),
+    Harmony(Harmony, &'a str),
+    Scale(&'a str, &'a str, usize),
+}
+
+pub fn run(
+    source: SheetSource,
+    options: &SheetOptions,
+    format: Option<SheetFormat>,
+    output: &Path,
+) -> Result<(), CliError> {
+    let format = match format {
+        Some(format) => format,
+        None => SheetFormat::from_path(output).ok_or_else(|| {
+            CliError::new(ExitCode::Usage, "--format is required unless the output ends in .png or .svg")
+        })?,
+    };
+
+    let palette = match source {
+        SheetSource::File(path) => Palette::load(path).map_err(|e| CliError::from(e).in_file(path))?,
+        SheetSource::Harmony(harmony, base) => {
+            let base = color(base)?;
+            colors_palette(format!("{} harmony of {}", harmony, base.to_hex()), harmony.colors(&base))
+        }
+        SheetSource::Scale(from, to, steps) => {
+            let (from, to) = (color(from)?, color(to)?);
+            colors_palette(format!("Scale from {} to {}", from.to_hex(), to.to_hex()), scale(&from, &to, steps))
+        }
+    };
+
+    let layout = sheet::layout(&palette, options);
+    let data = match format {
+        SheetFormat::Png => sheet::render_png(&layout)
+            .map_err(|e| CliError::new(ExitCode::Failure, format!("Could not encode PNG: {}", e)))?,
+        SheetFormat::Svg => sheet::render_svg(&layout).into_bytes(),
+    };
+
+    if output == Path::new("-") {
+        io::stdout().lock().write_all(&data)?;
+    } else {
+        fs::write(output, data).map_err(|e| CliError::from(e).in_file(output))?;
+    }
+
+    Ok(())
+}
+
+fn color(input: &str) -> Result<CasColor, CliError> {
+    parse_color(input).map_err(CliError::parse)
+}
+
+fn colors_palette(name: String, colors: Vec<CasColor>) -> Palette {
+    let mut palette = Palette::new(name);
+    for color in colors {
+        palette.push(color, None);
+    }
+    palette
+}
@@ -0,0 +1,60 @@
+// `cascolor theme`: generate a terminal color scheme from a palette file
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::exit::{CliError, ExitCode};
+use crate::palette::Palette;
+use crate::theme::{self, TerminalTheme, ThemeFormat};
+
+pub fn run(
+    file: &Path,
+    format: ThemeFormat,
+    name: Option<String>,
+    min_contrast: f32,
+    strict: bool,
+    output: Option<&Path>,
+) -> Result<(), CliError> {
+    let palette = Palette::load(file).map_err(|e| CliError::from(e).in_file(file))?;
+    let mut theme = TerminalTheme::from_palette(&palette).map_err(|e| CliError::from(e).in_file(file))?;
+    if let Some(name) = name {
+        theme.name = name;
+    }
+
+    let issues = theme.contrast_issues(min_contrast);
+    for issue in &issues {
+        eprintln!(
+            "warning: {} {} has contrast {:.2}:1 against the background (minimum {:.1}:1)",
+            issue.slot,
+            issue.color.to_hex(),
+            issue.ratio,
+            issue.required
+        );
+    }
+    for color in &theme.unused {
+        eprintln!("warning: {} is not used; all 16 ANSI slots are taken", color.to_hex());
+    }
+    if strict && !issues.is_empty() {
+        return Err(CliError::new(
+            ExitCode::Failure,
+            format!("{} colors are below the minimum contrast", issues.len()),
+        ));
+    }
+    if strict && !theme.unused.is_empty() {
+        return Err(CliError::new(
+            ExitCode::Failure,
+            format!("{} palette colors don't fit the theme", theme.unused.len()),
+        ));
+    }
+
+    let scheme = theme::render(&theme, format);
+    match output {
+        Some(path) if path != Path::new("-") => {
+            fs::write(path, scheme).map_err(|e| CliError::from(e).in_file(path))?;
+        }
+        _ => io::stdout().lock().write_all(scheme.as_bytes())?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+// Edits to the config file that keep the user's comments and formatting
+
+use toml_edit::{DocumentMut, Item, Table, Value};
+
+use super::migrate::set_version;
+use super::validate::{self, Kind};
+use super::{Config, ConfigError};
+
+// What a dotted key like "ui.mode" refers to
+enum Target {
+    Section(&'static str),
+    Key(&'static str, &'static str, Kind),
+    Version,
+}
+
+fn target(key: &str) -> Result<Target, ConfigError> {
+    let unknown = || ConfigError::UnknownKey(key.to_string());
+    if key == "config_version" {
+        return Ok(Target::Version);
+    }
+
+    let (section_name, key_name) = match key.split_once('.') {
+        Some((section, key)) => (section, Some(key)),
+        None => (key, None),
+    };
+    let (section, keys) =
+        validate::sections().into_iter().find(|(section, _)| *section == section_name).ok_or_else(unknown)?;
+    match key_name {
+        None => Ok(Target::Section(section)),
+        Some(key_name) => {
+            let (name, kind) = keys.into_iter().find(|(name, _)| *name == key_name).ok_or_else(unknown)?;
+            Ok(Target::Key(section, name, kind))
+        }
+    }
+}
+
+impl Config {
+    // The effective value of a section or key, defaults included
+    pub fn get(&self, key: &str) -> Result<toml::Value, ConfigError> {
+        let value = toml::Value::try_from(self)?;
+        let value = match target(key)? {
+            Target::Section(section) => &value[section],
+            Target::Key(section, name, _) => &value[section][name],
+            Target::Version => &value["config_version"],
+        };
+        Ok(value.clone())
+    }
+
+    // Write the values that changed since this config was loaded into
+    // `contents` (or, for a config not loaded from anywhere, that differ from
+    // what `contents` loads as), leaving comments, key order, unknown keys
+    // and values other layers or instances set alone
+    pub fn update(&self, contents: &str) -> Result<String, ConfigError> {
+        let mut document: DocumentMut = contents.parse()?;
+        if contents.trim().is_empty() {
+            set_version(&mut document);
+        }
+        let base = match &self.loaded {
+            Some(loaded) => toml::to_string(loaded)?,
+            None => toml::to_string(&Config::parse(contents).0)?,
+        };
+        let current: DocumentMut = base.parse()?;
+        let fresh: DocumentMut = toml::to_string(self)?.parse()?;
+
+        for (name, item) in fresh.iter() {
+            match item {
+                Item::Table(table) => {
+                    for (key, value) in table.iter() {
+                        let Some(value) = value.as_value() else { continue };
+                        if !unchanged(current.get(name).and_then(|section| section.get(key)), value) {
+                            put(section_mut(&mut document, name), key, value.clone());
+                        }
+                    }
+                }
+                Item::Value(value) if !unchanged(current.get(name), value) => {
+                    put(document.as_table_mut(), name, value.clone());
+                }
+                _ => {}
+            }
+        }
+        Ok(document.to_string())
+    }
+}
+
+// `contents` with `key` set to `value`, which is checked like the rest of the file
+pub fn set_key(contents: &str, key: &str, value: &str) -> Result<String, ConfigError> {
+    let mut document: DocumentMut = contents.parse()?;
+    set_in(&mut document, key, value)?;
+    if contents.trim().is_empty() {
+        set_version(&mut document);
+    }
+    Ok(document.to_string())
+}
+
+// Set a dotted key in `document` from text, as typed on the command line or
+// in an environment variable
+pub(super) fn set_in(document: &mut DocumentMut, key: &str, value: &str) -> Result<(), ConfigError> {
+    let (section, name, kind) = match target(key)? {
+        Target::Key(section, name, kind) => (section, name, kind),
+        Target::Section(_) => {
+            return Err(ConfigError::InvalidValue(key.to_string(), "is a table; set one of its keys".to_string()))
+        }
+        Target::Version => {
+            return Err(ConfigError::InvalidValue(key.to_string(), "is managed by cascolor and can't be set".to_string()))
+        }
+    };
+
+    let value = typed(&kind, value);
+    if let Some(message) = validate::check_value(&Item::Value(value.clone()), &kind) {
+        return Err(ConfigError::InvalidValue(key.to_string(), message));
+    }
+    put(section_mut(document, section), name, value);
+    Ok(())
+}
+
+// `contents` without `key`, so its default applies again
+pub fn unset_key(contents: &str, key: &str) -> Result<String, ConfigError> {
+    let mut document: DocumentMut = contents.parse()?;
+    match target(key)? {
+        Target::Key(section, name, _) => {
+            if let Some(table) = document.get_mut(section).and_then(|item| item.as_table_like_mut()) {
+                table.remove(name);
+            }
+        }
+        Target::Section(section) => {
+            document.remove(section);
+        }
+        Target::Version => {
+            return Err(ConfigError::InvalidValue(key.to_string(), "is managed by cascolor and can't be reset".to_string()))
+        }
+    }
+    Ok(document.to_string())
+}
+
+// A command-line value as the TOML type the key expects, when it parses
+fn typed(kind: &Kind, value: &str) -> Value {
+    match kind {
+        Kind::Bool => value.trim().parse::<bool>().map(Value::from).unwrap_or_else(|_| value.into()),
+        Kind::Integer(..) => value.trim().parse::<i64>().map(Value::from).unwrap_or_else(|_| value.into()),
+        Kind::Choice(_) => value.trim().to_ascii_lowercase().into(),
+        Kind::Key => value.trim().into(),
+    }
+}
+
+fn section_mut<'a>(document: &'a mut DocumentMut, section: &str) -> &'a mut dyn toml_edit::TableLike {
+    let item = document.entry(section).or_insert_with(|| Item::Table(Table::new()));
+    if item.as_table_like().is_none() {
+        *item = Item::Table(Table::new());
+    }
+    item.as_table_like_mut().expect("section is a table")
+}
+
+// Set `key`, keeping the existing value's surrounding whitespace and
+// trailing comment; unchanged values are left exactly as written
+fn put(table: &mut dyn toml_edit::TableLike, key: &str, mut value: Value) {
+    match table.get_mut(key).and_then(|item| item.as_value_mut()) {
+        Some(existing) if same(existing, &value) => {}
+        Some(existing) => {
+            *value.decor_mut() = existing.decor().clone();
+            *existing = value;
+        }
+        None => {
+            table.insert(key, Item::Value(value));
+        }
+    }
+}
+
+fn unchanged(old: Option<&Item>, value: &Value) -> bool {
+    old.and_then(Item::as_value).is_some_and(|old| same(old, value))
+}
+
+fn same(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::String(a), Value::String(b)) => a.value() == b.value(),
+        (Value::Integer(a), Value::Integer(b)) => a.value() == b.value(),
+        (Value::Float(a), Value::Float(b)) => a.value() == b.value(),
+        (Value::Boolean(a), Value::Boolean(b)) => a.value() == b.value(),
+        (Value::Array(a), Value::Array(b)) => a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| same(a, b)),
+        _ => false,
+    }
+}
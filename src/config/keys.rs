@@ -0,0 +1,252 @@
+// TUI key bindings, the [keys] section. Each action takes one key such as
+// "q", "tab", "f2" or "ctrl+o"; the arrow keys, Esc, Ctrl+C and 1-5 keep
+// working whatever is configured.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyName {
+    Char(char),
+    Tab,
+    Enter,
+    Esc,
+    Backspace,
+    Up,
+    Down,
+    Left,
+    Right,
+    F(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Key {
+    pub name: KeyName,
+    pub ctrl: bool,
+    pub alt: bool,
+}
+
+impl Key {
+    pub const fn char(c: char) -> Self {
+        Self { name: KeyName::Char(c), ctrl: false, alt: false }
+    }
+
+    pub const fn named(name: KeyName) -> Self {
+        Self { name, ctrl: false, alt: false }
+    }
+}
+
+const NAMED: [(&str, KeyName); 8] = [
+    ("tab", KeyName::Tab),
+    ("enter", KeyName::Enter),
+    ("esc", KeyName::Esc),
+    ("backspace", KeyName::Backspace),
+    ("up", KeyName::Up),
+    ("down", KeyName::Down),
+    ("left", KeyName::Left),
+    ("right", KeyName::Right),
+];
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "ctrl+")?;
+        }
+        if self.alt {
+            write!(f, "alt+")?;
+        }
+        match self.name {
+            KeyName::Char(' ') => write!(f, "space"),
+            KeyName::Char(c) => write!(f, "{}", c),
+            KeyName::F(n) => write!(f, "f{}", n),
+            name => {
+                let (text, _) = NAMED.iter().find(|(_, named)| *named == name).expect("every other key is named");
+                write!(f, "{}", text)
+            }
+        }
+    }
+}
+
+impl FromStr for Key {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut key = Key::char(' ');
+        let mut rest = s.trim();
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            if lower.starts_with("ctrl+") && rest.len() > 5 {
+                key.ctrl = true;
+                rest = &rest[5..];
+            } else if lower.starts_with("alt+") && rest.len() > 4 {
+                key.alt = true;
+                rest = &rest[4..];
+            } else {
+                break;
+            }
+        }
+
+        let lower = rest.to_ascii_lowercase();
+        let mut chars = rest.chars();
+        key.name = match (chars.next(), chars.next()) {
+            (Some(c), None) if !c.is_control() => KeyName::Char(c),
+            _ if lower == "space" => KeyName::Char(' '),
+            _ => match NAMED.iter().find(|(text, _)| *text == lower) {
+                Some((_, name)) => *name,
+                None => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n) if (1..=12).contains(&n) => KeyName::F(n),
+                    _ => return Err(format!("unknown key {:?}", s)),
+                },
+            },
+        };
+        Ok(key)
+    }
+}
+
+impl TryFrom<String> for Key {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Key> for String {
+    fn from(key: Key) -> Self {
+        key.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    NextPanel,
+    Input,
+    OpenPalette,
+    PickImage,
+    Export,
+    Theme,
+    Copy,
+    Left,
+    Down,
+    Up,
+    Right,
+}
+
+impl Action {
+    pub const ALL: [Action; 12] = [
+        Action::Quit,
+        Action::NextPanel,
+        Action::Input,
+        Action::OpenPalette,
+        Action::PickImage,
+        Action::Export,
+        Action::Theme,
+        Action::Copy,
+        Action::Left,
+        Action::Down,
+        Action::Up,
+        Action::Right,
+    ];
+
+    // Name of the action in the [keys] section
+    pub fn key(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::NextPanel => "next_panel",
+            Action::Input => "input",
+            Action::OpenPalette => "open_palette",
+            Action::PickImage => "pick_image",
+            Action::Export => "export",
+            Action::Theme => "theme",
+            Action::Copy => "copy",
+            Action::Left => "left",
+            Action::Down => "down",
+            Action::Up => "up",
+            Action::Right => "right",
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeysConfig {
+    pub quit: Key,
+    pub next_panel: Key,
+    pub input: Key,
+    pub open_palette: Key,
+    pub pick_image: Key,
+    pub export: Key,
+    pub theme: Key,
+    pub copy: Key,
+    pub left: Key,
+    pub down: Key,
+    pub up: Key,
+    pub right: Key,
+}
+
+impl KeysConfig {
+    pub fn binding(&self, action: Action) -> Key {
+        match action {
+            Action::Quit => self.quit,
+            Action::NextPanel => self.next_panel,
+            Action::Input => self.input,
+            Action::OpenPalette => self.open_palette,
+            Action::PickImage => self.pick_image,
+            Action::Export => self.export,
+            Action::Theme => self.theme,
+            Action::Copy => self.copy,
+            Action::Left => self.left,
+            Action::Down => self.down,
+            Action::Up => self.up,
+            Action::Right => self.right,
+        }
+    }
+
+    // The action bound to `key`; the first one when several share it
+    pub fn action(&self, key: Key) -> Option<Action> {
+        Action::ALL.into_iter().find(|action| self.binding(*action) == key)
+    }
+
+    // Pairs of actions bound to the same key, so all but the first of each
+    // can't be reached
+    pub fn conflicts(&self) -> Vec<(Action, Action)> {
+        let mut conflicts = Vec::new();
+        for (index, first) in Action::ALL.iter().enumerate() {
+            for second in &Action::ALL[index + 1..] {
+                if self.binding(*first) == self.binding(*second) {
+                    conflicts.push((*first, *second));
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+impl Default for KeysConfig {
+    fn default() -> Self {
+        Self {
+            quit: Key::char('q'),
+            next_panel: Key::named(KeyName::Tab),
+            input: Key::char('i'),
+            open_palette: Key::char('o'),
+            pick_image: Key::char('p'),
+            export: Key::char('e'),
+            theme: Key::char('t'),
+            copy: Key::char('c'),
+            left: Key::char('h'),
+            down: Key::char('j'),
+            up: Key::char('k'),
+            right: Key::char('l'),
+        }
+    }
+}
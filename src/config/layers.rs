@@ -0,0 +1,248 @@
+// Layered configuration. Each layer overrides only the keys it sets, in this
+// order: built-in defaults, the system config, the user config, the project
+// config, CASCOLOR_* environment variables and `--set` options.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use toml_edit::{DocumentMut, Item};
+
+use super::edit::set_in;
+use super::validate;
+use super::migrate::upgrade_values;
+use super::{valid_part, version, Config, ConfigError, Severity, CONFIG_VERSION};
+use crate::history::History;
+use crate::project;
+
+// Project config, the nearest one at or above the current directory (see
+// `project::find`)
+pub const PROJECT_FILE: &str = ".cascolor.toml";
+
+const ENV_PREFIX: &str = "CASCOLOR_";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    Default,
+    System,
+    User,
+    Project,
+    Env,
+    Cli,
+}
+
+impl Layer {
+    pub fn key(&self) -> &'static str {
+        match self {
+            Layer::Default => "default",
+            Layer::System => "system",
+            Layer::User => "user",
+            Layer::Project => "project",
+            Layer::Env => "env",
+            Layer::Cli => "cli",
+        }
+    }
+}
+
+impl fmt::Display for Layer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+// Where an effective value came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Origin {
+    pub layer: Layer,
+    // File path, environment variable or `--set` option; None for defaults
+    pub source: Option<String>,
+}
+
+impl Origin {
+    fn new(layer: Layer, source: impl Into<String>) -> Self {
+        Self { layer, source: Some(source.into()) }
+    }
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.source {
+            Some(source) => write!(f, "{} {}", self.layer, source),
+            None => write!(f, "{}", self.layer),
+        }
+    }
+}
+
+// Everything the layers are read from; files that don't exist are skipped
+#[derive(Debug, Clone, Default)]
+pub struct Sources {
+    pub system: Option<PathBuf>,
+    pub user: Option<PathBuf>,
+    pub project: Option<PathBuf>,
+    // CASCOLOR_* variables; others are ignored
+    pub env: Vec<(String, String)>,
+    // `--set key=value` options, later ones winning
+    pub overrides: Vec<(String, String)>,
+    // Where an older user config's `color_history` goes when `save`
+    // upgrades it
+    pub history: Option<PathBuf>,
+}
+
+impl Sources {
+    // The standard locations, with `file` from `--config` as the user config,
+    // and this process's environment
+    pub fn discover(file: Option<&Path>, overrides: &[(String, String)]) -> Self {
+        Self {
+            system: Some(system_path()),
+            user: Config::config_path(file).ok(),
+            project: env::current_dir().ok().and_then(|dir| project::find(&dir)).map(|dir| dir.join(PROJECT_FILE)),
+            env: env::vars().filter(|(name, _)| name.starts_with(ENV_PREFIX)).collect(),
+            overrides: overrides.to_vec(),
+            history: History::default_path().ok(),
+        }
+    }
+}
+
+pub fn system_path() -> PathBuf {
+    #[cfg(windows)]
+    let dir = env::var_os("PROGRAMDATA").map(PathBuf::from).unwrap_or_else(|| PathBuf::from(r"C:\ProgramData"));
+    #[cfg(not(windows))]
+    let dir = PathBuf::from("/etc");
+    dir.join("casapps").join("cascolor").join("config.toml")
+}
+
+// The environment variable for a dotted key, e.g. CASCOLOR_UI_DEFAULT_COLOR_FORMAT
+pub fn env_name(key: &str) -> String {
+    format!("{}{}", ENV_PREFIX, key.replace('.', "_").to_ascii_uppercase())
+}
+
+pub struct Layered {
+    pub config: Config,
+    // Every key with the layer its effective value came from
+    pub origins: Vec<(String, Origin)>,
+    // Invalid values that were ignored, as "source: message"
+    pub problems: Vec<String>,
+    // Files that couldn't be read or aren't valid TOML, so none of their
+    // settings were used
+    pub unreadable: Vec<PathBuf>,
+}
+
+// Invalid values in files and the environment are skipped and reported in
+// `problems`; an invalid `--set` is an error. Older files are upgraded in
+// memory only; `Config::save` upgrades the user config when it writes it.
+pub fn load(sources: &Sources) -> Result<Layered, ConfigError> {
+    let mut merged = DocumentMut::new();
+    let mut origins: Vec<(String, Origin)> =
+        keys().into_iter().map(|key| (key, Origin { layer: Layer::Default, source: None })).collect();
+    let mut problems = Vec::new();
+    let mut unreadable = Vec::new();
+
+    let files = [(Layer::System, &sources.system), (Layer::User, &sources.user), (Layer::Project, &sources.project)];
+    for (layer, path) in files {
+        let Some(path) = path.as_deref().filter(|path| path.is_file()) else { continue };
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) => {
+                problems.push(format!("{}: {}", path.display(), e));
+                unreadable.push(path.to_path_buf());
+                continue;
+            }
+        };
+        let contents = upgrade_values(&contents);
+
+        let (valid, diagnostics) = valid_part(&contents);
+        if diagnostics.iter().any(|d| d.key.is_empty() && d.severity == Severity::Error) {
+            unreadable.push(path.to_path_buf());
+        }
+        let errors = diagnostics.iter().filter(|d| d.severity == Severity::Error);
+        problems.extend(errors.map(|d| format!("{}:{}", path.display(), d)));
+        // A file's version describes that file; the user config's is the one
+        // `save` keeps, and `save` upgrades the file before writing to it
+        let mut document: DocumentMut = valid.parse()?;
+        if layer != Layer::User {
+            document.remove("config_version");
+        } else if version(&valid).is_some_and(|version| version < CONFIG_VERSION) {
+            document.insert("config_version", toml_edit::value(CONFIG_VERSION as i64));
+        }
+        let source = path.display().to_string();
+        apply(&mut merged, &mut origins, &document, |_| Origin::new(layer, source.clone()));
+    }
+
+    let mut env_layer = DocumentMut::new();
+    for key in setting_keys() {
+        let name = env_name(&key);
+        if let Some((_, value)) = sources.env.iter().find(|(var, _)| *var == name) {
+            if let Err(e) = set_in(&mut env_layer, &key, value) {
+                problems.push(format!("{}: {}", name, e));
+            }
+        }
+    }
+    apply(&mut merged, &mut origins, &env_layer, |key| Origin::new(Layer::Env, env_name(key)));
+
+    let mut cli_layer = DocumentMut::new();
+    for (key, value) in &sources.overrides {
+        set_in(&mut cli_layer, key, value)?;
+    }
+    apply(&mut merged, &mut origins, &cli_layer, |key| {
+        let (_, value) = sources.overrides.iter().rev().find(|(set, _)| set == key).expect("key was set");
+        Origin::new(Layer::Cli, format!("--set {}={}", key, value))
+    });
+
+    // Without a user config, `save` creates one at the current version
+    if merged.get("config_version").is_none() {
+        merged.insert("config_version", toml_edit::value(CONFIG_VERSION as i64));
+    }
+
+    // Every layer was validated, so this only fails on a bug in the validator
+    let mut config: Config = toml::from_str(&merged.to_string()).unwrap_or_default();
+    config.path = sources.user.clone();
+    config.history = sources.history.clone();
+    Ok(Layered { config: config.snapshot(), origins, problems, unreadable })
+}
+
+// Copy the keys `layer` sets over `merged` and note where they came from
+fn apply(
+    merged: &mut DocumentMut,
+    origins: &mut [(String, Origin)],
+    layer: &DocumentMut,
+    origin: impl Fn(&str) -> Origin,
+) {
+    for (name, item) in layer.iter() {
+        match (merged.get_mut(name).and_then(Item::as_table_like_mut), item.as_table_like()) {
+            (Some(table), Some(values)) => {
+                for (key, value) in values.iter() {
+                    table.insert(key, value.clone());
+                }
+            }
+            _ => {
+                merged.insert(name, item.clone());
+            }
+        }
+    }
+
+    for (key, current) in origins.iter_mut() {
+        let item = match key.split_once('.') {
+            Some((section, name)) => layer.get(section).and_then(|section| section.get(name)),
+            None => layer.get(key),
+        };
+        if item.is_some() {
+            *current = origin(key);
+        }
+    }
+}
+
+// Every known key, in the order `config show` lists them
+fn keys() -> Vec<String> {
+    let mut keys = vec!["config_version".to_string()];
+    keys.extend(setting_keys());
+    keys
+}
+
+// The keys that can be set from the environment and `--set`
+fn setting_keys() -> Vec<String> {
+    validate::sections()
+        .iter()
+        .flat_map(|(section, keys)| keys.iter().map(move |(key, _)| format!("{}.{}", section, key)))
+        .collect()
+}
@@ -0,0 +1,193 @@
+// Config schema versions and the migrations between them. Each step edits
+// the TOML document in place, so comments and formatting survive upgrades.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use toml_edit::{DocumentMut, Item, Value};
+
+use super::store::{self, sibling};
+use super::ConfigError;
+
+// Version written by this build. Files without `config_version` predate
+// versioning and are version 1, as are files with a version below 1.
+pub const CONFIG_VERSION: u32 = 3;
+
+// MIGRATIONS[n] upgrades a version n + 1 document to version n + 2
+const MIGRATIONS: [fn(&mut DocumentMut); (CONFIG_VERSION - 1) as usize] = [typed_values, drop_history];
+
+pub struct Migration {
+    pub from: u32,
+    pub backup: PathBuf,
+}
+
+// The version `contents` declares; None when it can't be parsed
+pub fn version(contents: &str) -> Option<u32> {
+    let document: DocumentMut = contents.parse().ok()?;
+    Some(document_version(&document))
+}
+
+// `contents` upgraded to CONFIG_VERSION, or None when it is already current,
+// newer than this build or not valid TOML
+pub fn upgrade(contents: &str) -> Option<String> {
+    let mut document: DocumentMut = contents.parse().ok()?;
+    let from = document_version(&document);
+    if from >= CONFIG_VERSION {
+        return None;
+    }
+
+    for step in &MIGRATIONS[from as usize - 1..] {
+        step(&mut document);
+    }
+    set_version(&mut document);
+    Some(document.to_string())
+}
+
+// `contents` read as the current version without adding `config_version`,
+// for files that are never written back. Line numbers stay put, so
+// diagnostics point into the file as it is on disk.
+pub(super) fn upgrade_values(contents: &str) -> String {
+    let Ok(mut document) = contents.parse::<DocumentMut>() else {
+        return contents.to_string();
+    };
+    let from = document_version(&document);
+    if from >= CONFIG_VERSION {
+        return contents.to_string();
+    }
+    for step in &MIGRATIONS[from as usize - 1..] {
+        step(&mut document);
+    }
+    document.to_string()
+}
+
+// Upgrade the file at `path` in place, keeping the original next to it as
+// `<name>.v<version>.bak`
+pub fn upgrade_file(path: &Path) -> Result<Option<Migration>, ConfigError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let _lock = store::lock(path)?;
+    let contents = fs::read_to_string(path)?;
+    let (Some(from), Some(upgraded)) = (version(&contents), upgrade(&contents)) else {
+        return Ok(None);
+    };
+
+    let backup = sibling(path, &format!(".v{}.bak", from));
+    store::store(&backup, &contents)?;
+    store::store(path, &upgraded)?;
+    Ok(Some(Migration { from, backup }))
+}
+
+fn document_version(document: &DocumentMut) -> u32 {
+    document
+        .get("config_version")
+        .and_then(Item::as_integer)
+        .and_then(|version| u32::try_from(version).ok())
+        .filter(|version| *version >= 1)
+        .unwrap_or(1)
+}
+
+// `config_version` goes first, under the comment that heads the file
+pub(super) fn set_version(document: &mut DocumentMut) {
+    let table = document.as_table_mut();
+    if let Some(value) = table.get_mut("config_version").and_then(Item::as_value_mut) {
+        let decor = value.decor().clone();
+        *value = Value::from(CONFIG_VERSION as i64);
+        *value.decor_mut() = decor;
+        return;
+    }
+
+    let first = table.iter().find(|(_, item)| item.is_value()).map(|(key, _)| key.to_string());
+    table.insert("config_version", Item::Value(Value::from(CONFIG_VERSION as i64)));
+    table.sort_values_by(|a, _, b, _| (b.get() == "config_version").cmp(&(a.get() == "config_version")));
+
+    let Some(first) = first else {
+        // Keep a blank line between the version and the first table, and
+        // move a comment heading the file above the version
+        let first_table = table.iter_mut().find_map(|(_, item)| item.as_table_mut());
+        let mut header = None;
+        if let Some(decor) = first_table.map(|table| table.decor_mut()) {
+            let prefix = decor.prefix().and_then(|prefix| prefix.as_str()).unwrap_or_default().to_string();
+            if !prefix.trim().is_empty() {
+                header = Some(prefix);
+                decor.set_prefix("\n");
+            } else if prefix.is_empty() {
+                decor.set_prefix("\n");
+            }
+        }
+        if let (Some(header), Some(mut key)) = (header, table.key_mut("config_version")) {
+            key.leaf_decor_mut().set_prefix(header.trim_end_matches('\n').to_string() + "\n");
+        }
+        return;
+    };
+    let header = table.key_mut(&first).and_then(|mut key| {
+        let prefix = key.leaf_decor().prefix().cloned();
+        key.leaf_decor_mut().set_prefix("");
+        prefix
+    });
+    if let (Some(header), Some(mut key)) = (header, table.key_mut("config_version")) {
+        key.leaf_decor_mut().set_prefix(header);
+    }
+}
+
+// Old spelling and the key it maps to
+type Aliases = &'static [(&'static str, &'static str)];
+
+// 1 -> 2: `updates.channel` and `ui.default_color_format` used to be free
+// strings. Lowercase them and the other choices, and map the spellings
+// older versions accepted onto the current keys.
+fn typed_values(document: &mut DocumentMut) {
+    let renames: [(&str, &str, Aliases); 4] = [
+        ("general", "theme", &[]),
+        ("updates", "channel", &[("nightly", "daily")]),
+        ("ui", "default_color_format", &[("rgba", "rgb"), ("hsla", "hsl")]),
+        ("ui", "mode", &[]),
+    ];
+
+    for (section, key, aliases) in renames {
+        let value = document.get_mut(section).and_then(|item| item.get_mut(key));
+        let Some(value) = value.and_then(Item::as_value_mut) else { continue };
+        let Some(text) = value.as_str() else { continue };
+
+        let normalized = text.trim().to_ascii_lowercase();
+        let normalized = match aliases.iter().find(|(old, _)| *old == normalized) {
+            Some((_, new)) => new.to_string(),
+            None => normalized,
+        };
+        if normalized != text {
+            let decor = value.decor().clone();
+            *value = Value::from(normalized);
+            *value.decor_mut() = decor;
+        }
+    }
+}
+
+// 2 -> 3: the color history moved to its own file (see `history`). The user
+// config's `color_history` is imported there before the upgrade, and the
+// backup keeps it.
+fn drop_history(document: &mut DocumentMut) {
+    let table = document.as_table_mut();
+    let Some(index) = table.iter().filter(|(_, item)| item.is_value()).position(|(key, _)| key == "color_history") else {
+        return;
+    };
+    let comments = table.key("color_history").and_then(|key| key.leaf_decor().prefix()).and_then(|prefix| prefix.as_str());
+    let comments = comments.unwrap_or_default().to_string();
+    table.remove("color_history");
+    if comments.trim().is_empty() {
+        return;
+    }
+
+    // The comments above it, such as the one heading the file, move to
+    // whatever now comes first in its place
+    let next = table.iter().filter(|(_, item)| item.is_value()).nth(index).map(|(key, _)| key.to_string());
+    if let Some(mut key) = next.and_then(|next| table.key_mut(&next)) {
+        let prefix = key.leaf_decor().prefix().and_then(|prefix| prefix.as_str()).unwrap_or_default();
+        let prefix = format!("{}{}", comments, prefix);
+        key.leaf_decor_mut().set_prefix(prefix);
+    } else if let Some(first_table) = table.iter_mut().find_map(|(_, item)| item.as_table_mut()) {
+        let decor = first_table.decor_mut();
+        let prefix = decor.prefix().and_then(|prefix| prefix.as_str()).unwrap_or_default();
+        let prefix = format!("{}{}", comments, prefix);
+        decor.set_prefix(prefix);
+    }
+}
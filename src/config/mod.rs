@@ -1,15 +1,44 @@
 // Configuration management
 
+mod edit;
+mod keys;
+mod layers;
+mod migrate;
+pub mod paths;
+mod store;
+mod validate;
+
+pub use edit::{set_key, unset_key};
+pub use keys::{Action, Key, KeyName, KeysConfig};
+pub use layers::{env_name, system_path, Layer, Layered, Origin, Sources, PROJECT_FILE};
+pub use migrate::{upgrade, upgrade_file, version, CONFIG_VERSION};
+pub use paths::{config_dir, data_dir, palettes_dir, portable_dir, CONFIG_DIR_ENV};
+pub use store::{modify, sibling, store};
+pub use validate::{Diagnostic, Severity};
+
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fmt;
+use std::str::FromStr;
+
+use crate::color::ColorFormat;
+use crate::display::UiMode;
+use crate::history;
+use validate::Removal;
+
+// Upper bound for `general.history_size`
+pub const MAX_HISTORY_SIZE: usize = 1000;
 
 #[derive(Debug)]
 pub enum ConfigError {
     IoError(std::io::Error),
     ParseError(toml::de::Error),
     SerializeError(toml::ser::Error),
+    SyntaxError(toml_edit::TomlError),
+    UnknownKey(String),
+    InvalidValue(String, String),
+    Locked(PathBuf),
     InvalidPath,
 }
 
@@ -19,6 +48,10 @@ impl fmt::Display for ConfigError {
             ConfigError::IoError(e) => write!(f, "IO error: {}", e),
             ConfigError::ParseError(e) => write!(f, "Failed to parse config: {}", e),
             ConfigError::SerializeError(e) => write!(f, "Failed to serialize config: {}", e),
+            ConfigError::SyntaxError(e) => write!(f, "Failed to parse config: {}", e.message().trim().replace('\n', ", ")),
+            ConfigError::UnknownKey(key) => write!(f, "Unknown config key: {}", key),
+            ConfigError::InvalidValue(key, message) => write!(f, "Invalid value for {}: {}", key, message),
+            ConfigError::Locked(path) => write!(f, "Another cascolor is still writing the config (lock: {})", path.display()),
             ConfigError::InvalidPath => write!(f, "Invalid config path"),
         }
     }
@@ -44,8 +77,17 @@ impl From<toml::ser::Error> for ConfigError {
     }
 }
 
+impl From<toml_edit::TomlError> for ConfigError {
+    fn from(e: toml_edit::TomlError) -> Self {
+        ConfigError::SyntaxError(e)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    // Schema version, see `migrate`
+    #[serde(default = "unversioned")]
+    pub config_version: u32,
     #[serde(default)]
     pub general: GeneralConfig,
     #[serde(default)]
@@ -53,7 +95,16 @@ pub struct Config {
     #[serde(default)]
     pub ui: UiConfig,
     #[serde(default)]
-    pub color_history: Vec<String>,
+    pub keys: KeysConfig,
+    // The config as loaded, so `save` writes only what changed since
+    #[serde(skip)]
+    loaded: Option<Box<Config>>,
+    // The user config file it was loaded from, which `save` writes to
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    // Where `save` moves the file's legacy `color_history` when it upgrades it
+    #[serde(skip)]
+    history: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,7 +115,7 @@ pub struct GeneralConfig {
     pub history_size: usize,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ThemeMode {
     Dark,
@@ -73,10 +124,81 @@ pub enum ThemeMode {
     Auto,
 }
 
+impl ThemeMode {
+    pub const ALL: [ThemeMode; 4] = [ThemeMode::Dark, ThemeMode::Light, ThemeMode::System, ThemeMode::Auto];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            ThemeMode::Dark => "dark",
+            ThemeMode::Light => "light",
+            ThemeMode::System => "system",
+            ThemeMode::Auto => "auto",
+        }
+    }
+}
+
+impl fmt::Display for ThemeMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for ThemeMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        ThemeMode::ALL
+            .iter()
+            .copied()
+            .find(|theme| theme.key() == s)
+            .ok_or_else(|| format!("Unknown theme: {}", s))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    Stable,
+    Beta,
+    Daily,
+}
+
+impl UpdateChannel {
+    pub const ALL: [UpdateChannel; 3] = [UpdateChannel::Stable, UpdateChannel::Beta, UpdateChannel::Daily];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "stable",
+            UpdateChannel::Beta => "beta",
+            UpdateChannel::Daily => "daily",
+        }
+    }
+}
+
+impl fmt::Display for UpdateChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for UpdateChannel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        UpdateChannel::ALL
+            .iter()
+            .copied()
+            .find(|channel| channel.key() == s)
+            .ok_or_else(|| format!("Unknown update channel: {}", s))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateConfig {
     #[serde(default = "default_channel")]
-    pub channel: String,
+    pub channel: UpdateChannel,
     #[serde(default = "default_true")]
     pub check_on_startup: bool,
     #[serde(default = "default_true")]
@@ -92,16 +214,22 @@ pub struct UiConfig {
     #[serde(default = "default_true")]
     pub remember_window_position: bool,
     #[serde(default = "default_format")]
-    pub default_color_format: String,
+    pub default_color_format: ColorFormat,
+    #[serde(default = "default_mode")]
+    pub mode: UiMode,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
+            config_version: CONFIG_VERSION,
             general: GeneralConfig::default(),
             updates: UpdateConfig::default(),
             ui: UiConfig::default(),
-            color_history: Vec::new(),
+            keys: KeysConfig::default(),
+            loaded: None,
+            path: None,
+            history: None,
         }
     }
 }
@@ -132,10 +260,15 @@ impl Default for UiConfig {
             show_system_tray: true,
             remember_window_position: true,
             default_color_format: default_format(),
+            mode: default_mode(),
         }
     }
 }
 
+fn unversioned() -> u32 {
+    1
+}
+
 fn default_theme() -> ThemeMode {
     ThemeMode::Dark
 }
@@ -144,64 +277,149 @@ fn default_history_size() -> usize {
     20
 }
 
-fn default_channel() -> String {
-    "stable".to_string()
+fn default_channel() -> UpdateChannel {
+    UpdateChannel::Stable
 }
 
 fn default_true() -> bool {
     true
 }
 
-fn default_format() -> String {
-    "hex".to_string()
+fn default_format() -> ColorFormat {
+    ColorFormat::Hex
+}
+
+fn default_mode() -> UiMode {
+    UiMode::Auto
 }
 
 impl Config {
-    pub fn load() -> Self {
-        match Self::try_load() {
-            Ok(config) => config,
-            Err(e) => {
-                eprintln!("Warning: Failed to load config ({}), using defaults", e);
-                Self::default()
-            }
+    // The effective config from every layer (see `layers`). Invalid values
+    // in files and the environment fall back with a warning on stderr; an
+    // invalid `--set` is an error.
+    pub fn load(file: Option<&Path>, overrides: &[(String, String)]) -> Result<Self, ConfigError> {
+        let layered = Self::load_layered(&Sources::discover(file, overrides))?;
+        for problem in &layered.problems {
+            eprintln!("Warning: {}", problem);
         }
+        if !layered.problems.is_empty() {
+            eprintln!("Warning: Using defaults for the values above; run `cascolor config check` for details");
+        }
+        Ok(layered.config)
     }
 
-    fn try_load() -> Result<Self, ConfigError> {
-        let path = Self::config_path()?;
-        
-        if path.exists() {
-            let contents = fs::read_to_string(&path)?;
-            Ok(toml::from_str(&contents)?)
-        } else {
-            Ok(Self::default())
-        }
+    // The effective config from `sources`, with where each value came from
+    pub fn load_layered(sources: &Sources) -> Result<Layered, ConfigError> {
+        layers::load(sources)
     }
 
-    pub fn save(&self) -> Result<(), ConfigError> {
-        let path = Self::config_path()?;
-        
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
-        }
+    // Parse a config file's contents. Values that fail validation are
+    // reported and replaced by their defaults; a syntax error means all
+    // defaults.
+    pub fn parse(contents: &str) -> (Self, Vec<Diagnostic>) {
+        let (valid, diagnostics) = valid_part(contents);
+        // Validation checks every key serde reads, so this only fails on a
+        // bug in the validator
+        let config: Self = toml::from_str(&valid).unwrap_or_default();
+        (config.snapshot(), diagnostics)
+    }
 
-        let contents = toml::to_string_pretty(self)?;
-        fs::write(&path, contents)?;
+    // Remember this config as loaded, for `save`
+    fn snapshot(mut self) -> Self {
+        self.loaded = None;
+        self.loaded = Some(Box::new(self.clone()));
+        self
+    }
 
-        Ok(())
+    // Write changed values back to the config file, keeping its comments.
+    // An older file is upgraded first; the note says so.
+    pub fn save(&mut self) -> Result<Option<String>, ConfigError> {
+        let path = match &self.path {
+            Some(path) => path.clone(),
+            None => Self::config_path(None)?,
+        };
+        let note = upgrade_with_note(&path, self.history.as_deref())?;
+        modify(&path, |contents| self.update(contents))?;
+        *self = self.clone().snapshot();
+        Ok(note)
     }
 
-    fn config_path() -> Result<PathBuf, ConfigError> {
-        let mut path = dirs::config_dir().ok_or(ConfigError::InvalidPath)?;
-        path.push("casapps");
-        path.push("cascolor");
-        path.push("config.toml");
-        Ok(path)
+    // A fresh config file with every default spelled out
+    pub fn default_contents() -> String {
+        toml::to_string_pretty(&Self::default()).expect("default config serializes")
     }
-    
-    pub fn config_path_string() -> String {
-        Self::config_path()
-            .map(|p| p.display().to_string())
-            .unwrap_or_else(|_| "Unknown".to_string())
+
+    // The user config file: `file` (from `--config`) when given, otherwise
+    // config.toml in CASCOLOR_CONFIG_DIR, the portable directory or the
+    // platform config directory
+    pub fn config_path(file: Option<&Path>) -> Result<PathBuf, ConfigError> {
+        match file {
+            Some(file) => Ok(file.to_path_buf()),
+            None => Ok(config_dir()?.join("config.toml")),
+        }
     }
 }
+
+// Upgrade an older config file before a command changes it, saying so on
+// stderr. Loading only upgrades in memory, so reading never rewrites it.
+pub fn upgrade_in_place(path: &Path, history: Option<&Path>) -> Result<(), ConfigError> {
+    if let Some(note) = upgrade_with_note(path, history)? {
+        eprintln!("{}", note);
+    }
+    Ok(())
+}
+
+// Upgrade an older config file and describe what was done. Its legacy
+// `color_history` goes to the history file at `history` first, when given;
+// otherwise only the backup keeps it.
+fn upgrade_with_note(path: &Path, history: Option<&Path>) -> Result<Option<String>, ConfigError> {
+    let outdated = fs::read_to_string(path).ok().and_then(|contents| upgrade(&contents)).is_some();
+    let imported = match history {
+        Some(history) if outdated => history::import_legacy(path, history)?,
+        _ => 0,
+    };
+    Ok(upgrade_file(path)?.map(|migration| {
+        let mut note = format!(
+            "Upgraded {} from config version {} to {}; the original is saved as {}",
+            path.display(),
+            migration.from,
+            CONFIG_VERSION,
+            migration.backup.display()
+        );
+        if let Some(history) = history.filter(|_| imported > 0) {
+            note.push_str(&format!("; moved {} history colors to {}", imported, history.display()));
+        }
+        note
+    }))
+}
+
+// The TOML of `contents` that passed validation, and the problems found
+fn valid_part(contents: &str) -> (String, Vec<Diagnostic>) {
+    match validate::validate(contents) {
+        Ok(validation) if validation.removals.is_empty() => (contents.to_string(), validation.diagnostics),
+        Ok(validation) => (without(contents, &validation.removals), validation.diagnostics),
+        Err(diagnostic) => (String::new(), vec![diagnostic]),
+    }
+}
+
+// `contents` with the rejected values taken out
+fn without(contents: &str, removals: &[Removal]) -> String {
+    let Ok(mut document) = contents.parse::<toml_edit::DocumentMut>() else {
+        return contents.to_string();
+    };
+
+    for removal in removals {
+        match removal {
+            Removal::Section(section) => {
+                document.remove(section);
+            }
+            Removal::Key(section, key) => {
+                if let Some(table) = document.get_mut(section).and_then(|item| item.as_table_like_mut()) {
+                    table.remove(key);
+                }
+            }
+        }
+    }
+
+    document.to_string()
+}
@@ -0,0 +1,56 @@
+// Where cascolor keeps its files. `--config` names the config file itself;
+// otherwise it lives in CASCOLOR_CONFIG_DIR, the portable directory or the
+// platform config directory, in that order.
+//
+// Portable mode is for running from removable drives on machines where the
+// user profile is off limits: a `cascolor.portable` file next to the
+// executable keeps the config, history and palettes in `cascolor-data`
+// beside it instead.
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use super::ConfigError;
+
+pub const CONFIG_DIR_ENV: &str = "CASCOLOR_CONFIG_DIR";
+pub const PORTABLE_MARKER: &str = "cascolor.portable";
+pub const PORTABLE_DIR: &str = "cascolor-data";
+
+// The portable data directory, when the running executable has a marker
+pub fn portable_dir() -> Option<PathBuf> {
+    let exe = env::current_exe().ok()?;
+    portable_dir_for(&exe)
+}
+
+// The portable data directory for the executable at `exe`
+pub fn portable_dir_for(exe: &Path) -> Option<PathBuf> {
+    let dir = exe.parent()?;
+    dir.join(PORTABLE_MARKER).is_file().then(|| dir.join(PORTABLE_DIR))
+}
+
+pub fn config_dir() -> Result<PathBuf, ConfigError> {
+    if let Some(dir) = env::var_os(CONFIG_DIR_ENV).filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+    if let Some(dir) = portable_dir() {
+        return Ok(dir);
+    }
+    let dir = dirs::config_dir().ok_or(ConfigError::InvalidPath)?;
+    Ok(dir.join("casapps").join("cascolor"))
+}
+
+// Application data other than the config
+pub fn data_dir() -> Result<PathBuf, ConfigError> {
+    if let Some(dir) = portable_dir() {
+        return Ok(dir);
+    }
+    let dir = dirs::data_dir().ok_or(ConfigError::InvalidPath)?;
+    Ok(dir.join("casapps").join("cascolor"))
+}
+
+// Where the TUI opens and exports palettes given by a relative path; only
+// portable mode has one, elsewhere those paths are relative to the current
+// directory
+pub fn palettes_dir() -> Option<PathBuf> {
+    portable_dir().map(|dir| dir.join("palettes"))
+}
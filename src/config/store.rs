@@ -0,0 +1,93 @@
+// Safe config writes: an advisory lock around read-modify-write, so two
+// running instances take turns, and write-to-temp-then-rename, so a crash
+// never leaves a half-written file
+
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::ConfigError;
+
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+const LOCK_RETRY: Duration = Duration::from_millis(25);
+
+// Held while reading and rewriting the config; released on drop
+pub struct ConfigLock {
+    _file: File,
+}
+
+// Lock `path` against other cascolor instances. The lock lives in a
+// separate `<name>.lock` file because the rename in `store` replaces the
+// config file itself.
+pub fn lock(path: &Path) -> Result<ConfigLock, ConfigError> {
+    let lock_path = sibling(path, ".lock");
+    if let Some(parent) = lock_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = OpenOptions::new().create(true).truncate(false).write(true).open(&lock_path)?;
+
+    let started = Instant::now();
+    loop {
+        match file.try_lock() {
+            Ok(()) => return Ok(ConfigLock { _file: file }),
+            Err(TryLockError::WouldBlock) if started.elapsed() < LOCK_TIMEOUT => thread::sleep(LOCK_RETRY),
+            Err(TryLockError::WouldBlock) => return Err(ConfigError::Locked(lock_path)),
+            Err(TryLockError::Error(e)) => return Err(e.into()),
+        }
+    }
+}
+
+// Replace the file at `path` in one step, creating its directory if needed.
+// A symlinked config is written through to its target.
+pub fn store(path: &Path, contents: &str) -> Result<(), ConfigError> {
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let temp = sibling(&path, &format!(".{}.tmp", process::id()));
+    let written = write_synced(&temp, contents, &path).and_then(|()| fs::rename(&temp, &path));
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+// Read the file at `path` (empty when missing), let `change` rewrite it and
+// store the result, all under the lock
+pub fn modify<F>(path: &Path, change: F) -> Result<(), ConfigError>
+where
+    F: FnOnce(&str) -> Result<String, ConfigError>,
+{
+    let _lock = lock(path)?;
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let updated = change(&contents)?;
+    if updated != contents || !path.exists() {
+        store(path, &updated)?;
+    }
+    Ok(())
+}
+
+fn write_synced(temp: &Path, contents: &str, original: &Path) -> io::Result<()> {
+    let mut file = File::create(temp)?;
+    file.write_all(contents.as_bytes())?;
+    if let Ok(metadata) = fs::metadata(original) {
+        file.set_permissions(metadata.permissions())?;
+    }
+    file.sync_all()
+}
+
+// `path` with `suffix` added to its file name
+pub fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
@@ -0,0 +1,227 @@
+// Config validation: walk the parsed TOML, check every known key and report
+// problems with their position in the file
+
+use std::fmt;
+use std::ops::Range;
+
+use serde::Serialize;
+use toml_edit::{ImDocument, Item, TableLike};
+
+use super::{Action, Key, ThemeMode, UpdateChannel, CONFIG_VERSION, MAX_HISTORY_SIZE};
+use crate::color::ColorFormat;
+use crate::display::UiMode;
+use crate::project::PROJECT_SECTIONS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    // The value is ignored and its default used instead
+    Error,
+    // Harmless but probably a mistake, such as a misspelled key
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    // Dotted path of the offending key, e.g. "general.history_size"; empty
+    // for syntax errors
+    pub key: String,
+    pub message: String,
+    // 1-based; the column counts characters
+    pub line: usize,
+    pub column: usize,
+    // Byte range in the file
+    #[serde(skip)]
+    pub span: Range<usize>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}: ", self.line, self.column, self.severity)?;
+        if !self.key.is_empty() {
+            write!(f, "{}: ", self.key)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+// A value to drop before deserializing, so its default is used
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Removal {
+    Section(&'static str),
+    Key(&'static str, &'static str),
+}
+
+pub(super) enum Kind {
+    Bool,
+    Choice(Vec<&'static str>),
+    Integer(i64, i64),
+    Key,
+}
+
+// Every known section and its keys
+pub(super) fn sections() -> [(&'static str, Vec<(&'static str, Kind)>); 4] {
+    [
+        (
+            "general",
+            vec![
+                ("theme", Kind::Choice(ThemeMode::ALL.map(|t| t.key()).to_vec())),
+                ("history_size", Kind::Integer(0, MAX_HISTORY_SIZE as i64)),
+            ],
+        ),
+        (
+            "updates",
+            vec![
+                ("channel", Kind::Choice(UpdateChannel::ALL.map(|c| c.key()).to_vec())),
+                ("check_on_startup", Kind::Bool),
+                ("check_in_background", Kind::Bool),
+                ("prompt_before_update", Kind::Bool),
+            ],
+        ),
+        (
+            "ui",
+            vec![
+                ("show_system_tray", Kind::Bool),
+                ("remember_window_position", Kind::Bool),
+                ("default_color_format", Kind::Choice(ColorFormat::ALL.map(|f| f.key()).to_vec())),
+                ("mode", Kind::Choice(UiMode::ALL.map(|m| m.key()).to_vec())),
+            ],
+        ),
+        ("keys", Action::ALL.iter().map(|action| (action.key(), Kind::Key)).collect()),
+    ]
+}
+
+pub(super) struct Validation {
+    pub diagnostics: Vec<Diagnostic>,
+    pub removals: Vec<Removal>,
+}
+
+pub(super) fn validate(contents: &str) -> Result<Validation, Diagnostic> {
+    let document = ImDocument::parse(contents).map_err(|e| {
+        let span = e.span().unwrap_or(0..0);
+        let message = e.message().trim().replace('\n', ", ");
+        diagnostic(contents, Severity::Error, String::new(), message, span)
+    })?;
+
+    let mut validation = Validation { diagnostics: Vec::new(), removals: Vec::new() };
+    let root = document.as_table();
+    let sections = sections();
+
+    for (name, item) in root.iter() {
+        let span = key_span(root, name);
+        // Moved to the history file in version 3
+        if name == "color_history" {
+            let message = "the history is kept in its own file now; import it with `cascolor history import`".to_string();
+            validation.diagnostics.push(diagnostic(contents, Severity::Warning, name.to_string(), message, span));
+            continue;
+        }
+        if name == "config_version" {
+            check_version(contents, item, &mut validation);
+            continue;
+        }
+        // Read by `project` from .cascolor.toml, not part of the config
+        if PROJECT_SECTIONS.contains(&name) {
+            continue;
+        }
+
+        let Some((section, keys)) = sections.iter().find(|(section, _)| *section == name) else {
+            let message = "unknown key, ignored".to_string();
+            validation.diagnostics.push(diagnostic(contents, Severity::Warning, name.to_string(), message, span));
+            continue;
+        };
+        let Some(table) = item.as_table_like() else {
+            let message = format!("expected a table, got {}", item.type_name());
+            validation.diagnostics.push(diagnostic(contents, Severity::Error, name.to_string(), message, span));
+            validation.removals.push(Removal::Section(section));
+            continue;
+        };
+
+        for (key, value) in table.iter() {
+            let path = format!("{}.{}", section, key);
+            let Some((known, kind)) = keys.iter().find(|(known, _)| *known == key) else {
+                let span = key_span(table, key);
+                let message = "unknown key, ignored".to_string();
+                validation.diagnostics.push(diagnostic(contents, Severity::Warning, path, message, span));
+                continue;
+            };
+            if let Some(message) = check_value(value, kind) {
+                let span = value.span().unwrap_or_else(|| key_span(table, key));
+                validation.diagnostics.push(diagnostic(contents, Severity::Error, path, message, span));
+                validation.removals.push(Removal::Key(section, known));
+            }
+        }
+    }
+
+    Ok(validation)
+}
+
+pub(super) fn check_value(item: &Item, kind: &Kind) -> Option<String> {
+    match kind {
+        Kind::Bool if item.as_bool().is_none() => Some(format!("expected true or false, got {}", describe(item))),
+        Kind::Bool => None,
+        Kind::Choice(keys) => match item.as_str() {
+            Some(value) if keys.contains(&value) => None,
+            _ => Some(format!("expected one of {}, got {}", keys.join(", "), describe(item))),
+        },
+        Kind::Integer(min, max) => match item.as_integer() {
+            Some(value) if (*min..=*max).contains(&value) => None,
+            _ => Some(format!("expected an integer from {} to {}, got {}", min, max, describe(item))),
+        },
+        Kind::Key => match item.as_str().map(str::parse::<Key>) {
+            Some(Ok(_)) => None,
+            Some(Err(e)) => Some(format!("{}; expected a character, a key name like tab or f2, or ctrl+<key>", e)),
+            None => Some(format!("expected a key, got {}", describe(item))),
+        },
+    }
+}
+
+fn check_version(contents: &str, item: &Item, validation: &mut Validation) {
+    let span = item.span().unwrap_or(0..0);
+    let key = "config_version".to_string();
+    match item.as_integer() {
+        Some(version) if version > CONFIG_VERSION as i64 => {
+            let message = format!("written by a newer cascolor (this one reads up to {}); newer settings are ignored", CONFIG_VERSION);
+            validation.diagnostics.push(diagnostic(contents, Severity::Warning, key, message, span));
+        }
+        Some(version) if version >= 1 => {}
+        _ => {
+            let message = format!("expected a version from 1 to {}, got {}", CONFIG_VERSION, describe(item));
+            validation.diagnostics.push(diagnostic(contents, Severity::Error, key, message, span));
+            validation.removals.push(Removal::Section("config_version"));
+        }
+    }
+}
+
+// A value as written in the file, for messages
+fn describe(item: &Item) -> String {
+    match item.as_value() {
+        Some(value) => value.to_string().trim().to_string(),
+        None => item.type_name().to_string(),
+    }
+}
+
+fn key_span(table: &dyn TableLike, key: &str) -> Range<usize> {
+    table.get_key_value(key).and_then(|(key, _)| key.span()).unwrap_or(0..0)
+}
+
+fn diagnostic(contents: &str, severity: Severity, key: String, message: String, span: Range<usize>) -> Diagnostic {
+    let (line, column) = line_column(contents, span.start);
+    Diagnostic { severity, key, message, line, column, span }
+}
+
+fn line_column(contents: &str, offset: usize) -> (usize, usize) {
+    let before = contents.get(..offset).unwrap_or(contents);
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    (line, column)
+}
@@ -0,0 +1,175 @@
+// UI mode selection and display detection
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UiMode {
+    Auto,
+    Gui,
+    Tui,
+    Headless,
+}
+
+impl UiMode {
+    pub const ALL: [UiMode; 4] = [UiMode::Auto, UiMode::Gui, UiMode::Tui, UiMode::Headless];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            UiMode::Auto => "auto",
+            UiMode::Gui => "gui",
+            UiMode::Tui => "tui",
+            UiMode::Headless => "headless",
+        }
+    }
+}
+
+impl fmt::Display for UiMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for UiMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(UiMode::Auto),
+            "gui" => Ok(UiMode::Gui),
+            "tui" => Ok(UiMode::Tui),
+            "headless" => Ok(UiMode::Headless),
+            other => Err(format!("Unknown UI mode: {}", other)),
+        }
+    }
+}
+
+// Everything detection looks at, gathered up front so it can be faked in tests
+#[derive(Debug, Clone, Default)]
+pub struct Environment {
+    pub vars: HashMap<String, String>,
+    pub stdout_is_tty: bool,
+    // Windows and macOS always run inside a desktop session
+    pub native_display: bool,
+}
+
+impl Environment {
+    pub fn current() -> Self {
+        use std::io::IsTerminal;
+
+        Self {
+            vars: std::env::vars().collect(),
+            stdout_is_tty: std::io::stdout().is_terminal(),
+            native_display: cfg!(any(target_os = "windows", target_os = "macos")),
+        }
+    }
+
+    fn var(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(|v| v.as_str()).filter(|v| !v.is_empty())
+    }
+
+    pub fn is_ssh(&self) -> bool {
+        self.var("SSH_CONNECTION").is_some() || self.var("SSH_CLIENT").is_some() || self.var("SSH_TTY").is_some()
+    }
+
+    // Whether the environment claims a display; probe_display() confirms it
+    pub fn has_display_hint(&self) -> bool {
+        if self.native_display {
+            return true;
+        }
+
+        // A text console keeps stale DISPLAY values inherited from tmux or
+        // screen; trust XDG_SESSION_TYPE unless we came in over SSH (X forwarding)
+        if self.var("XDG_SESSION_TYPE") == Some("tty") && !self.is_ssh() {
+            return false;
+        }
+
+        self.var("DISPLAY").is_some() || self.var("WAYLAND_DISPLAY").is_some()
+    }
+}
+
+// Resolve the requested mode to a concrete one. `probe_display` is only
+// called when the environment suggests a display is available.
+pub fn detect_mode(requested: UiMode, env: &Environment, probe_display: impl FnOnce(&Environment) -> bool) -> UiMode {
+    if requested != UiMode::Auto {
+        return requested;
+    }
+
+    if env.has_display_hint() && probe_display(env) {
+        return UiMode::Gui;
+    }
+
+    if env.stdout_is_tty {
+        UiMode::Tui
+    } else {
+        UiMode::Headless
+    }
+}
+
+// Try to actually connect to the X11 or Wayland display named in the environment
+pub fn probe_display(env: &Environment) -> bool {
+    if env.native_display {
+        return true;
+    }
+
+    #[cfg(unix)]
+    {
+        let wayland = env.var("WAYLAND_DISPLAY").is_some_and(|name| probe_wayland(env, name));
+        wayland || env.var("DISPLAY").is_some_and(probe_x11)
+    }
+
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+#[cfg(unix)]
+fn probe_wayland(env: &Environment, name: &str) -> bool {
+    use std::os::unix::net::UnixStream;
+    use std::path::PathBuf;
+
+    let path = if name.starts_with('/') {
+        PathBuf::from(name)
+    } else {
+        match env.var("XDG_RUNTIME_DIR") {
+            Some(dir) => PathBuf::from(dir).join(name),
+            None => return false,
+        }
+    };
+
+    UnixStream::connect(path).is_ok()
+}
+
+// DISPLAY is [host]:display[.screen]; an empty host (or "unix") means the
+// local socket in /tmp/.X11-unix, anything else is TCP port 6000 + display
+#[cfg(unix)]
+fn probe_x11(display: &str) -> bool {
+    use std::net::{TcpStream, ToSocketAddrs};
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+
+    // macOS XQuartz sets DISPLAY to a launchd socket path
+    if display.starts_with('/') {
+        return UnixStream::connect(display.split(':').next().unwrap_or(display)).is_ok();
+    }
+
+    let Some((host, rest)) = display.rsplit_once(':') else {
+        return false;
+    };
+    let Ok(number) = rest.split('.').next().unwrap_or("").parse::<u16>() else {
+        return false;
+    };
+
+    if host.is_empty() || host == "unix" {
+        return UnixStream::connect(format!("/tmp/.X11-unix/X{}", number)).is_ok();
+    }
+
+    let Ok(mut addrs) = (host, 6000 + number).to_socket_addrs() else {
+        return false;
+    };
+    addrs.any(|addr| TcpStream::connect_timeout(&addr, Duration::from_millis(500)).is_ok())
+}
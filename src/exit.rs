@@ -0,0 +1,296 @@
+// Process exit codes and machine-readable error reports
+//
+// Wrapper scripts branch on these codes, so existing values must never be
+// renumbered; add new ones at the end.
+
+use image::ImageError;
+use serde::Serialize;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::batch::BatchError;
+use crate::clipboard::ClipboardError;
+use crate::config::ConfigError;
+use crate::extract::ExtractError;
+use crate::palette::PaletteError;
+use crate::theme::ThemeError;
+use crate::update::UpdateError;
+
+// Schema version of the `--error-format json` document
+pub const ERROR_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Success,
+    Failure,
+    Usage,
+    Parse,
+    Config,
+    ClipboardUnavailable,
+    Network,
+    Terminal,
+    UpdateAvailable,
+}
+
+impl ExitCode {
+    pub fn code(&self) -> i32 {
+        match self {
+            ExitCode::Success => 0,
+            ExitCode::Failure => 1,
+            // Matches clap's own exit status for bad arguments
+            ExitCode::Usage => 2,
+            ExitCode::Parse => 3,
+            ExitCode::Config => 4,
+            ExitCode::ClipboardUnavailable => 5,
+            ExitCode::Network => 6,
+            ExitCode::Terminal => 7,
+            ExitCode::UpdateAvailable => 8,
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            ExitCode::Success => "success",
+            ExitCode::Failure => "failure",
+            ExitCode::Usage => "usage",
+            ExitCode::Parse => "parse",
+            ExitCode::Config => "config",
+            ExitCode::ClipboardUnavailable => "clipboard-unavailable",
+            ExitCode::Network => "network",
+            ExitCode::Terminal => "terminal",
+            ExitCode::UpdateAvailable => "update-available",
+        }
+    }
+}
+
+impl fmt::Display for ExitCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl From<&ConfigError> for ExitCode {
+    fn from(e: &ConfigError) -> Self {
+        match e {
+            ConfigError::UnknownKey(_) | ConfigError::InvalidValue(..) => ExitCode::Usage,
+            _ => ExitCode::Config,
+        }
+    }
+}
+
+impl From<&ClipboardError> for ExitCode {
+    fn from(_: &ClipboardError) -> Self {
+        ExitCode::ClipboardUnavailable
+    }
+}
+
+impl From<&UpdateError> for ExitCode {
+    fn from(e: &UpdateError) -> Self {
+        match e {
+            UpdateError::NetworkError(_) | UpdateError::ParseError(_) => ExitCode::Network,
+            UpdateError::NotFound => ExitCode::Failure,
+            UpdateError::InvalidChannel(_) => ExitCode::Usage,
+        }
+    }
+}
+
+impl From<&BatchError> for ExitCode {
+    fn from(e: &BatchError) -> Self {
+        match e {
+            BatchError::IoError(_) => ExitCode::Failure,
+            BatchError::MissingColumn(_) => ExitCode::Usage,
+            BatchError::InvalidColor { .. } => ExitCode::Parse,
+        }
+    }
+}
+
+impl From<&ThemeError> for ExitCode {
+    fn from(e: &ThemeError) -> Self {
+        match e {
+            ThemeError::TooFewColors { .. } => ExitCode::Parse,
+        }
+    }
+}
+
+impl From<&ExtractError> for ExitCode {
+    fn from(e: &ExtractError) -> Self {
+        match e {
+            ExtractError::Image(ImageError::Decoding(_) | ImageError::Unsupported(_)) => ExitCode::Parse,
+            ExtractError::Image(_) => ExitCode::Failure,
+            ExtractError::NoPixels { .. } | ExtractError::Transparent { .. } => ExitCode::Parse,
+            ExtractError::OutOfBounds { .. } => ExitCode::Usage,
+        }
+    }
+}
+
+impl From<&PaletteError> for ExitCode {
+    fn from(e: &PaletteError) -> Self {
+        match e {
+            PaletteError::IoError(_) => ExitCode::Failure,
+            PaletteError::UnknownFormat(_) => ExitCode::Usage,
+            PaletteError::ParseError { .. } | PaletteError::InvalidData { .. } | PaletteError::InvalidToken { .. } => {
+                ExitCode::Parse
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    Text,
+    Json,
+}
+
+impl ErrorFormat {
+    // Find `--error-format` in raw arguments, for errors raised before clap
+    // has finished parsing them
+    pub fn from_args<I, S>(args: I) -> ErrorFormat
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut format = ErrorFormat::Text;
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let value = match arg.as_ref().strip_prefix("--error-format") {
+                Some("") => args.next().map(|v| v.as_ref().to_string()),
+                Some(rest) => rest.strip_prefix('=').map(|v| v.to_string()),
+                None => None,
+            };
+            if let Some(parsed) = value.and_then(|v| v.parse().ok()) {
+                format = parsed;
+            }
+        }
+
+        format
+    }
+}
+
+impl FromStr for ErrorFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "text" => Ok(ErrorFormat::Text),
+            "json" => Ok(ErrorFormat::Json),
+            other => Err(format!("Unknown error format: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorReport {
+    pub schema_version: u32,
+    pub code: i32,
+    pub kind: &'static str,
+    pub message: String,
+}
+
+// An error on its way out of main(), tagged with the exit code it maps to
+#[derive(Debug)]
+pub struct CliError {
+    pub exit_code: ExitCode,
+    pub message: String,
+}
+
+impl CliError {
+    pub fn new(exit_code: ExitCode, message: impl Into<String>) -> Self {
+        Self { exit_code, message: message.into() }
+    }
+
+    // Color and argument parsers in this crate report errors as plain strings
+    pub fn parse(message: impl Into<String>) -> Self {
+        Self::new(ExitCode::Parse, message)
+    }
+
+    // Prefix the message with the file it concerns
+    pub fn in_file(mut self, path: &Path) -> Self {
+        self.message = format!("{}: {}", path.display(), self.message);
+        self
+    }
+
+    pub fn report(&self) -> ErrorReport {
+        ErrorReport {
+            schema_version: ERROR_SCHEMA_VERSION,
+            code: self.exit_code.code(),
+            kind: self.exit_code.key(),
+            message: self.message.clone(),
+        }
+    }
+
+    // Print to stderr in the requested format
+    pub fn print(&self, format: ErrorFormat) {
+        match format {
+            ErrorFormat::Text => eprintln!("Error: {}", self.message),
+            ErrorFormat::Json => match serde_json::to_string(&self.report()) {
+                Ok(json) => eprintln!("{}", json),
+                Err(_) => eprintln!("Error: {}", self.message),
+            },
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<ConfigError> for CliError {
+    fn from(e: ConfigError) -> Self {
+        CliError::new(ExitCode::from(&e), e.to_string())
+    }
+}
+
+impl From<ClipboardError> for CliError {
+    fn from(e: ClipboardError) -> Self {
+        CliError::new(ExitCode::from(&e), e.to_string())
+    }
+}
+
+impl From<UpdateError> for CliError {
+    fn from(e: UpdateError) -> Self {
+        CliError::new(ExitCode::from(&e), e.to_string())
+    }
+}
+
+impl From<BatchError> for CliError {
+    fn from(e: BatchError) -> Self {
+        CliError::new(ExitCode::from(&e), e.to_string())
+    }
+}
+
+impl From<ExtractError> for CliError {
+    fn from(e: ExtractError) -> Self {
+        CliError::new(ExitCode::from(&e), e.to_string())
+    }
+}
+
+impl From<PaletteError> for CliError {
+    fn from(e: PaletteError) -> Self {
+        CliError::new(ExitCode::from(&e), e.to_string())
+    }
+}
+
+impl From<ThemeError> for CliError {
+    fn from(e: ThemeError) -> Self {
+        CliError::new(ExitCode::from(&e), e.to_string())
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(e: io::Error) -> Self {
+        CliError::new(ExitCode::Failure, e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for CliError {
+    fn from(e: serde_json::Error) -> Self {
+        CliError::new(ExitCode::Failure, e.to_string())
+    }
+}
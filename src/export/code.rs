@@ -0,0 +1,86 @@
+// Renderers for stylesheet and JavaScript/TypeScript source
+
+use super::{capitalize, native, palette_words, ExportFormat, ExportOptions, NameCase};
+use crate::color::CasColor;
+use crate::palette::Palette;
+
+// `entries` holds (variable name, color) pairs in palette order
+pub fn render(palette: &Palette, entries: &[(String, CasColor)], options: &ExportOptions) -> String {
+    let colors: Vec<(String, String)> = entries
+        .iter()
+        .map(|(name, color)| (name.clone(), color.format(options.color_format)))
+        .collect();
+    let colors = &colors[..];
+
+    match options.format {
+        ExportFormat::Css => {
+            let body: String = colors.iter().map(|(name, value)| format!("  --{}: {};\n", name, value)).collect();
+            format!(":root {{\n{}}}\n", body)
+        }
+        ExportFormat::Scss => colors.iter().map(|(name, value)| format!("${}: {};\n", name, value)).collect(),
+        ExportFormat::Less => colors.iter().map(|(name, value)| format!("@{}: {};\n", name, value)).collect(),
+        ExportFormat::Json => {
+            let body: Vec<String> = colors
+                .iter()
+                .map(|(name, value)| format!("  {}: {}", json_string(name), json_string(value)))
+                .collect();
+            if body.is_empty() {
+                "{}\n".to_string()
+            } else {
+                format!("{{\n{}\n}}\n", body.join(",\n"))
+            }
+        }
+        ExportFormat::Tailwind => format!(
+            "/** @type {{import('tailwindcss').Config}} */\nmodule.exports = {{\n  theme: {{\n    extend: {{\n      colors: {{\n{}      }},\n    }},\n  }},\n}};\n",
+            object_body(colors, 8)
+        ),
+        ExportFormat::TailwindTs => format!(
+            "import type {{ Config }} from 'tailwindcss';\n\nexport default {{\n  theme: {{\n    extend: {{\n      colors: {{\n{}      }},\n    }},\n  }},\n}} satisfies Partial<Config>;\n",
+            object_body(colors, 8)
+        ),
+        ExportFormat::Typescript => {
+            // Named after the palette, e.g. "Brand Colors" -> brandColors
+            let const_name = NameCase::Camel.join(&palette_words(palette));
+            let type_name = capitalize(&const_name);
+
+            format!(
+                "export const {} = {{\n{}}} as const;\n\nexport type {}Name = keyof typeof {};\n",
+                const_name,
+                object_body(colors, 2),
+                type_name,
+                const_name
+            )
+        }
+        ExportFormat::Android => native::android(entries),
+        ExportFormat::Compose => native::compose(&NameCase::Pascal.join(&palette_words(palette)), entries),
+        ExportFormat::SwiftUi => native::swiftui(&NameCase::Pascal.join(&palette_words(palette)), entries),
+        ExportFormat::Xcassets => native::asset_catalog_listing(entries),
+        ExportFormat::Flutter => native::flutter(&NameCase::Pascal.join(&palette_words(palette)), entries),
+    }
+}
+
+// `key: 'value',` lines, quoting keys that are not plain identifiers
+fn object_body(colors: &[(String, String)], indent: usize) -> String {
+    colors
+        .iter()
+        .map(|(name, value)| format!("{}{}: {},\n", " ".repeat(indent), js_key(name), js_string(value)))
+        .collect()
+}
+
+fn js_key(name: &str) -> String {
+    let is_identifier = name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name.to_string()
+    } else {
+        js_string(name)
+    }
+}
+
+fn js_string(text: &str) -> String {
+    format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn json_string(text: &str) -> String {
+    serde_json::Value::from(text).to_string()
+}
@@ -0,0 +1,384 @@
+// Export palettes as source code for stylesheets and front-end projects
+
+mod code;
+mod native;
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::color::{CasColor, ColorFormat};
+use crate::history::HistoryEntry;
+use crate::palette::{Palette, PaletteError, PaletteFormat};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Css,
+    Scss,
+    Less,
+    Tailwind,
+    TailwindTs,
+    Json,
+    Typescript,
+    Android,
+    Compose,
+    SwiftUi,
+    Xcassets,
+    Flutter,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 12] = [
+        ExportFormat::Css,
+        ExportFormat::Scss,
+        ExportFormat::Less,
+        ExportFormat::Tailwind,
+        ExportFormat::TailwindTs,
+        ExportFormat::Json,
+        ExportFormat::Typescript,
+        ExportFormat::Android,
+        ExportFormat::Compose,
+        ExportFormat::SwiftUi,
+        ExportFormat::Xcassets,
+        ExportFormat::Flutter,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            ExportFormat::Css => "css",
+            ExportFormat::Scss => "scss",
+            ExportFormat::Less => "less",
+            ExportFormat::Tailwind => "tailwind",
+            ExportFormat::TailwindTs => "tailwind-ts",
+            ExportFormat::Json => "json",
+            ExportFormat::Typescript => "ts",
+            ExportFormat::Android => "android",
+            ExportFormat::Compose => "compose",
+            ExportFormat::SwiftUi => "swiftui",
+            ExportFormat::Xcassets => "xcassets",
+            ExportFormat::Flutter => "flutter",
+        }
+    }
+
+    pub fn default_file_name(&self) -> &'static str {
+        match self {
+            ExportFormat::Css => "colors.css",
+            ExportFormat::Scss => "_colors.scss",
+            ExportFormat::Less => "colors.less",
+            ExportFormat::Tailwind => "tailwind.colors.js",
+            ExportFormat::TailwindTs => "tailwind.colors.ts",
+            ExportFormat::Json => "colors.json",
+            ExportFormat::Typescript => "colors.ts",
+            ExportFormat::Android => "colors.xml",
+            ExportFormat::Compose => "Colors.kt",
+            ExportFormat::SwiftUi => "Colors.swift",
+            ExportFormat::Xcassets => "Colors.xcassets",
+            ExportFormat::Flutter => "colors.dart",
+        }
+    }
+
+    // The format a file name suggests: colors.css is css, tailwind.colors.ts
+    // is tailwind-ts and any other .ts file is ts. None for extensions that
+    // no export format uses.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        let (stem, extension) = file_name.rsplit_once('.')?;
+        let tailwind = stem.starts_with("tailwind");
+        Some(match extension {
+            "css" => ExportFormat::Css,
+            "scss" => ExportFormat::Scss,
+            "less" => ExportFormat::Less,
+            "js" | "cjs" | "mjs" => ExportFormat::Tailwind,
+            "ts" if tailwind => ExportFormat::TailwindTs,
+            "ts" => ExportFormat::Typescript,
+            "json" => ExportFormat::Json,
+            "xml" => ExportFormat::Android,
+            "kt" => ExportFormat::Compose,
+            "swift" => ExportFormat::SwiftUi,
+            "xcassets" => ExportFormat::Xcassets,
+            "dart" => ExportFormat::Flutter,
+            _ => return None,
+        })
+    }
+
+    // Asset catalogs are written as a directory of JSON files
+    pub fn is_directory(&self) -> bool {
+        matches!(self, ExportFormat::Xcassets)
+    }
+
+    // The naming scheme each language conventionally uses
+    pub fn default_case(&self) -> NameCase {
+        match self {
+            ExportFormat::Typescript | ExportFormat::SwiftUi | ExportFormat::Flutter => NameCase::Camel,
+            ExportFormat::Compose | ExportFormat::Xcassets => NameCase::Pascal,
+            // Resource names may only contain letters, digits and underscores
+            ExportFormat::Android => NameCase::Snake,
+            _ => NameCase::Kebab,
+        }
+    }
+
+    // The naming schemes that give valid names in the format. Android
+    // resource names are lowercase by convention and can't hold hyphens.
+    pub fn cases(&self) -> &'static [NameCase] {
+        match self {
+            ExportFormat::Android => &[NameCase::Snake],
+            _ => &NameCase::ALL,
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        ExportFormat::ALL
+            .iter()
+            .copied()
+            .find(|format| format.key() == s)
+            .ok_or_else(|| format!("Unknown export format: {}", s))
+    }
+}
+
+// Any format a palette can be written in: a palette file or exported code.
+// Palette keys (gpl, ase, aco, tokens) and export keys do not overlap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteOutput {
+    Palette(PaletteFormat),
+    Code(ExportFormat),
+}
+
+impl PaletteOutput {
+    pub fn keys() -> Vec<&'static str> {
+        PaletteFormat::ALL.iter().map(|f| f.key()).chain(ExportFormat::ALL.iter().map(|f| f.key())).collect()
+    }
+
+    // Palette formats first, so colors.tokens.json is tokens and not json
+    pub fn from_path(path: &Path) -> Result<Self, PaletteError> {
+        PaletteFormat::from_path(path)
+            .map(PaletteOutput::Palette)
+            .or_else(|e| ExportFormat::from_path(path).map(PaletteOutput::Code).ok_or(e))
+    }
+}
+
+impl fmt::Display for PaletteOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaletteOutput::Palette(format) => write!(f, "{}", format),
+            PaletteOutput::Code(format) => write!(f, "{}", format),
+        }
+    }
+}
+
+impl FromStr for PaletteOutput {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<PaletteFormat>()
+            .map(PaletteOutput::Palette)
+            .or_else(|_| s.parse::<ExportFormat>().map(PaletteOutput::Code))
+            .map_err(|_| format!("Unknown output format: {}", s.trim()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameCase {
+    Kebab,
+    Camel,
+    Pascal,
+    Snake,
+}
+
+impl NameCase {
+    pub const ALL: [NameCase; 4] = [NameCase::Kebab, NameCase::Camel, NameCase::Pascal, NameCase::Snake];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            NameCase::Kebab => "kebab",
+            NameCase::Camel => "camel",
+            NameCase::Pascal => "pascal",
+            NameCase::Snake => "snake",
+        }
+    }
+
+    fn join(&self, words: &[String]) -> String {
+        match self {
+            NameCase::Kebab => words.join("-"),
+            NameCase::Snake => words.join("_"),
+            NameCase::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| if i == 0 { word.clone() } else { capitalize(word) })
+                .collect(),
+            NameCase::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+        }
+    }
+}
+
+impl fmt::Display for NameCase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for NameCase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        NameCase::ALL
+            .iter()
+            .copied()
+            .find(|case| case.key() == s)
+            .ok_or_else(|| format!("Unknown naming scheme: {}", s))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    pub case: NameCase,
+    // Joined to every name as its first word, e.g. "brand" -> brand-primary
+    pub prefix: String,
+    pub color_format: ColorFormat,
+}
+
+impl ExportOptions {
+    pub fn new(format: ExportFormat) -> Self {
+        Self {
+            format,
+            case: format.default_case(),
+            prefix: String::new(),
+            color_format: ColorFormat::Hex,
+        }
+    }
+}
+
+// Render `palette` as source code in the chosen format
+pub fn export(palette: &Palette, options: &ExportOptions) -> String {
+    code::render(palette, &named_colors(palette, options), options)
+}
+
+// Write the export to `path`, which is a directory for asset catalogs
+pub fn save(palette: &Palette, options: &ExportOptions, path: &Path) -> io::Result<()> {
+    if !options.format.is_directory() {
+        return fs::write(path, export(palette, options));
+    }
+
+    for (file, contents) in native::asset_catalog(&named_colors(palette, options)) {
+        let file = path.join(file);
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(file, contents)?;
+    }
+    Ok(())
+}
+
+fn named_colors(palette: &Palette, options: &ExportOptions) -> Vec<(String, CasColor)> {
+    variable_names(palette, options)
+        .into_iter()
+        .zip(palette.entries.iter().map(|entry| entry.color))
+        .collect()
+}
+
+// The color history as a palette, newest first; entries that no longer parse are skipped
+pub fn history_palette(history: &[HistoryEntry]) -> Palette {
+    let mut palette = Palette::new("history");
+    for color in history.iter().filter_map(HistoryEntry::color) {
+        palette.push(color, None);
+    }
+    palette
+}
+
+// One identifier per entry. Unnamed colors become color-1, color-2, ...
+// (by position) and clashes get a numeric suffix. A naming scheme the format
+// doesn't allow falls back to its default.
+pub fn variable_names(palette: &Palette, options: &ExportOptions) -> Vec<String> {
+    let case = match options.format.cases().contains(&options.case) {
+        true => options.case,
+        false => options.format.default_case(),
+    };
+    let prefix = words(&options.prefix);
+    let mut names: Vec<String> = Vec::with_capacity(palette.len());
+
+    for (index, entry) in palette.entries.iter().enumerate() {
+        let mut name_words = entry.name.as_deref().map(words).unwrap_or_default();
+        if name_words.is_empty() {
+            name_words = vec!["color".to_string(), (index + 1).to_string()];
+        }
+
+        // Identifiers cannot start with a digit in most of the target languages
+        let mut all_words = prefix.clone();
+        all_words.extend(name_words);
+        if all_words[0].starts_with(|c: char| c.is_ascii_digit()) {
+            all_words.insert(0, "color".to_string());
+        }
+
+        let mut name = case.join(&all_words);
+        let mut suffix = 2;
+        while names.contains(&name) {
+            let mut numbered = all_words.clone();
+            numbered.push(suffix.to_string());
+            name = case.join(&numbered);
+            suffix += 1;
+        }
+        names.push(name);
+    }
+
+    names
+}
+
+// Lowercase words split on punctuation, whitespace and camelCase boundaries
+fn words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+
+    for c in text.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+
+        if c.is_uppercase() && previous_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.extend(c.to_lowercase());
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+// Words naming the palette as a whole, e.g. "Brand Colors"; "colors" when unnamed
+fn palette_words(palette: &Palette) -> Vec<String> {
+    let mut palette_words = words(palette.name.as_deref().unwrap_or(""));
+    if palette_words.is_empty() || palette_words[0].starts_with(|c: char| c.is_ascii_digit()) {
+        palette_words.insert(0, "colors".to_string());
+    }
+    palette_words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
@@ -0,0 +1,135 @@
+// Renderers for Android, Jetpack Compose, SwiftUI, Xcode asset catalogs and Flutter.
+// Each uses its platform's own color literal, so `color_format` does not apply.
+
+use std::path::PathBuf;
+
+use crate::color::CasColor;
+
+// Names that are reserved words in the generated language are escaped:
+// with backticks in Kotlin and Swift, and with a trailing underscore in Dart
+// and in Android resource names, which become fields of Java's R class
+const JAVA_KEYWORDS: &[&str] = &[
+    "abstract", "assert", "boolean", "break", "byte", "case", "catch", "char", "class", "const", "continue",
+    "default", "do", "double", "else", "enum", "extends", "false", "final", "finally", "float", "for", "goto", "if",
+    "implements", "import", "instanceof", "int", "interface", "long", "native", "new", "null", "package", "private",
+    "protected", "public", "return", "short", "static", "strictfp", "super", "switch", "synchronized", "this", "throw",
+    "throws", "transient", "true", "try", "void", "volatile", "while",
+];
+
+const KOTLIN_KEYWORDS: &[&str] = &[
+    "as", "break", "class", "continue", "do", "else", "false", "for", "fun", "if", "in", "interface", "is", "null",
+    "object", "package", "return", "super", "this", "throw", "true", "try", "typealias", "typeof", "val", "var",
+    "when", "while",
+];
+
+const SWIFT_KEYWORDS: &[&str] = &[
+    "Any", "Self", "as", "associatedtype", "await", "break", "case", "catch", "class", "continue", "default", "defer",
+    "deinit", "do", "else", "enum", "extension", "fallthrough", "false", "fileprivate", "for", "func", "guard", "if",
+    "import", "in", "init", "inout", "internal", "is", "let", "nil", "open", "operator", "precedencegroup", "private",
+    "protocol", "public", "repeat", "rethrows", "return", "self", "static", "struct", "subscript", "super", "switch",
+    "throw", "throws", "true", "try", "typealias", "var", "where", "while",
+];
+
+const DART_KEYWORDS: &[&str] = &[
+    "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else", "enum", "extends",
+    "false", "final", "finally", "for", "if", "in", "is", "new", "null", "rethrow", "return", "super", "switch",
+    "this", "throw", "true", "try", "var", "void", "while", "with",
+];
+
+pub fn android(colors: &[(String, CasColor)]) -> String {
+    let body: String = colors
+        .iter()
+        .map(|(name, color)| {
+            format!("    <color name=\"{}\">{}</color>\n", suffixed(name, JAVA_KEYWORDS), color.to_hex())
+        })
+        .collect();
+    format!("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n{}</resources>\n", body)
+}
+
+pub fn compose(type_name: &str, colors: &[(String, CasColor)]) -> String {
+    let body: String = colors
+        .iter()
+        .map(|(name, color)| format!("    val {} = Color({})\n", backticked(name, KOTLIN_KEYWORDS), argb(color)))
+        .collect();
+    format!("import androidx.compose.ui.graphics.Color\n\nobject {} {{\n{}}}\n", type_name, body)
+}
+
+pub fn swiftui(type_name: &str, colors: &[(String, CasColor)]) -> String {
+    let body: String = colors
+        .iter()
+        .map(|(name, color)| {
+            let (r, g, b) = components(color);
+            let name = backticked(name, SWIFT_KEYWORDS);
+            format!("    static let {} = Color(red: {:.3}, green: {:.3}, blue: {:.3})\n", name, r, g, b)
+        })
+        .collect();
+    format!("import SwiftUI\n\nenum {} {{\n{}}}\n", type_name, body)
+}
+
+pub fn flutter(type_name: &str, colors: &[(String, CasColor)]) -> String {
+    let body: String = colors
+        .iter()
+        .map(|(name, color)| {
+            format!("  static const Color {} = Color({});\n", suffixed(name, DART_KEYWORDS), argb(color))
+        })
+        .collect();
+    format!(
+        "import 'package:flutter/painting.dart';\n\nclass {} {{\n  {}._();\n\n{}}}\n",
+        type_name, type_name, body
+    )
+}
+
+// Files of an Xcode asset catalog, relative to the .xcassets directory
+pub fn asset_catalog(colors: &[(String, CasColor)]) -> Vec<(PathBuf, String)> {
+    let mut files = vec![(PathBuf::from("Contents.json"), xcode_json(""))];
+
+    for (name, color) in colors {
+        let (r, g, b) = components(color);
+        let colorset = format!(
+            "  \"colors\" : [\n    {{\n      \"color\" : {{\n        \"color-space\" : \"srgb\",\n        \"components\" : {{\n          \"alpha\" : \"1.000\",\n          \"blue\" : \"{:.3}\",\n          \"green\" : \"{:.3}\",\n          \"red\" : \"{:.3}\"\n        }}\n      }},\n      \"idiom\" : \"universal\"\n    }}\n  ],\n",
+            b, g, r
+        );
+        files.push((PathBuf::from(format!("{}.colorset", name)).join("Contents.json"), xcode_json(&colorset)));
+    }
+
+    files
+}
+
+// A catalog is a directory, so as a single text each file gets a header naming it
+pub fn asset_catalog_listing(colors: &[(String, CasColor)]) -> String {
+    asset_catalog(colors)
+        .into_iter()
+        .map(|(path, contents)| format!("==> {} <==\n{}", path.display(), contents))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Xcode's own JSON layout, so opening the catalog does not rewrite every file
+fn xcode_json(body: &str) -> String {
+    format!("{{\n{}  \"info\" : {{\n    \"author\" : \"xcode\",\n    \"version\" : 1\n  }}\n}}\n", body)
+}
+
+fn backticked(name: &str, keywords: &[&str]) -> String {
+    if keywords.contains(&name) {
+        format!("`{}`", name)
+    } else {
+        name.to_string()
+    }
+}
+
+fn suffixed(name: &str, keywords: &[&str]) -> String {
+    if keywords.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+fn argb(color: &CasColor) -> String {
+    format!("0xFF{}", color.to_hex().trim_start_matches('#'))
+}
+
+fn components(color: &CasColor) -> (f32, f32, f32) {
+    let (r, g, b) = color.to_rgb();
+    (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0)
+}
@@ -0,0 +1,92 @@
+// Weighted k-means in Oklab, so clusters follow perceived rather than sRGB distance
+
+use super::Point;
+use crate::color::CasColor;
+
+const MAX_ITERATIONS: usize = 32;
+
+pub(super) fn kmeans(points: &[Point], count: usize) -> Vec<(CasColor, f32)> {
+    let labs: Vec<[f32; 3]> = points.iter().map(|p| oklab(p.rgb)).collect();
+    let mut centers = seed(points, &labs, count.min(points.len()));
+    let mut weights = vec![0.0; centers.len()];
+    let mut assignment = vec![usize::MAX; points.len()];
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut changed = false;
+        for (index, lab) in labs.iter().enumerate() {
+            let nearest = nearest_center(lab, &centers);
+            if assignment[index] != nearest {
+                assignment[index] = nearest;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+
+        // Clusters that lose every point keep their old center
+        let mut sums = vec![[0.0f32; 3]; centers.len()];
+        weights = vec![0.0; centers.len()];
+        for ((lab, point), &cluster) in labs.iter().zip(points).zip(&assignment) {
+            for i in 0..3 {
+                sums[cluster][i] += lab[i] * point.weight;
+            }
+            weights[cluster] += point.weight;
+        }
+        for (center, (sum, &weight)) in centers.iter_mut().zip(sums.iter().zip(&weights)) {
+            if weight > 0.0 {
+                *center = sum.map(|s| s / weight);
+            }
+        }
+    }
+
+    centers
+        .into_iter()
+        .zip(weights)
+        .map(|([l, a, b], weight)| (CasColor::from_oklab(l, a, b), weight))
+        .collect()
+}
+
+// Deterministic k-means++: start from the most common bin, then keep taking
+// the bin with the largest weighted squared distance to its nearest center
+fn seed(points: &[Point], labs: &[[f32; 3]], count: usize) -> Vec<[f32; 3]> {
+    let first = (0..points.len()).max_by(|&a, &b| points[a].weight.total_cmp(&points[b].weight)).unwrap_or(0);
+    let mut centers = vec![labs[first]];
+    let mut nearest: Vec<f32> = labs.iter().map(|lab| distance2(lab, &labs[first])).collect();
+
+    while centers.len() < count {
+        let (index, score) = nearest
+            .iter()
+            .zip(points)
+            .map(|(d, p)| d * p.weight)
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((0, 0.0));
+        if score <= 0.0 {
+            break;
+        }
+        let center = labs[index];
+        for (d, lab) in nearest.iter_mut().zip(labs) {
+            *d = d.min(distance2(lab, &center));
+        }
+        centers.push(center);
+    }
+
+    centers
+}
+
+fn nearest_center(lab: &[f32; 3], centers: &[[f32; 3]]) -> usize {
+    (0..centers.len())
+        .min_by(|&a, &b| distance2(lab, &centers[a]).total_cmp(&distance2(lab, &centers[b])))
+        .unwrap_or(0)
+}
+
+fn distance2(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+}
+
+fn oklab(rgb: [f32; 3]) -> [f32; 3] {
+    let [r, g, b] = rgb.map(|c| c.round().clamp(0.0, 255.0) as u8);
+    let (l, a, b) = CasColor::from_rgb(r, g, b).to_oklab();
+    [l, a, b]
+}
@@ -0,0 +1,54 @@
+// Median cut: keep splitting the box with the widest channel range (scaled by
+// its pixel count) at the weighted median of that channel
+
+use super::{mean_color, Point};
+use crate::color::CasColor;
+
+pub(super) fn median_cut(points: &[Point], count: usize) -> Vec<(CasColor, f32)> {
+    let mut boxes: Vec<Vec<Point>> = vec![points.to_vec()];
+
+    while boxes.len() < count {
+        let Some((index, channel)) = widest_box(&boxes) else {
+            break;
+        };
+
+        let mut points = boxes.swap_remove(index);
+        points.sort_by(|a, b| a.rgb[channel].total_cmp(&b.rgb[channel]));
+        let half: f32 = points.iter().map(|p| p.weight).sum::<f32>() / 2.0;
+        let mut seen = 0.0;
+        let median = points.iter().position(|p| {
+            seen += p.weight;
+            seen >= half
+        });
+        // Both halves keep at least one bin
+        let split = (median.unwrap_or(0) + 1).clamp(1, points.len() - 1);
+        let upper = points.split_off(split);
+        boxes.push(points);
+        boxes.push(upper);
+    }
+
+    boxes.iter().map(|points| mean_color(points)).collect()
+}
+
+// The box to split next and its widest channel; None once no box has two bins
+fn widest_box(boxes: &[Vec<Point>]) -> Option<(usize, usize)> {
+    boxes
+        .iter()
+        .enumerate()
+        .filter(|(_, points)| points.len() > 1)
+        .map(|(index, points)| {
+            let (channel, range) = (0..3)
+                .map(|channel| {
+                    let values = points.iter().map(|p| p.rgb[channel]);
+                    let min = values.clone().fold(f32::INFINITY, f32::min);
+                    let max = values.fold(f32::NEG_INFINITY, f32::max);
+                    (channel, max - min)
+                })
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap_or((0, 0.0));
+            let weight: f32 = points.iter().map(|p| p.weight).sum();
+            (index, channel, range * weight)
+        })
+        .max_by(|a, b| a.2.total_cmp(&b.2))
+        .map(|(index, channel, _)| (index, channel))
+}
@@ -0,0 +1,248 @@
+// Colors from image files. For dominant colors, pixels are first binned into
+// a histogram, the chosen method clusters the bins, and near-duplicate
+// results are merged; `sample` reads the color at a pixel or region instead.
+
+mod kmeans;
+mod median_cut;
+mod octree;
+mod sample;
+
+pub use sample::{sample, sample_file, Averaging, Region};
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use image::{ImageError, RgbaImage};
+
+use crate::color::CasColor;
+use crate::palette::Palette;
+
+pub const DEFAULT_COUNT: usize = 8;
+pub const DEFAULT_MIN_ALPHA: u8 = 128;
+// About one just-noticeable difference in Oklab
+pub const DEFAULT_MIN_DISTANCE: f32 = 0.02;
+
+// File extensions `load_palette` treats as images
+pub const IMAGE_EXTENSIONS: [&str; 9] = ["png", "jpg", "jpeg", "gif", "bmp", "webp", "tif", "tiff", "tga"];
+
+#[derive(Debug)]
+pub enum ExtractError {
+    Image(ImageError),
+    NoPixels { min_alpha: u8 },
+    OutOfBounds { x: u32, y: u32, width: u32, height: u32 },
+    Transparent { region: Region },
+}
+
+impl fmt::Display for ExtractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtractError::Image(e) => write!(f, "{}", e),
+            ExtractError::NoPixels { min_alpha } => {
+                write!(f, "The image has no pixels with an alpha of at least {}", min_alpha)
+            }
+            ExtractError::OutOfBounds { x, y, width, height } => {
+                write!(f, "{},{} is outside the {}x{} image", x, y, width, height)
+            }
+            ExtractError::Transparent { region } => write!(f, "Every pixel at {} is transparent", region),
+        }
+    }
+}
+
+impl std::error::Error for ExtractError {}
+
+impl From<ImageError> for ExtractError {
+    fn from(e: ImageError) -> Self {
+        ExtractError::Image(e)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtractMethod {
+    Kmeans,
+    MedianCut,
+    Octree,
+}
+
+impl ExtractMethod {
+    pub const ALL: [ExtractMethod; 3] = [ExtractMethod::Kmeans, ExtractMethod::MedianCut, ExtractMethod::Octree];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            ExtractMethod::Kmeans => "kmeans",
+            ExtractMethod::MedianCut => "median-cut",
+            ExtractMethod::Octree => "octree",
+        }
+    }
+}
+
+impl fmt::Display for ExtractMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for ExtractMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        ExtractMethod::ALL
+            .iter()
+            .copied()
+            .find(|method| method.key() == s)
+            .ok_or_else(|| format!("Unknown extraction method: {}", s))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractOptions {
+    // Upper bound on the colors returned; merging may leave fewer
+    pub count: usize,
+    pub method: ExtractMethod,
+    // Pixels more transparent than this are ignored; 0 keeps every pixel
+    pub min_alpha: u8,
+    // Results closer than this in Oklab are merged; 0 keeps them all
+    pub min_distance: f32,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        Self {
+            count: DEFAULT_COUNT,
+            method: ExtractMethod::Kmeans,
+            min_alpha: DEFAULT_MIN_ALPHA,
+            min_distance: DEFAULT_MIN_DISTANCE,
+        }
+    }
+}
+
+// One extracted color and the fraction of the counted pixels it stands for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Swatch {
+    pub color: CasColor,
+    pub share: f32,
+}
+
+// A histogram bin: the mean color of the pixels in it (0-255 per channel)
+// and how many there were
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point {
+    rgb: [f32; 3],
+    weight: f32,
+}
+
+// Swatches sorted by share, largest first
+pub fn extract(image: &RgbaImage, options: &ExtractOptions) -> Result<Vec<Swatch>, ExtractError> {
+    let points = histogram(image, options.min_alpha);
+    if points.is_empty() {
+        return Err(ExtractError::NoPixels { min_alpha: options.min_alpha });
+    }
+    let total: f32 = points.iter().map(|p| p.weight).sum();
+
+    let count = options.count.max(1);
+    let clusters = match options.method {
+        ExtractMethod::Kmeans => kmeans::kmeans(&points, count),
+        ExtractMethod::MedianCut => median_cut::median_cut(&points, count),
+        ExtractMethod::Octree => octree::octree(&points, count),
+    };
+
+    let mut swatches: Vec<Swatch> = clusters
+        .into_iter()
+        .filter(|(_, weight)| *weight > 0.0)
+        .map(|(color, weight)| Swatch { color, share: weight / total })
+        .collect();
+    merge_similar(&mut swatches, options.min_distance);
+    swatches.sort_by(|a, b| b.share.total_cmp(&a.share));
+    Ok(swatches)
+}
+
+pub fn extract_file(path: &Path, options: &ExtractOptions) -> Result<Vec<Swatch>, ExtractError> {
+    let image = image::open(path)?.to_rgba8();
+    extract(&image, options)
+}
+
+// The swatches as a palette named after the image, most common color first
+pub fn swatch_palette(name: impl Into<String>, swatches: &[Swatch]) -> Palette {
+    let mut palette = Palette::new(name);
+    for swatch in swatches {
+        palette.push(swatch.color, None);
+    }
+    palette
+}
+
+// Extract an image's colors with `options` straight into a palette
+pub fn load_palette(path: &Path, options: &ExtractOptions) -> Result<Palette, ExtractError> {
+    let swatches = extract_file(path, options)?;
+    let name = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    Ok(swatch_palette(name, &swatches))
+}
+
+pub fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| IMAGE_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+// Bin pixels at 5 bits per channel; each bin keeps the mean of its exact colors
+fn histogram(image: &RgbaImage, min_alpha: u8) -> Vec<Point> {
+    let mut bins: BTreeMap<u16, ([u64; 3], u64)> = BTreeMap::new();
+    for pixel in image.pixels() {
+        let [r, g, b, a] = pixel.0;
+        if a < min_alpha {
+            continue;
+        }
+        let key = (r as u16 >> 3) << 10 | (g as u16 >> 3) << 5 | b as u16 >> 3;
+        let (sums, count) = bins.entry(key).or_insert(([0; 3], 0));
+        sums[0] += r as u64;
+        sums[1] += g as u64;
+        sums[2] += b as u64;
+        *count += 1;
+    }
+
+    bins.into_values()
+        .map(|(sums, count)| Point {
+            rgb: sums.map(|sum| sum as f32 / count as f32),
+            weight: count as f32,
+        })
+        .collect()
+}
+
+// Weighted mean of points in sRGB, with their total weight
+fn mean_color(points: &[Point]) -> (CasColor, f32) {
+    let weight: f32 = points.iter().map(|p| p.weight).sum();
+    if weight <= 0.0 {
+        return (CasColor::from_rgb(0, 0, 0), 0.0);
+    }
+    let channel = |i: usize| {
+        let sum: f32 = points.iter().map(|p| p.rgb[i] * p.weight).sum();
+        (sum / weight).round().clamp(0.0, 255.0) as u8
+    };
+    (CasColor::from_rgb(channel(0), channel(1), channel(2)), weight)
+}
+
+// Repeatedly fold the closest pair below `min_distance` into one swatch,
+// mixed in proportion to their shares
+fn merge_similar(swatches: &mut Vec<Swatch>, min_distance: f32) {
+    loop {
+        let mut closest: Option<(usize, usize, f32)> = None;
+        for i in 0..swatches.len() {
+            for j in i + 1..swatches.len() {
+                let distance = swatches[i].color.distance(&swatches[j].color);
+                if distance < min_distance && closest.is_none_or(|(_, _, d)| distance < d) {
+                    closest = Some((i, j, distance));
+                }
+            }
+        }
+
+        let Some((i, j, _)) = closest else {
+            return;
+        };
+        let other = swatches.remove(j);
+        let share = swatches[i].share + other.share;
+        swatches[i].color = swatches[i].color.mix(&other.color, other.share / share);
+        swatches[i].share = share;
+    }
+}
@@ -0,0 +1,60 @@
+// Octree quantization: each histogram bin is a leaf five levels deep, and the
+// least common siblings are folded into their parent until few enough remain
+
+use std::collections::BTreeMap;
+
+use super::{mean_color, Point};
+use crate::color::CasColor;
+
+// Matches the histogram's 5 bits per channel
+const DEPTH: u32 = 5;
+
+// Sibling leaves with their keys
+type Children = Vec<(u32, Vec<Point>)>;
+
+pub(super) fn octree(points: &[Point], count: usize) -> Vec<(CasColor, f32)> {
+    let mut level: BTreeMap<u32, Vec<Point>> = BTreeMap::new();
+    for point in points {
+        level.entry(leaf_key(point.rgb)).or_default().push(*point);
+    }
+
+    // Leaves left behind at a deeper level once the count was reached
+    let mut kept: Vec<Vec<Point>> = Vec::new();
+    for _ in 0..DEPTH {
+        if kept.len() + level.len() <= count {
+            break;
+        }
+
+        let mut siblings: BTreeMap<u32, Children> = BTreeMap::new();
+        for (key, points) in std::mem::take(&mut level) {
+            siblings.entry(key >> 3).or_default().push((key, points));
+        }
+        let mut groups: Vec<(u32, Children)> = siblings.into_iter().collect();
+        groups.sort_by(|a, b| weight(&a.1).total_cmp(&weight(&b.1)));
+
+        let mut remaining: usize = groups.iter().map(|(_, children)| children.len()).sum();
+        for (parent, children) in groups {
+            remaining -= children.len();
+            if kept.len() + level.len() + remaining + children.len() <= count {
+                kept.extend(children.into_iter().map(|(_, points)| points));
+            } else {
+                level.insert(parent, children.into_iter().flat_map(|(_, points)| points).collect());
+            }
+        }
+    }
+
+    kept.iter().chain(level.values()).map(|points| mean_color(points)).collect()
+}
+
+// Three bits per level, one from each channel, most significant level first
+fn leaf_key(rgb: [f32; 3]) -> u32 {
+    let [r, g, b] = rgb.map(|c| c.round().clamp(0.0, 255.0) as u32);
+    (0..DEPTH).fold(0, |key, level| {
+        let bit = 7 - level;
+        key << 3 | ((r >> bit) & 1) << 2 | ((g >> bit) & 1) << 1 | ((b >> bit) & 1)
+    })
+}
+
+fn weight(children: &Children) -> f32 {
+    children.iter().flat_map(|(_, points)| points).map(|p| p.weight).sum()
+}
@@ -0,0 +1,143 @@
+// Read a single color from an image: one pixel, or the average of a
+// rectangle or circle. Averages are weighted by alpha.
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use image::RgbaImage;
+
+use super::ExtractError;
+use crate::color::CasColor;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    Pixel { x: u32, y: u32 },
+    // Clipped to the image; the top-left corner must be inside it
+    Rect { x: u32, y: u32, width: u32, height: u32 },
+    // Pixels whose centers are within `radius` of the center pixel's
+    Circle { x: u32, y: u32, radius: u32 },
+}
+
+impl Region {
+    fn origin(&self) -> (u32, u32) {
+        match *self {
+            Region::Pixel { x, y } | Region::Rect { x, y, .. } | Region::Circle { x, y, .. } => (x, y),
+        }
+    }
+
+    // Pixel coordinates covered by the region inside a width x height image
+    fn pixels(&self, width: u32, height: u32) -> Vec<(u32, u32)> {
+        match *self {
+            Region::Pixel { x, y } => vec![(x, y)],
+            Region::Rect { x, y, width: w, height: h } => {
+                let right = x.saturating_add(w.max(1)).min(width);
+                let bottom = y.saturating_add(h.max(1)).min(height);
+                (y..bottom).flat_map(|py| (x..right).map(move |px| (px, py))).collect()
+            }
+            Region::Circle { x, y, radius } => {
+                let (left, top) = (x.saturating_sub(radius), y.saturating_sub(radius));
+                let right = x.saturating_add(radius).saturating_add(1).min(width);
+                let bottom = y.saturating_add(radius).saturating_add(1).min(height);
+                let r2 = radius as u64 * radius as u64;
+                (top..bottom)
+                    .flat_map(|py| (left..right).map(move |px| (px, py)))
+                    .filter(|&(px, py)| (px.abs_diff(x) as u64).pow(2) + (py.abs_diff(y) as u64).pow(2) <= r2)
+                    .collect()
+            }
+        }
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Region::Pixel { x, y } => write!(f, "{},{}", x, y),
+            Region::Rect { x, y, width, height } => write!(f, "rect {},{},{},{}", x, y, width, height),
+            Region::Circle { x, y, radius } => write!(f, "circle {},{},{}", x, y, radius),
+        }
+    }
+}
+
+// How pixel values are combined when a region covers more than one pixel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Averaging {
+    // Average light intensity, as a camera or a blurred eye would see it
+    Linear,
+    // Average the encoded sRGB values, as most image editors do
+    Srgb,
+}
+
+impl Averaging {
+    pub const ALL: [Averaging; 2] = [Averaging::Linear, Averaging::Srgb];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Averaging::Linear => "linear",
+            Averaging::Srgb => "srgb",
+        }
+    }
+}
+
+impl fmt::Display for Averaging {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for Averaging {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        Averaging::ALL
+            .iter()
+            .copied()
+            .find(|averaging| averaging.key() == s)
+            .ok_or_else(|| format!("Unknown averaging: {}", s))
+    }
+}
+
+pub fn sample(image: &RgbaImage, region: Region, averaging: Averaging) -> Result<CasColor, ExtractError> {
+    let (width, height) = image.dimensions();
+    let (x, y) = region.origin();
+    if x >= width || y >= height {
+        return Err(ExtractError::OutOfBounds { x, y, width, height });
+    }
+
+    let mut sums = [0.0f64; 3];
+    let mut total = 0.0f64;
+    for (px, py) in region.pixels(width, height) {
+        let [r, g, b, a] = image.get_pixel(px, py).0;
+        let weight = a as f64 / 255.0;
+        let channels = match averaging {
+            Averaging::Linear => {
+                let (r, g, b) = CasColor::from_rgb(r, g, b).to_linear_rgb();
+                [r as f64, g as f64, b as f64]
+            }
+            Averaging::Srgb => [r as f64, g as f64, b as f64],
+        };
+        for (sum, channel) in sums.iter_mut().zip(channels) {
+            *sum += channel * weight;
+        }
+        total += weight;
+    }
+
+    if total == 0.0 {
+        return Err(ExtractError::Transparent { region });
+    }
+
+    let [r, g, b] = sums.map(|sum| sum / total);
+    Ok(match averaging {
+        Averaging::Linear => CasColor::from_linear_rgb(r as f32, g as f32, b as f32),
+        Averaging::Srgb => {
+            let channel = |c: f64| c.round().clamp(0.0, 255.0) as u8;
+            CasColor::from_rgb(channel(r), channel(g), channel(b))
+        }
+    })
+}
+
+pub fn sample_file(path: &Path, region: Region, averaging: Averaging) -> Result<CasColor, ExtractError> {
+    let image = image::open(path)?.to_rgba8();
+    sample(&image, region, averaging)
+}
@@ -0,0 +1,264 @@
+// Color history: the colors entered, picked and copied, one JSON object per
+// line in history.jsonl in the data directory, oldest first. It used to be
+// the `color_history` array in config.toml, which meant every pick rewrote
+// the user's hand-edited config; `import` reads that array.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use toml_edit::ImDocument;
+
+use crate::color::{find_colors, parse_color, CasColor, ColorFormat, ColorSyntax};
+use crate::config::{self, Config, ConfigError, MAX_HISTORY_SIZE};
+
+pub const HISTORY_FILE: &str = "history.jsonl";
+
+// Where a color in the history came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    Input,
+    Palette,
+    Gradient,
+    Eyedropper,
+    Cli,
+    Import,
+}
+
+impl Source {
+    pub const ALL: [Source; 6] =
+        [Source::Input, Source::Palette, Source::Gradient, Source::Eyedropper, Source::Cli, Source::Import];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Source::Input => "input",
+            Source::Palette => "palette",
+            Source::Gradient => "gradient",
+            Source::Eyedropper => "eyedropper",
+            Source::Cli => "cli",
+            Source::Import => "import",
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for Source {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        Source::ALL
+            .iter()
+            .copied()
+            .find(|source| source.key() == s)
+            .ok_or_else(|| format!("Unknown history source: {}", s))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    // Hex, e.g. "#FF5733"
+    pub color: String,
+    // When it was last used, RFC 3339 in UTC
+    pub time: String,
+    pub source: Source,
+    // The format it was entered or copied in; None for color names and
+    // project format templates
+    pub format: Option<ColorFormat>,
+    // Pinned colors are never trimmed or cleared with the rest
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+impl HistoryEntry {
+    pub fn new(color: &CasColor, source: Source, format: Option<ColorFormat>) -> Self {
+        Self {
+            color: color.to_hex(),
+            time: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            source,
+            format,
+            pinned: false,
+        }
+    }
+
+    pub fn color(&self) -> Option<CasColor> {
+        CasColor::from_hex(&self.color).ok()
+    }
+
+    fn is(&self, color: &str) -> bool {
+        self.color.eq_ignore_ascii_case(color)
+    }
+}
+
+pub struct History {
+    pub path: PathBuf,
+    // How many unpinned entries to keep
+    pub limit: usize,
+}
+
+impl History {
+    pub fn new(path: PathBuf, limit: usize) -> Self {
+        Self { path, limit }
+    }
+
+    // The history file in the data directory, kept to `general.history_size`
+    pub fn open(config: &Config) -> Result<Self, ConfigError> {
+        Ok(Self::new(Self::default_path()?, config.general.history_size))
+    }
+
+    pub fn default_path() -> Result<PathBuf, ConfigError> {
+        Ok(config::data_dir()?.join(HISTORY_FILE))
+    }
+
+    // Every entry, newest first; none when the file doesn't exist yet
+    pub fn load(&self) -> Result<Vec<HistoryEntry>, ConfigError> {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) => Ok(parse(&contents).into_iter().rev().collect()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    // Add `entry` as the newest. An older entry for the same color is
+    // replaced but stays pinned.
+    pub fn record(&self, mut entry: HistoryEntry) -> Result<(), ConfigError> {
+        self.modify(|entries| {
+            if let Some(index) = entries.iter().position(|old| old.is(&entry.color)) {
+                entry.pinned |= entries.remove(index).pinned;
+            }
+            entries.push(entry);
+        })
+    }
+
+    // Pin or unpin `color`; false when it isn't in the history
+    pub fn set_pinned(&self, color: &CasColor, pinned: bool) -> Result<bool, ConfigError> {
+        let mut found = false;
+        self.modify(|entries| {
+            for entry in entries.iter_mut().filter(|entry| entry.is(&color.to_hex())) {
+                entry.pinned = pinned;
+                found = true;
+            }
+        })?;
+        Ok(found)
+    }
+
+    // Remove the unpinned entries, or all of them; returns how many went
+    pub fn clear(&self, pinned_too: bool) -> Result<usize, ConfigError> {
+        let mut removed = 0;
+        self.modify(|entries| {
+            let before = entries.len();
+            entries.retain(|entry| entry.pinned && !pinned_too);
+            removed = before - entries.len();
+        })?;
+        Ok(removed)
+    }
+
+    // Add `colors`, newest first as the legacy `color_history` listed them,
+    // behind the existing entries. Colors already in the history are
+    // skipped; returns how many were added.
+    pub fn import(&self, colors: &[CasColor]) -> Result<usize, ConfigError> {
+        let mut added = 0;
+        self.modify(|entries| {
+            let mut imported: Vec<HistoryEntry> = Vec::new();
+            for color in colors {
+                let entry = HistoryEntry::new(color, Source::Import, None);
+                if !entries.iter().chain(&imported).any(|old| old.is(&entry.color)) {
+                    imported.push(entry);
+                }
+            }
+            added = imported.len();
+            entries.splice(0..0, imported.into_iter().rev());
+        })?;
+        Ok(added)
+    }
+
+    // Rewrite the file under the same lock the config uses, then drop the
+    // oldest unpinned entries past the limit. Lines this build can't read,
+    // such as hand edits or entries from a newer cascolor, are kept as they
+    // are ahead of the rest.
+    fn modify(&self, change: impl FnOnce(&mut Vec<HistoryEntry>)) -> Result<(), ConfigError> {
+        config::modify(&self.path, |contents| {
+            let mut entries = parse(contents);
+            change(&mut entries);
+
+            let unpinned = entries.iter().filter(|entry| !entry.pinned).count();
+            let mut excess = unpinned.saturating_sub(self.limit);
+            entries.retain(|entry| {
+                let drop = !entry.pinned && excess > 0;
+                if drop {
+                    excess -= 1;
+                }
+                !drop
+            });
+
+            let mut written = String::new();
+            for line in unreadable(contents) {
+                written.push_str(line);
+                written.push('\n');
+            }
+            for entry in &entries {
+                written.push_str(&serde_json::to_string(entry).expect("history entries serialize"));
+                written.push('\n');
+            }
+            Ok(written)
+        })
+    }
+}
+
+// The format `input` is written in, when it is a single color literal in
+// one of the output formats
+pub fn input_format(input: &str) -> Option<ColorFormat> {
+    let input = input.trim();
+    match find_colors(input, &ColorSyntax::ALL).as_slice() {
+        [found] if found.range == (0..input.len()) => found.syntax.format(),
+        _ => None,
+    }
+}
+
+// The entries in a history file, oldest first. Lines that don't parse, such
+// as one cut short by a full disk, are skipped; `modify` writes them back.
+pub fn parse(contents: &str) -> Vec<HistoryEntry> {
+    contents.lines().filter_map(read_line).collect()
+}
+
+// The non-blank lines `parse` skips
+fn unreadable(contents: &str) -> impl Iterator<Item = &str> {
+    contents.lines().filter(|line| !line.trim().is_empty() && read_line(line).is_none())
+}
+
+fn read_line(line: &str) -> Option<HistoryEntry> {
+    serde_json::from_str::<HistoryEntry>(line).ok().filter(|entry| entry.color().is_some())
+}
+
+// The colors in a config file's legacy `color_history` array, newest first.
+// Entries that aren't colors are skipped.
+pub fn legacy_colors(contents: &str) -> Vec<CasColor> {
+    let Ok(document) = ImDocument::parse(contents) else {
+        return Vec::new();
+    };
+    let Some(colors) = document.get("color_history").and_then(|item| item.as_array()) else {
+        return Vec::new();
+    };
+    colors.iter().filter_map(|entry| entry.as_str()).filter_map(|text| parse_color(text).ok()).collect()
+}
+
+// Move the `color_history` of the config at `path` into the history at
+// `history`, before an upgrade drops it from the file
+pub fn import_legacy(path: &Path, history: &Path) -> Result<usize, ConfigError> {
+    let colors = legacy_colors(&fs::read_to_string(path)?);
+    if colors.is_empty() {
+        return Ok(0);
+    }
+    // The legacy list was already trimmed to the configured size
+    History::new(history.to_path_buf(), MAX_HISTORY_SIZE).import(&colors)
+}
@@ -2,6 +2,17 @@
 
 pub mod config;
 pub mod color;
+pub mod batch;
+pub mod palette;
+pub mod project;
+pub mod history;
+pub mod export;
+pub mod extract;
+pub mod theme;
+pub mod sheet;
 pub mod clipboard;
 pub mod update;
 pub mod version;
+pub mod term;
+pub mod display;
+pub mod exit;
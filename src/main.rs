@@ -1,121 +1,196 @@
-use std::env;
+use clap::Parser;
 use std::process;
 
+mod cli;
+mod commands;
 mod config;
 mod color;
+mod batch;
+mod palette;
+mod project;
+mod history;
+mod export;
+mod extract;
+mod theme;
+mod sheet;
 mod clipboard;
 mod update;
 mod version;
+mod term;
+mod display;
+mod exit;
 mod gui;
 mod tui;
 
-fn main() {
-    if let Err(e) = run() {
-        eprintln!("Error: {}", e);
-        process::exit(1);
-    }
-}
+use cli::{Cli, Command, ConfigCommand};
+use commands::sheet::SheetSource;
+use display::UiMode;
+use exit::{CliError, ErrorFormat, ExitCode};
 
-fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = env::args().collect();
+fn main() {
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => exit_usage(e),
+    };
+    let error_format = cli.error_format;
 
-    // Handle CLI flags
-    if args.len() > 1 {
-        match args[1].as_str() {
-            "--help" => {
-                print_help();
-                process::exit(0);
-            }
-            "--version" => {
-                version::print_version();
-                process::exit(0);
-            }
-            "--update" => {
-                let channel = args.get(2).map(|s| s.as_str()).unwrap_or("stable");
-                match update::check_for_updates(channel) {
-                    Ok(has_update) => {
-                        if has_update {
-                            println!("Update available! Use cascolor --update {} to install.", channel);
-                        } else {
-                            println!("No update available.");
-                        }
-                        process::exit(0);
-                    }
-                    Err(e) => {
-                        if matches!(e, update::UpdateError::NotFound) {
-                            println!("No update available.");
-                            process::exit(0);
-                        } else {
-                            eprintln!("Update check failed: {}", e);
-                            process::exit(1);
-                        }
-                    }
-                }
-            }
-            _ => {
-                eprintln!("Unknown option: {}", args[1]);
-                eprintln!("Use --help for usage information");
-                process::exit(1);
-            }
+    match run(cli) {
+        Ok(exit_code) => process::exit(exit_code.code()),
+        Err(e) => {
+            e.print(error_format);
+            process::exit(e.exit_code.code());
         }
     }
+}
 
-    // Display detection logic
-    let use_tui = should_use_tui();
-    let config = config::Config::load();
+// clap exits with status 2 on bad arguments; only JSON output needs handling here
+fn exit_usage(e: clap::Error) -> ! {
+    use clap::error::ErrorKind;
 
-    if use_tui {
-        tui::run(config)?;
-    } else {
-        gui::run(config)?;
-    }
-    
-    Ok(())
-}
+    let is_usage_error = !matches!(
+        e.kind(),
+        ErrorKind::DisplayHelp | ErrorKind::DisplayVersion | ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+    );
 
-fn should_use_tui() -> bool {
-    // Check if SSH connection
-    if env::var("SSH_CONNECTION").is_ok() || env::var("SSH_CLIENT").is_ok() || env::var("SSH_TTY").is_ok() {
-        return true;
+    if is_usage_error && ErrorFormat::from_args(std::env::args()) == ErrorFormat::Json {
+        let rendered = e.render().to_string();
+        let message = rendered.lines().next().unwrap_or("").trim_start_matches("error: ");
+        CliError::new(ExitCode::Usage, message).print(ErrorFormat::Json);
+        process::exit(ExitCode::Usage.code());
     }
 
-    // Check for display environment - must be non-empty
-    let has_display = env::var("DISPLAY")
-        .ok()
-        .filter(|d| !d.is_empty())
-        .is_some() 
-        || env::var("WAYLAND_DISPLAY")
-        .ok()
-        .filter(|d| !d.is_empty())
-        .is_some();
-    
-    !has_display
+    e.exit()
 }
 
-fn print_help() {
-    println!("cascolor - Beautiful cross-platform color picker
+fn run(cli: Cli) -> Result<ExitCode, CliError> {
+    // Handle CLI flags
+    if cli.version {
+        if cli.json {
+            version::print_version_json()?;
+        } else {
+            version::print_version();
+        }
+        return Ok(ExitCode::Success);
+    }
 
-USAGE:
-    cascolor [OPTIONS]
+    if let Some(channel) = cli.update.as_deref() {
+        return match update::check_for_updates(channel) {
+            Ok(true) => {
+                println!("Update available! Use cascolor --update {} to install.", channel);
+                Ok(ExitCode::UpdateAvailable)
+            }
+            Ok(false) | Err(update::UpdateError::NotFound) => {
+                println!("No update available.");
+                Ok(ExitCode::Success)
+            }
+            Err(e) => Err(CliError::new(ExitCode::from(&e), format!("Update check failed: {}", e))),
+        };
+    }
 
-OPTIONS:
-    --help              Show this help message
-    --version           Show version information
-    --update [channel]  Check for updates (stable, beta, daily)
+    if let Some(command) = cli.command {
+        match command {
+            Command::Info { color } => commands::info::run(&color, cli.json)?,
+            Command::Filter { to, syntaxes } => commands::filter::run(to, &syntaxes)?,
+            Command::Palette { colors, file } => commands::palette::run(&colors, file.as_deref())?,
+            Command::Convert { input, output, from, to } => commands::convert::run(&input, &output, from, to)?,
+            Command::Export { file, history, format, naming, prefix, color_format, output } => {
+                let options = export::ExportOptions {
+                    format,
+                    case: naming.unwrap_or(format.default_case()),
+                    prefix,
+                    color_format,
+                };
+                commands::export::run(file.as_deref(), history, &options, output.as_deref(), cli.config.as_deref(), &cli.set)?
+            }
+            Command::Theme { file, format, name, min_contrast, strict, output } => {
+                commands::theme::run(&file, format, name, min_contrast, strict, output.as_deref())?
+            }
+            Command::Sheet {
+                file,
+                harmony,
+                base,
+                scale,
+                steps,
+                columns,
+                swatch_width,
+                swatch_height,
+                gap,
+                text_scale,
+                contrast,
+                format,
+                output,
+            } => {
+                let source = match (&file, harmony, &scale[..]) {
+                    (Some(path), _, _) => SheetSource::File(path),
+                    (None, Some(harmony), _) => SheetSource::Harmony(harmony, base.as_deref().unwrap_or_default()),
+                    (None, None, [from, to]) => SheetSource::Scale(from, to, steps),
+                    _ => unreachable!("clap requires a file, --harmony or --scale"),
+                };
+                let options = sheet::SheetOptions { columns, swatch_width, swatch_height, gap, text_scale, contrast };
+                commands::sheet::run(source, &options, format, &output)?
+            }
+            Command::Extract { image, count, method, min_alpha, min_distance, format, output } => {
+                let options = extract::ExtractOptions { count, method, min_alpha, min_distance };
+                commands::extract::run(&image, &options, format, output.as_deref(), cli.json)?
+            }
+            Command::Sample { image, at, rect, circle, average, to } => {
+                let region = match (at, rect, circle) {
+                    (Some([x, y]), _, _) => extract::Region::Pixel { x, y },
+                    (None, Some([x, y, width, height]), _) => extract::Region::Rect { x, y, width, height },
+                    (None, None, Some([x, y, radius])) => extract::Region::Circle { x, y, radius },
+                    _ => unreachable!("clap requires --at, --rect or --circle"),
+                };
+                commands::sample::run(&image, region, average, to, cli.json)?
+            }
+            Command::Config { command } => match command {
+                ConfigCommand::Check { file } => {
+                    commands::config::run_check(file.as_deref().or(cli.config.as_deref()), cli.json)?
+                }
+                ConfigCommand::Get { key } => commands::config::run_get(&key, cli.config.as_deref(), &cli.set, cli.json)?,
+                ConfigCommand::Show { origin } => {
+                    commands::config::run_show(origin, cli.config.as_deref(), &cli.set, cli.json)?
+                }
+                ConfigCommand::Set { key, value } => commands::config::run_set(&key, &value, cli.config.as_deref())?,
+                ConfigCommand::Edit => commands::config::run_edit(cli.config.as_deref())?,
+                ConfigCommand::Path => commands::config::run_path(cli.config.as_deref(), cli.json)?,
+                ConfigCommand::Reset { key } => commands::config::run_reset(key.as_deref(), cli.config.as_deref())?,
+            },
+            Command::History { command } => commands::history::run(command, cli.config.as_deref(), &cli.set, cli.json)?,
+            Command::Completions { shell } => commands::completions::run_completions(shell)?,
+            Command::Manpage => commands::completions::run_manpage()?,
+            Command::Batch { file, column, delimiter, header, columns, output } => {
+                commands::batch::run(file.as_deref(), column, delimiter, header, &columns, output)?
+            }
+        }
+        return Ok(ExitCode::Success);
+    }
 
-DESCRIPTION:
-    A beautiful color picker with support for HEX, RGB, RGBA, HSL, HSLA,
-    HSV, CMYK, Lab, and more. Features eyedropper tool, color history,
-    and cross-platform support.
+    let config = config::Config::load(cli.config.as_deref(), &cli.set)?;
+    // A broken project file shouldn't keep the UI from starting
+    let project = project::Project::discover().unwrap_or_else(|e| {
+        eprintln!("Warning: Ignoring project settings: {}", e);
+        None
+    });
+    let palette = match &cli.palette {
+        Some(path) if extract::is_image(path) => {
+            extract::load_palette(path, &extract::ExtractOptions::default()).map_err(|e| CliError::from(e).in_file(path))?
+        }
+        Some(path) => palette::Palette::load(path).map_err(|e| CliError::from(e).in_file(path))?,
+        None => project.as_ref().and_then(|project| project.palette.clone()).unwrap_or_else(palette::Palette::builtin),
+    };
 
-    Automatically detects display environment:
-    - GUI mode: When display is available (X11/Wayland/Windows/macOS)
-    - TUI mode: For remote sessions or no display
+    // Display detection logic: CLI flag, then config, then auto-detect
+    let requested = cli.requested_mode().unwrap_or(config.ui.mode);
+    let environment = display::Environment::current();
 
-CONFIG:
-    Linux/BSD:  ~/.config/casapps/cascolor/config.toml
-    macOS:      ~/Library/Application Support/casapps/cascolor/config.toml
-    Windows:    %APPDATA%\\casapps\\cascolor\\config.toml
+    match display::detect_mode(requested, &environment, display::probe_display) {
+        UiMode::Gui => gui::run(config)?,
+        UiMode::Tui => tui::run(config, palette, project, config::Sources::discover(cli.config.as_deref(), &cli.set))?,
+        UiMode::Headless | UiMode::Auto => {
+            eprintln!("No display or terminal available; not starting a UI.");
+            eprintln!("Use a command such as `cascolor info <color>`, or see --help.");
+        }
+    }
 
-For more information, visit: https://github.com/casapps/cascolor");
+    Ok(ExitCode::Success)
 }
@@ -0,0 +1,125 @@
+// Photoshop color swatch (.aco) files. A version 1 section (colors only) is
+// usually followed by a version 2 section repeating the colors with names.
+// Each color is a color space id and four big-endian u16 values.
+
+use super::binary::{put_u16, put_u32, put_utf16, Reader};
+use super::{Palette, PaletteError};
+use crate::color::CasColor;
+
+const SPACE_RGB: u16 = 0;
+const SPACE_HSB: u16 = 1;
+const SPACE_CMYK: u16 = 2;
+const SPACE_LAB: u16 = 7;
+const SPACE_GRAY: u16 = 8;
+const SPACE_WIDE_CMYK: u16 = 9;
+
+pub fn read_aco(bytes: &[u8]) -> Result<Palette, PaletteError> {
+    let mut reader = Reader::new(bytes);
+    let mut palette = Palette::default();
+
+    while !reader.is_at_end() {
+        let version = reader.u16()?;
+        if version != 1 && version != 2 {
+            return Err(reader.error(format!("unsupported ACO version {}", version)));
+        }
+
+        let count = reader.u16()?;
+        let mut section = Palette::default();
+        for _ in 0..count {
+            let color = read_color(&mut reader)?;
+            let name = if version == 2 {
+                let units = reader.u32()? as usize;
+                Some(reader.utf16(units)?).filter(|n| !n.is_empty())
+            } else {
+                None
+            };
+            section.push(color, name);
+        }
+
+        // The named version 2 section supersedes version 1
+        palette = section;
+        if version == 2 {
+            break;
+        }
+    }
+
+    Ok(palette)
+}
+
+fn read_color(reader: &mut Reader) -> Result<CasColor, PaletteError> {
+    let offset = reader.position();
+    let space = reader.u16()?;
+    let (w, x, y, z) = (reader.u16()?, reader.u16()?, reader.u16()?, reader.u16()?);
+    let unit = |v: u16| v as f32 / 65535.0;
+
+    let color = match space {
+        SPACE_RGB => CasColor::from_rgb(to_u8(w), to_u8(x), to_u8(y)),
+        SPACE_HSB => CasColor::from_hsv(unit(w) * 360.0, unit(x), unit(y)),
+        // 0 means 100% ink
+        SPACE_CMYK => CasColor::from_cmyk(1.0 - unit(w), 1.0 - unit(x), 1.0 - unit(y), 1.0 - unit(z)),
+        // L is 0-10000; a and b are signed hundredths
+        SPACE_LAB => CasColor::from_lab_d50(w as f32 / 100.0, x as i16 as f32 / 100.0, y as i16 as f32 / 100.0),
+        // Ink coverage 0-10000, so 10000 is black
+        SPACE_GRAY => {
+            let gray = (255.0 * (1.0 - (w as f32 / 10000.0).min(1.0))).round() as u8;
+            CasColor::from_rgb(gray, gray, gray)
+        }
+        SPACE_WIDE_CMYK => {
+            let ink = |v: u16| (v as f32 / 10000.0).min(1.0);
+            CasColor::from_cmyk(ink(w), ink(x), ink(y), ink(z))
+        }
+        other => {
+            return Err(PaletteError::InvalidData {
+                offset,
+                message: format!("unsupported color space {} ({})", other, space_name(other)),
+            })
+        }
+    };
+
+    Ok(color)
+}
+
+// Named spaces are proprietary color books we cannot convert without their tables
+fn space_name(space: u16) -> &'static str {
+    match space {
+        3 => "Pantone",
+        4 => "Focoltone",
+        5 => "Trumatch",
+        6 => "Toyo",
+        10 => "HKS",
+        _ => "unknown",
+    }
+}
+
+fn to_u8(value: u16) -> u8 {
+    ((value as u32 + 128) / 257) as u8
+}
+
+// Both sections, so older readers still see the colors. Colors are written as RGB.
+pub fn write_aco(palette: &Palette) -> Vec<u8> {
+    let mut out = Vec::new();
+    let entries = &palette.entries[..palette.len().min(u16::MAX as usize)];
+
+    for version in [1u16, 2] {
+        put_u16(&mut out, version);
+        put_u16(&mut out, entries.len() as u16);
+
+        for entry in entries {
+            let (r, g, b) = entry.color.to_rgb();
+            put_u16(&mut out, SPACE_RGB);
+            for channel in [r, g, b, 0] {
+                put_u16(&mut out, channel as u16 * 257);
+            }
+
+            if version == 2 {
+                let name = entry.name.clone().unwrap_or_else(|| entry.color.to_hex());
+                let mut text = Vec::new();
+                let units = put_utf16(&mut text, &name);
+                put_u32(&mut out, units as u32);
+                out.extend_from_slice(&text);
+            }
+        }
+    }
+
+    out
+}
@@ -0,0 +1,197 @@
+// Adobe Swatch Exchange (.ase) files, shared by Illustrator, Photoshop and
+// InDesign. Big-endian: "ASEF", version 1.0, a block count, then blocks of
+// (type, length, data). Color blocks hold a UTF-16 name, a four-character
+// color model, its float channels and a swatch type.
+
+use super::binary::{put_f32, put_u16, put_u32, put_utf16, Reader};
+use super::{Palette, PaletteEntry, PaletteError, SwatchKind};
+use crate::color::CasColor;
+
+const SIGNATURE: &[u8] = b"ASEF";
+
+const BLOCK_COLOR: u16 = 0x0001;
+const BLOCK_GROUP_START: u16 = 0xC001;
+const BLOCK_GROUP_END: u16 = 0xC002;
+
+pub fn read_ase(bytes: &[u8]) -> Result<Palette, PaletteError> {
+    let mut reader = Reader::new(bytes);
+
+    if reader.take(4).ok() != Some(SIGNATURE) {
+        return Err(PaletteError::InvalidData {
+            offset: 0,
+            message: "not an Adobe Swatch Exchange file (missing ASEF signature)".to_string(),
+        });
+    }
+
+    let (major, minor) = (reader.u16()?, reader.u16()?);
+    if major != 1 {
+        return Err(reader.error(format!("unsupported ASE version {}.{}", major, minor)));
+    }
+
+    let block_count = reader.u32()?;
+    let mut palette = Palette::default();
+    let mut group: Option<String> = None;
+
+    for _ in 0..block_count {
+        let block_type = reader.u16()?;
+        let length = reader.u32()? as usize;
+        let start = reader.position();
+
+        match block_type {
+            BLOCK_GROUP_START => {
+                let units = reader.u16()? as usize;
+                group = Some(reader.utf16(units)?);
+            }
+            BLOCK_GROUP_END => group = None,
+            BLOCK_COLOR => {
+                let mut entry = read_color(&mut reader)?;
+                entry.group = group.clone();
+                palette.entries.push(entry);
+            }
+            // Unknown blocks are skipped using their length
+            _ => {}
+        }
+
+        let consumed = reader.position() - start;
+        if consumed > length {
+            return Err(reader.error(format!(
+                "block at offset {} is longer than its declared {} bytes",
+                start - 6,
+                length
+            )));
+        }
+        reader.take(length - consumed)?;
+    }
+
+    Ok(palette)
+}
+
+fn read_color(reader: &mut Reader) -> Result<PaletteEntry, PaletteError> {
+    let units = reader.u16()? as usize;
+    let name = reader.utf16(units)?;
+
+    let model_offset = reader.position();
+    let model = reader.take(4)?;
+    let color = match model {
+        b"RGB " => {
+            let (r, g, b) = (reader.f32()?, reader.f32()?, reader.f32()?);
+            CasColor::from_rgb(unit_channel(r), unit_channel(g), unit_channel(b))
+        }
+        b"CMYK" => {
+            let (c, m, y, k) = (reader.f32()?, reader.f32()?, reader.f32()?, reader.f32()?);
+            CasColor::from_cmyk(c, m, y, k)
+        }
+        // L is stored as 0-1 rather than 0-100
+        b"LAB " => {
+            let (l, a, b) = (reader.f32()?, reader.f32()?, reader.f32()?);
+            CasColor::from_lab_d50(l * 100.0, a, b)
+        }
+        b"Gray" => {
+            let gray = unit_channel(reader.f32()?);
+            CasColor::from_rgb(gray, gray, gray)
+        }
+        other => {
+            return Err(PaletteError::InvalidData {
+                offset: model_offset,
+                message: format!("unknown color model \"{}\"", String::from_utf8_lossy(other)),
+            })
+        }
+    };
+
+    let kind = match reader.u16()? {
+        0 => SwatchKind::Global,
+        1 => SwatchKind::Spot,
+        2 => SwatchKind::Normal,
+        other => return Err(reader.error(format!("unknown swatch type {}", other))),
+    };
+
+    Ok(PaletteEntry {
+        color,
+        name: Some(name).filter(|n| !n.is_empty()),
+        group: None,
+        kind,
+        alias: None,
+    })
+}
+
+// Floats outside 0-1 (seen in some exporters) are clamped; NaN becomes 0
+fn unit_channel(value: f32) -> u8 {
+    (value * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+// Colors are written as RGB. Consecutive entries sharing a group are
+// wrapped in group start/end blocks.
+pub fn write_ase(palette: &Palette) -> Vec<u8> {
+    let mut blocks: Vec<(u16, Vec<u8>)> = Vec::new();
+    let mut group: Option<&str> = None;
+
+    for entry in &palette.entries {
+        if entry.group.as_deref() != group {
+            if group.is_some() {
+                blocks.push((BLOCK_GROUP_END, Vec::new()));
+            }
+            if let Some(name) = entry.group.as_deref() {
+                blocks.push((BLOCK_GROUP_START, name_data(name)));
+            }
+            group = entry.group.as_deref();
+        }
+        blocks.push((BLOCK_COLOR, color_data(entry)));
+    }
+    if group.is_some() {
+        blocks.push((BLOCK_GROUP_END, Vec::new()));
+    }
+
+    let mut out = SIGNATURE.to_vec();
+    put_u16(&mut out, 1);
+    put_u16(&mut out, 0);
+    put_u32(&mut out, blocks.len() as u32);
+
+    for (block_type, data) in blocks {
+        put_u16(&mut out, block_type);
+        put_u32(&mut out, data.len() as u32);
+        out.extend_from_slice(&data);
+    }
+
+    out
+}
+
+// A u16 length in code units (including the NUL) followed by the name.
+// Names too long for that are cut short at a character boundary.
+fn name_data(name: &str) -> Vec<u8> {
+    let mut units = 1;
+    let end = name
+        .char_indices()
+        .find_map(|(index, c)| {
+            units += c.len_utf16();
+            (units > u16::MAX as usize).then_some(index)
+        })
+        .unwrap_or(name.len());
+
+    let mut text = Vec::new();
+    let units = put_utf16(&mut text, &name[..end]);
+
+    let mut data = Vec::new();
+    put_u16(&mut data, units as u16);
+    data.extend_from_slice(&text);
+    data
+}
+
+fn color_data(entry: &PaletteEntry) -> Vec<u8> {
+    // Adobe applications expect every swatch to be named
+    let name = entry.name.clone().unwrap_or_else(|| entry.color.to_hex());
+    let mut data = name_data(&name);
+
+    let (r, g, b) = entry.color.to_rgb();
+    data.extend_from_slice(b"RGB ");
+    for channel in [r, g, b] {
+        put_f32(&mut data, channel as f32 / 255.0);
+    }
+
+    let kind = match entry.kind {
+        SwatchKind::Global => 0,
+        SwatchKind::Spot => 1,
+        SwatchKind::Normal => 2,
+    };
+    put_u16(&mut data, kind);
+    data
+}
@@ -0,0 +1,93 @@
+// Big-endian readers and writers shared by the Adobe swatch formats
+
+use super::PaletteError;
+
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> PaletteError {
+        PaletteError::InvalidData {
+            offset: self.pos,
+            message: message.into(),
+        }
+    }
+
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8], PaletteError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| self.error(format!("unexpected end of file reading {} bytes", len)))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    pub fn u16(&mut self) -> Result<u16, PaletteError> {
+        let b = self.take(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    pub fn u32(&mut self) -> Result<u32, PaletteError> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    pub fn f32(&mut self) -> Result<f32, PaletteError> {
+        let b = self.take(4)?;
+        Ok(f32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    // `units` UTF-16BE code units, the last of which is a NUL terminator
+    pub fn utf16(&mut self, units: usize) -> Result<String, PaletteError> {
+        let start = self.pos;
+        let bytes = self.take(units.checked_mul(2).ok_or_else(|| self.error("name length overflows"))?)?;
+        let mut code_units: Vec<u16> = bytes.chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect();
+
+        if code_units.last() == Some(&0) {
+            code_units.pop();
+        }
+
+        String::from_utf16(&code_units).map_err(|_| PaletteError::InvalidData {
+            offset: start,
+            message: "name is not valid UTF-16".to_string(),
+        })
+    }
+}
+
+pub fn put_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+pub fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+pub fn put_f32(out: &mut Vec<u8>, value: f32) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+// NUL-terminated UTF-16BE, returning the number of code units written
+pub fn put_utf16(out: &mut Vec<u8>, text: &str) -> usize {
+    let mut units = 0;
+    for unit in text.encode_utf16().chain(std::iter::once(0)) {
+        put_u16(out, unit);
+        units += 1;
+    }
+    units
+}
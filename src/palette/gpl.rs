@@ -0,0 +1,111 @@
+// GIMP palette (.gpl) files, also read and written by Inkscape and Krita
+//
+//   GIMP Palette
+//   Name: Brand
+//   Columns: 4
+//   #
+//   255  87  51	Tomato
+
+use super::{Palette, PaletteError};
+use crate::color::CasColor;
+
+const HEADER: &str = "GIMP Palette";
+
+// GIMP writes this for colors without a name
+const UNTITLED: &str = "Untitled";
+
+pub fn read_gpl(text: &str) -> Result<Palette, PaletteError> {
+    let mut lines = text.trim_start_matches('\u{feff}').lines().enumerate();
+
+    match lines.next() {
+        Some((_, line)) if line.trim() == HEADER => {}
+        _ => {
+            return Err(PaletteError::ParseError {
+                line: 1,
+                message: format!("missing \"{}\" header", HEADER),
+            })
+        }
+    }
+
+    let mut palette = Palette::default();
+
+    for (index, line) in lines {
+        let line_number = index + 1;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = trimmed.strip_prefix("Name:") {
+            palette.name = Some(name.trim().to_string()).filter(|n| !n.is_empty());
+            continue;
+        }
+
+        if let Some(columns) = trimmed.strip_prefix("Columns:") {
+            let columns = columns.trim().parse::<usize>().map_err(|_| PaletteError::ParseError {
+                line: line_number,
+                message: format!("invalid column count: {}", columns.trim()),
+            })?;
+            palette.columns = Some(columns).filter(|c| *c > 0);
+            continue;
+        }
+
+        let (color, name) = parse_entry(trimmed).map_err(|message| PaletteError::ParseError {
+            line: line_number,
+            message,
+        })?;
+        palette.push(color, name);
+    }
+
+    Ok(palette)
+}
+
+// "R G B [name]" with any amount of whitespace between the channels
+fn parse_entry(line: &str) -> Result<(CasColor, Option<String>), String> {
+    let mut rest = line;
+    let mut channels = [0u8; 3];
+
+    for channel in channels.iter_mut() {
+        rest = rest.trim_start();
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let value = &rest[..end];
+        *channel = value
+            .parse::<u8>()
+            .map_err(|_| format!("expected a channel value 0-255, found \"{}\"", value))?;
+        rest = &rest[end..];
+    }
+
+    let name = rest.trim();
+    let name = if name.is_empty() || name == UNTITLED {
+        None
+    } else {
+        Some(name.to_string())
+    };
+
+    Ok((CasColor::from_rgb(channels[0], channels[1], channels[2]), name))
+}
+
+pub fn write_gpl(palette: &Palette) -> String {
+    let mut output = format!("{}\n", HEADER);
+
+    if let Some(name) = &palette.name {
+        output.push_str(&format!("Name: {}\n", single_line(name)));
+    }
+    if let Some(columns) = palette.columns {
+        output.push_str(&format!("Columns: {}\n", columns));
+    }
+    output.push_str("#\n");
+
+    for entry in &palette.entries {
+        let (r, g, b) = entry.color.to_rgb();
+        let name = entry.name.as_deref().map(single_line).unwrap_or_else(|| UNTITLED.to_string());
+        output.push_str(&format!("{:3} {:3} {:3}\t{}\n", r, g, b, name));
+    }
+
+    output
+}
+
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
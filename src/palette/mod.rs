@@ -0,0 +1,246 @@
+// Palettes: named, ordered color lists and the file formats that store them
+
+mod aco;
+mod ase;
+mod binary;
+mod gpl;
+mod tokens;
+
+pub use aco::{read_aco, write_aco};
+pub use ase::{read_ase, write_ase};
+pub use gpl::{read_gpl, write_gpl};
+pub use tokens::{read_tokens, write_tokens};
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::color::{grid_color, CasColor, GRID_COLS, GRID_ROWS};
+
+#[derive(Debug)]
+pub enum PaletteError {
+    IoError(io::Error),
+    UnknownFormat(String),
+    ParseError { line: usize, message: String },
+    // Binary formats report the byte offset instead of a line
+    InvalidData { offset: usize, message: String },
+    // Design token files report the dotted token path
+    InvalidToken { path: String, message: String },
+}
+
+impl fmt::Display for PaletteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaletteError::IoError(e) => write!(f, "IO error: {}", e),
+            PaletteError::UnknownFormat(name) => write!(f, "Unknown palette format: {}", name),
+            PaletteError::ParseError { line, message } => write!(f, "line {}: {}", line, message),
+            PaletteError::InvalidData { offset, message } => write!(f, "offset {}: {}", offset, message),
+            PaletteError::InvalidToken { path, message } => write!(f, "token {}: {}", path, message),
+        }
+    }
+}
+
+impl std::error::Error for PaletteError {}
+
+impl From<io::Error> for PaletteError {
+    fn from(e: io::Error) -> Self {
+        PaletteError::IoError(e)
+    }
+}
+
+// How Adobe applications treat a swatch; other formats only carry Normal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SwatchKind {
+    // Edits to the swatch update every use of it
+    Global,
+    Spot,
+    #[default]
+    Normal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteEntry {
+    pub color: CasColor,
+    pub name: Option<String>,
+    pub group: Option<String>,
+    pub kind: SwatchKind,
+    // Path of the design token this entry refers to, e.g. color.brand.500
+    pub alias: Option<String>,
+}
+
+impl PaletteEntry {
+    pub fn new(color: CasColor, name: Option<String>) -> Self {
+        Self {
+            color,
+            name,
+            group: None,
+            kind: SwatchKind::Normal,
+            alias: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Palette {
+    pub name: Option<String>,
+    // Preferred number of columns when shown as a grid
+    pub columns: Option<usize>,
+    pub entries: Vec<PaletteEntry>,
+}
+
+impl Palette {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: Some(name.into()),
+            ..Self::default()
+        }
+    }
+
+    // The hue x lightness grid shown when no palette file is loaded
+    pub fn builtin() -> Self {
+        let mut palette = Palette::new("cascolor");
+        palette.columns = Some(GRID_COLS);
+        for row in 0..GRID_ROWS {
+            for col in 0..GRID_COLS {
+                palette.push(grid_color(row, col), None);
+            }
+        }
+        palette
+    }
+
+    pub fn push(&mut self, color: CasColor, name: Option<String>) {
+        self.entries.push(PaletteEntry::new(color, name));
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Grid width: the palette's own preference, else 10 like the built-in grid
+    pub fn grid_columns(&self) -> usize {
+        self.columns.filter(|c| *c > 0).unwrap_or(GRID_COLS)
+    }
+
+    pub fn grid_rows(&self) -> usize {
+        self.len().div_ceil(self.grid_columns())
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&PaletteEntry> {
+        if col >= self.grid_columns() {
+            return None;
+        }
+        self.entries.get(row * self.grid_columns() + col)
+    }
+
+    // Read a palette file, choosing the format from its extension
+    pub fn load(path: &Path) -> Result<Self, PaletteError> {
+        let format = PaletteFormat::from_path(path)?;
+        format.read(&fs::read(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), PaletteError> {
+        let format = PaletteFormat::from_path(path)?;
+        fs::write(path, format.write(self)?)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteFormat {
+    Gpl,
+    Ase,
+    Aco,
+    Tokens,
+}
+
+impl PaletteFormat {
+    pub const ALL: [PaletteFormat; 4] = [PaletteFormat::Gpl, PaletteFormat::Ase, PaletteFormat::Aco, PaletteFormat::Tokens];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            PaletteFormat::Gpl => "gpl",
+            PaletteFormat::Ase => "ase",
+            PaletteFormat::Aco => "aco",
+            PaletteFormat::Tokens => "tokens",
+        }
+    }
+
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            PaletteFormat::Gpl => &["gpl"],
+            PaletteFormat::Ase => &["ase"],
+            PaletteFormat::Aco => &["aco"],
+            PaletteFormat::Tokens => &["tokens.json", "tokens"],
+        }
+    }
+
+    // Matches whole suffixes so compound extensions like .tokens.json work
+    pub fn from_path(path: &Path) -> Result<Self, PaletteError> {
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n.to_ascii_lowercase())
+            .unwrap_or_default();
+
+        PaletteFormat::ALL
+            .iter()
+            .copied()
+            .find(|format| {
+                format
+                    .extensions()
+                    .iter()
+                    .any(|ext| file_name.strip_suffix(ext).is_some_and(|stem| stem.ends_with('.')))
+            })
+            .ok_or_else(|| PaletteError::UnknownFormat(path.display().to_string()))
+    }
+
+    pub fn read(&self, bytes: &[u8]) -> Result<Palette, PaletteError> {
+        match self {
+            PaletteFormat::Gpl => read_gpl(utf8(bytes)?),
+            PaletteFormat::Ase => read_ase(bytes),
+            PaletteFormat::Aco => read_aco(bytes),
+            PaletteFormat::Tokens => read_tokens(utf8(bytes)?),
+        }
+    }
+
+    pub fn write(&self, palette: &Palette) -> Result<Vec<u8>, PaletteError> {
+        Ok(match self {
+            PaletteFormat::Gpl => write_gpl(palette).into_bytes(),
+            PaletteFormat::Ase => write_ase(palette),
+            PaletteFormat::Aco => write_aco(palette),
+            PaletteFormat::Tokens => write_tokens(palette)?.into_bytes(),
+        })
+    }
+}
+
+fn utf8(bytes: &[u8]) -> Result<&str, PaletteError> {
+    std::str::from_utf8(bytes).map_err(|e| PaletteError::ParseError {
+        line: 1 + bytes[..e.valid_up_to()].iter().filter(|b| **b == b'\n').count(),
+        message: "not valid UTF-8".to_string(),
+    })
+}
+
+impl fmt::Display for PaletteFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for PaletteFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        PaletteFormat::ALL
+            .iter()
+            .copied()
+            .find(|format| format.key() == s || format.extensions().contains(&s.as_str()))
+            .ok_or_else(|| format!("Unknown palette format: {}", s))
+    }
+}
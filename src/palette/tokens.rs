@@ -0,0 +1,274 @@
+// W3C Design Tokens Community Group (DTCG) JSON files (.tokens.json)
+//
+// Tokens are objects with a `$value`; any other object is a group, and
+// `$type` is inherited from the nearest enclosing group. Color values are
+// either a CSS color string or the 2025 color object
+// `{ "colorSpace": "oklch", "components": [0.68, 0.21, 33.7], "hex": "#ff5733" }`.
+// Aliases such as `{color.brand.500}` refer to another token by path.
+
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+use super::{Palette, PaletteEntry, PaletteError};
+use crate::color::{parse_color, CasColor};
+
+// Alias chains longer than this are treated as cycles
+const MAX_ALIAS_DEPTH: usize = 32;
+
+struct Token<'a> {
+    path: Vec<String>,
+    token_type: Option<&'a str>,
+    value: &'a Value,
+}
+
+pub fn read_tokens(text: &str) -> Result<Palette, PaletteError> {
+    let root: Value = serde_json::from_str(text).map_err(|e| PaletteError::ParseError {
+        line: e.line(),
+        message: e.to_string(),
+    })?;
+    let Value::Object(root) = &root else {
+        return Err(PaletteError::ParseError {
+            line: 1,
+            message: "expected a JSON object of token groups".to_string(),
+        });
+    };
+
+    let mut tokens = Vec::new();
+    collect_tokens(root, &mut Vec::new(), None, &mut tokens);
+    let by_path: HashMap<String, &Token> = tokens.iter().map(|token| (token.path.join("."), token)).collect();
+
+    let mut palette = Palette::default();
+    for token in &tokens {
+        let path = token.path.join(".");
+        let (target, alias) = resolve_alias(token, &by_path)?;
+
+        // Tokens without a type of their own take the type of what they alias
+        if token.token_type.or(target.token_type) != Some("color") {
+            continue;
+        }
+
+        let color = parse_value(target.value).map_err(|message| PaletteError::InvalidToken {
+            path: target.path.join("."),
+            message,
+        })?;
+
+        let (name, group) = token.path.split_last().map(|(name, group)| (name.clone(), group.join("."))).unwrap_or_default();
+        let mut entry = PaletteEntry::new(color, Some(name));
+        entry.group = Some(group).filter(|g| !g.is_empty());
+        entry.alias = alias.filter(|target| *target != path);
+        palette.entries.push(entry);
+    }
+
+    Ok(palette)
+}
+
+fn collect_tokens<'a>(group: &'a Map<String, Value>, path: &mut Vec<String>, inherited_type: Option<&'a str>, tokens: &mut Vec<Token<'a>>) {
+    let group_type = group.get("$type").and_then(Value::as_str).or(inherited_type);
+
+    for (key, child) in group {
+        let Value::Object(child) = child else {
+            continue;
+        };
+        if key.starts_with('$') {
+            continue;
+        }
+
+        path.push(key.clone());
+        match child.get("$value") {
+            Some(value) => tokens.push(Token {
+                path: path.clone(),
+                token_type: child.get("$type").and_then(Value::as_str).or(group_type),
+                value,
+            }),
+            None => collect_tokens(child, path, group_type, tokens),
+        }
+        path.pop();
+    }
+}
+
+fn alias_target(value: &Value) -> Option<&str> {
+    value.as_str()?.trim().strip_prefix('{')?.strip_suffix('}')
+}
+
+// Follow `{...}` references to the token holding a concrete value. Also
+// returns the path of the first alias, which is what gets written back.
+fn resolve_alias<'a>(token: &'a Token<'a>, by_path: &HashMap<String, &'a Token<'a>>) -> Result<(&'a Token<'a>, Option<String>), PaletteError> {
+    let first = alias_target(token.value).map(|target| target.to_string());
+    let mut current = token;
+
+    for _ in 0..MAX_ALIAS_DEPTH {
+        let Some(target) = alias_target(current.value) else {
+            return Ok((current, first));
+        };
+        current = by_path.get(target).copied().ok_or_else(|| PaletteError::InvalidToken {
+            path: token.path.join("."),
+            message: format!("alias {{{}}} does not refer to a token", target),
+        })?;
+    }
+
+    Err(PaletteError::InvalidToken {
+        path: token.path.join("."),
+        message: "alias cycle".to_string(),
+    })
+}
+
+fn parse_value(value: &Value) -> Result<CasColor, String> {
+    match value {
+        Value::String(text) => parse_color(text),
+        Value::Object(object) => parse_color_object(object),
+        other => Err(format!("unsupported color value {}", other)),
+    }
+}
+
+// The 2025 color object; unknown color spaces fall back to the `hex` member
+fn parse_color_object(object: &Map<String, Value>) -> Result<CasColor, String> {
+    let space = object.get("colorSpace").and_then(Value::as_str).unwrap_or("");
+    let hex_fallback = || object.get("hex").and_then(Value::as_str).map(parse_color);
+
+    let components = object
+        .get("components")
+        .and_then(Value::as_array)
+        .ok_or_else(|| "color object has no components".to_string())?
+        .iter()
+        // "none" is a missing component, which CSS treats as zero
+        .map(|c| match c {
+            Value::Number(n) => n.as_f64().map(|v| v as f32).ok_or(()),
+            Value::String(s) if s == "none" => Ok(0.0),
+            _ => Err(()),
+        })
+        .collect::<Result<Vec<f32>, ()>>()
+        .map_err(|_| "components must be numbers or \"none\"".to_string())?;
+
+    let [a, b, c] = components[..] else {
+        return Err(format!("expected 3 components, found {}", components.len()));
+    };
+
+    let color = match space {
+        "srgb" => CasColor::from_rgb(unit(a), unit(b), unit(c)),
+        "srgb-linear" => CasColor::from_linear_rgb(a, b, c),
+        "hsl" => CasColor::from_hsl(a, b / 100.0, c / 100.0),
+        "hwb" => from_hwb(a, b / 100.0, c / 100.0),
+        "lab" => CasColor::from_lab_d50(a, b, c),
+        "lch" => {
+            let (a_axis, b_axis) = (b * c.to_radians().cos(), b * c.to_radians().sin());
+            CasColor::from_lab_d50(a, a_axis, b_axis)
+        }
+        "oklab" => CasColor::from_oklab(a, b, c),
+        "oklch" => CasColor::from_oklch(a, b, c),
+        "xyz-d65" | "xyz" => CasColor::from_xyz(a, b, c),
+        "xyz-d50" => CasColor::from_xyz_d50(a, b, c),
+        other => {
+            return hex_fallback()
+                .unwrap_or_else(|| Err(format!("unsupported color space \"{}\" and no hex fallback", other)))
+        }
+    };
+
+    Ok(color)
+}
+
+fn unit(value: f32) -> u8 {
+    (value * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+fn from_hwb(hue: f32, white: f32, black: f32) -> CasColor {
+    if white + black >= 1.0 {
+        let gray = unit(white / (white + black));
+        return CasColor::from_rgb(gray, gray, gray);
+    }
+
+    let (r, g, b) = CasColor::from_hsl(hue, 1.0, 0.5).to_rgb();
+    let mix = |channel: u8| unit(channel as f32 / 255.0 * (1.0 - white - black) + white);
+    CasColor::from_rgb(mix(r), mix(g), mix(b))
+}
+
+// Each entry becomes group path + name; aliases are kept when their target
+// is also written, otherwise the resolved color is written
+pub fn write_tokens(palette: &Palette) -> Result<String, PaletteError> {
+    let paths = token_paths(palette);
+    let mut root = Map::new();
+
+    for (entry, path) in palette.entries.iter().zip(&paths) {
+        let value = match &entry.alias {
+            Some(target) if paths.iter().any(|p| p.join(".") == *target) => Value::String(format!("{{{}}}", target)),
+            _ => color_object(&entry.color),
+        };
+
+        let mut group = &mut root;
+        for (depth, segment) in path[..path.len() - 1].iter().enumerate() {
+            group = group
+                .entry(segment.clone())
+                .or_insert_with(|| Value::Object(Map::new()))
+                .as_object_mut()
+                .filter(|group| !group.contains_key("$value"))
+                .ok_or_else(|| PaletteError::InvalidToken {
+                    path: path[..=depth].join("."),
+                    message: "is a token and a group".to_string(),
+                })?;
+        }
+        group.insert(path[path.len() - 1].clone(), json!({ "$type": "color", "$value": value }));
+    }
+
+    let mut output = serde_json::to_string_pretty(&Value::Object(root)).unwrap_or_default();
+    output.push('\n');
+    Ok(output)
+}
+
+// Unique paths that never put a token where a group is needed or vice versa
+fn token_paths(palette: &Palette) -> Vec<Vec<String>> {
+    let mut paths: Vec<Vec<String>> = Vec::with_capacity(palette.len());
+
+    for (index, entry) in palette.entries.iter().enumerate() {
+        let mut path: Vec<String> = entry
+            .group
+            .as_deref()
+            .map(|group| group.split('.').map(segment).filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+        let name = entry.name.as_deref().map(segment).filter(|s| !s.is_empty());
+        path.push(name.unwrap_or_else(|| format!("color-{}", index + 1)));
+
+        // A group segment that an earlier token already took as its name
+        // gets a suffix; later entries in that group find the same free name
+        for depth in 0..path.len() - 1 {
+            let base = path[depth].clone();
+            let mut suffix = 2;
+            while paths.iter().any(|p| p[..] == path[..=depth]) {
+                path[depth] = format!("{}-{}", base, suffix);
+                suffix += 1;
+            }
+        }
+
+        // The token itself may not reuse an earlier token's path or a group
+        let taken = |candidate: &[String]| paths.iter().any(|p| p.starts_with(candidate));
+        let base = path[path.len() - 1].clone();
+        let mut suffix = 2;
+        while taken(&path) {
+            if let Some(last) = path.last_mut() {
+                *last = format!("{}-{}", base, suffix);
+            }
+            suffix += 1;
+        }
+        paths.push(path);
+    }
+
+    paths
+}
+
+// Token and group names may not start with $ or contain {, } or .
+fn segment(name: &str) -> String {
+    name.trim()
+        .trim_start_matches('$')
+        .chars()
+        .map(|c| if matches!(c, '{' | '}' | '.') { '-' } else { c })
+        .collect()
+}
+
+fn color_object(color: &CasColor) -> Value {
+    let (r, g, b) = color.to_rgb();
+    let component = |channel: u8| (channel as f64 / 255.0 * 10000.0).round() / 10000.0;
+
+    json!({
+        "colorSpace": "srgb",
+        "components": [component(r), component(g), component(b)],
+        "hex": color.to_hex().to_lowercase(),
+    })
+}
@@ -0,0 +1,336 @@
+// Per-project settings shared by a team: a brand palette, extra copy formats
+// and contrast targets. They come from the nearest directory at or above the
+// current one with a .cascolor.toml or a cascolor.palette.* file:
+//
+//   [palette]
+//   name = "Acme"
+//   file = "design/brand.gpl"      # relative to the project directory, or
+//   colors = { primary = "#0055FF", accent = "#FF6600" }
+//
+//   [[formats]]
+//   name = "Tailwind"
+//   template = "{r} {g} {b}"
+//
+//   [contrast]
+//   minimum = 4.5
+//   backgrounds = ["#FFFFFF", "#111827"]
+//
+// The same file is also the project layer of the config (see config::layers);
+// these sections are ignored there.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use toml_edit::{DocumentMut, Item, TableLike};
+
+use crate::color::{parse_color, CasColor, ColorFormat};
+use crate::config::PROJECT_FILE;
+use crate::palette::{Palette, PaletteError, PaletteFormat};
+
+// Sections of .cascolor.toml that belong to the project, not the config
+pub const PROJECT_SECTIONS: [&str; 3] = ["palette", "formats", "contrast"];
+
+// Palette files are found by this prefix, e.g. cascolor.palette.gpl
+pub const PALETTE_PREFIX: &str = "cascolor.palette.";
+
+// WCAG AA for normal text
+pub const DEFAULT_MINIMUM_CONTRAST: f32 = 4.5;
+
+#[derive(Debug)]
+pub enum ProjectError {
+    IoError(PathBuf, io::Error),
+    SyntaxError(PathBuf, toml_edit::TomlError),
+    PaletteError(PathBuf, PaletteError),
+    // File, dotted key and what is wrong with its value
+    InvalidValue(PathBuf, String, String),
+}
+
+impl fmt::Display for ProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProjectError::IoError(path, e) => write!(f, "{}: {}", path.display(), e),
+            ProjectError::SyntaxError(path, e) => {
+                write!(f, "{}: {}", path.display(), e.message().trim().replace('\n', ", "))
+            }
+            ProjectError::PaletteError(path, e) => write!(f, "{}: {}", path.display(), e),
+            ProjectError::InvalidValue(path, key, message) => write!(f, "{}: {}: {}", path.display(), key, message),
+        }
+    }
+}
+
+impl std::error::Error for ProjectError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatTemplate {
+    pub name: String,
+    template: String,
+}
+
+impl FormatTemplate {
+    // Placeholders are {r}, {g} and {b} for the channels and the color
+    // format keys ({hex}, {rgb}, {oklch}, ...); {{ and }} are literal braces
+    pub fn new(name: impl Into<String>, template: impl Into<String>) -> Result<Self, String> {
+        let template = template.into();
+        expand(&template, &CasColor::from_rgb(0, 0, 0))?;
+        Ok(Self { name: name.into(), template })
+    }
+
+    pub fn render(&self, color: &CasColor) -> String {
+        expand(&self.template, color).expect("template was checked in new")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastTarget {
+    pub minimum: f32,
+    pub backgrounds: Vec<CasColor>,
+}
+
+impl ContrastTarget {
+    // The contrast ratio of `color` on each background and whether it passes
+    pub fn check(&self, color: &CasColor) -> Vec<(CasColor, f32, bool)> {
+        self.backgrounds
+            .iter()
+            .map(|background| {
+                let ratio = color.contrast_ratio(background);
+                (*background, ratio, ratio >= self.minimum)
+            })
+            .collect()
+    }
+}
+
+impl Default for ContrastTarget {
+    fn default() -> Self {
+        Self {
+            minimum: DEFAULT_MINIMUM_CONTRAST,
+            backgrounds: vec![CasColor::from_rgb(255, 255, 255), CasColor::from_rgb(0, 0, 0)],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Project {
+    // The directory the project files were found in
+    pub root: PathBuf,
+    pub palette: Option<Palette>,
+    // The file the palette was read from, when not inline
+    pub palette_file: Option<PathBuf>,
+    pub formats: Vec<FormatTemplate>,
+    pub contrast: Option<ContrastTarget>,
+}
+
+impl Project {
+    // The project around the current directory, if there is one
+    pub fn discover() -> Result<Option<Self>, ProjectError> {
+        let Ok(cwd) = env::current_dir() else {
+            return Ok(None);
+        };
+        find(&cwd).map(|root| Self::load(&root)).transpose()
+    }
+
+    // Read the project files in `root`. A palette in .cascolor.toml wins over
+    // a cascolor.palette.* file next to it.
+    pub fn load(root: &Path) -> Result<Self, ProjectError> {
+        let mut project = Project { root: root.to_path_buf(), ..Project::default() };
+
+        let file = root.join(PROJECT_FILE);
+        if file.is_file() {
+            let contents = fs::read_to_string(&file).map_err(|e| ProjectError::IoError(file.clone(), e))?;
+            let document: DocumentMut = contents.parse().map_err(|e| ProjectError::SyntaxError(file.clone(), e))?;
+            let invalid = |key: &str, message: String| ProjectError::InvalidValue(file.clone(), key.to_string(), message);
+
+            if let Some(item) = document.get("palette") {
+                let table = item.as_table_like().ok_or_else(|| invalid("palette", expected("a table", item)))?;
+                project.read_palette(table, &invalid)?;
+            }
+            if let Some(item) = document.get("formats") {
+                project.formats = read_formats(item, &invalid)?;
+            }
+            if let Some(item) = document.get("contrast") {
+                let table = item.as_table_like().ok_or_else(|| invalid("contrast", expected("a table", item)))?;
+                project.contrast = Some(read_contrast(table, &invalid)?);
+            }
+        }
+
+        if project.palette.is_none() {
+            if let Some(path) = palette_file(root) {
+                let palette = Palette::load(&path).map_err(|e| ProjectError::PaletteError(path.clone(), e))?;
+                project.palette = Some(palette);
+                project.palette_file = Some(path);
+            }
+        }
+        if let Some(palette) = project.palette.as_mut().filter(|palette| palette.name.is_none()) {
+            palette.name = root.file_name().map(|name| name.to_string_lossy().into_owned());
+        }
+        Ok(project)
+    }
+
+    fn read_palette(
+        &mut self,
+        table: &dyn TableLike,
+        invalid: &dyn Fn(&str, String) -> ProjectError,
+    ) -> Result<(), ProjectError> {
+        let mut palette = match (table.get("file"), table.get("colors")) {
+            (Some(_), Some(_)) => {
+                return Err(invalid("palette", "set either `file` or `colors`, not both".to_string()));
+            }
+            (Some(file), None) => {
+                let file = file.as_str().ok_or_else(|| invalid("palette.file", expected("a path", file)))?;
+                let path = self.root.join(file);
+                let palette = Palette::load(&path).map_err(|e| ProjectError::PaletteError(path.clone(), e))?;
+                self.palette_file = Some(path);
+                palette
+            }
+            (None, Some(colors)) => {
+                let colors =
+                    colors.as_table_like().ok_or_else(|| invalid("palette.colors", expected("a table", colors)))?;
+                let mut palette = Palette::default();
+                for (name, value) in colors.iter() {
+                    let key = format!("palette.colors.{}", name);
+                    let text = value.as_str().ok_or_else(|| invalid(&key, expected("a color string", value)))?;
+                    let color = parse_color(text).map_err(|e| invalid(&key, e))?;
+                    palette.push(color, Some(name.to_string()));
+                }
+                palette
+            }
+            (None, None) => return Err(invalid("palette", "expected `file` or `colors`".to_string())),
+        };
+
+        if let Some(name) = table.get("name") {
+            let name = name.as_str().ok_or_else(|| invalid("palette.name", expected("a string", name)))?;
+            palette.name = Some(name.to_string());
+        }
+        if let Some(columns) = table.get("columns") {
+            let count = columns.as_integer().filter(|count| *count > 0);
+            let count = count.ok_or_else(|| invalid("palette.columns", expected("a positive integer", columns)))?;
+            palette.columns = Some(count as usize);
+        }
+        self.palette = Some(palette);
+        Ok(())
+    }
+}
+
+// The nearest directory from `start` upwards with project files. The search
+// stops at the root of a git repository so an unrelated file further up is
+// never picked up.
+pub fn find(start: &Path) -> Option<PathBuf> {
+    for dir in start.ancestors() {
+        if dir.join(PROJECT_FILE).is_file() || palette_file(dir).is_some() {
+            return Some(dir.to_path_buf());
+        }
+        if dir.join(".git").exists() {
+            return None;
+        }
+    }
+    None
+}
+
+// The first cascolor.palette.* file in `dir` with a known palette format
+fn palette_file(dir: &Path) -> Option<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+            name.starts_with(PALETTE_PREFIX) && path.is_file() && PaletteFormat::from_path(path).is_ok()
+        })
+        .collect();
+    files.sort();
+    files.into_iter().next()
+}
+
+fn read_formats(
+    item: &Item,
+    invalid: &dyn Fn(&str, String) -> ProjectError,
+) -> Result<Vec<FormatTemplate>, ProjectError> {
+    // [[formats]] sections or an array of inline tables
+    let tables: Option<Vec<&dyn TableLike>> = match (item.as_array_of_tables(), item.as_array()) {
+        (Some(tables), _) => Some(tables.iter().map(|table| table as &dyn TableLike).collect()),
+        (None, Some(array)) => {
+            array.iter().map(|value| value.as_inline_table().map(|table| table as &dyn TableLike)).collect()
+        }
+        (None, None) => None,
+    };
+    let tables = tables.ok_or_else(|| invalid("formats", expected("an array of tables ([[formats]])", item)))?;
+
+    let mut formats = Vec::new();
+    for (index, table) in tables.into_iter().enumerate() {
+        let key = |name: &str| format!("formats[{}].{}", index, name);
+        let text = |name: &str| -> Result<String, ProjectError> {
+            let value = table.get(name).ok_or_else(|| invalid(&key(name), "missing".to_string()))?;
+            value.as_str().map(str::to_string).ok_or_else(|| invalid(&key(name), expected("a string", value)))
+        };
+        let template = FormatTemplate::new(text("name")?, text("template")?);
+        formats.push(template.map_err(|e| invalid(&key("template"), e))?);
+    }
+    Ok(formats)
+}
+
+fn read_contrast(
+    table: &dyn TableLike,
+    invalid: &dyn Fn(&str, String) -> ProjectError,
+) -> Result<ContrastTarget, ProjectError> {
+    let mut target = ContrastTarget::default();
+
+    if let Some(value) = table.get("minimum") {
+        let minimum = value.as_float().or_else(|| value.as_integer().map(|n| n as f64));
+        let minimum = minimum.filter(|ratio| (1.0..=21.0).contains(ratio));
+        target.minimum =
+            minimum.ok_or_else(|| invalid("contrast.minimum", expected("a ratio from 1 to 21", value)))? as f32;
+    }
+    if let Some(value) = table.get("backgrounds") {
+        let entries =
+            value.as_array().ok_or_else(|| invalid("contrast.backgrounds", expected("an array of colors", value)))?;
+        target.backgrounds = Vec::new();
+        for (index, entry) in entries.iter().enumerate() {
+            let key = format!("contrast.backgrounds[{}]", index);
+            let text = entry.as_str().ok_or_else(|| invalid(&key, format!("expected a color string, got {}", entry)))?;
+            target.backgrounds.push(parse_color(text).map_err(|e| invalid(&key, e))?);
+        }
+    }
+    Ok(target)
+}
+
+fn expected(what: &str, item: &Item) -> String {
+    match item.as_value() {
+        Some(value) => format!("expected {}, got {}", what, value.to_string().trim()),
+        None => format!("expected {}, got {}", what, item.type_name()),
+    }
+}
+
+fn expand(template: &str, color: &CasColor) -> Result<String, String> {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        if tail.starts_with('}') {
+            return Err("unmatched `}`; write `}}` for a literal brace".to_string());
+        }
+
+        let end = tail.find('}').ok_or("unclosed `{`; write `{{` for a literal brace")?;
+        let name = &tail[1..end];
+        let (r, g, b) = color.to_rgb();
+        let value = match name {
+            "r" => r.to_string(),
+            "g" => g.to_string(),
+            "b" => b.to_string(),
+            _ => match name.parse::<ColorFormat>() {
+                Ok(format) if format.key() == name => color.format(format),
+                _ => return Err(format!("unknown placeholder {{{}}}", name)),
+            },
+        };
+        out.push_str(&value);
+        rest = &tail[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
@@ -0,0 +1,231 @@
+// Swatch sheets: a palette laid out as labelled color cards, rendered to PNG or SVG.
+// Both renderers draw the same list of shapes, so the two outputs match.
+
+mod font;
+mod png;
+mod svg;
+
+pub use png::render_png;
+pub use svg::render_svg;
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::color::CasColor;
+use crate::palette::Palette;
+
+pub const DEFAULT_COLUMNS: usize = 4;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SheetOptions {
+    // Cards per row; defaults to DEFAULT_COLUMNS, or fewer for short palettes
+    pub columns: Option<usize>,
+    pub swatch_width: u32,
+    pub swatch_height: u32,
+    // Space between cards; the sheet margin is twice this
+    pub gap: u32,
+    // Size of one font pixel; the title is drawn one step larger
+    pub text_scale: u32,
+    // Show WCAG contrast of white and black text on each swatch
+    pub contrast: bool,
+}
+
+impl Default for SheetOptions {
+    fn default() -> Self {
+        Self {
+            columns: None,
+            swatch_width: 264,
+            swatch_height: 120,
+            gap: 16,
+            text_scale: 2,
+            contrast: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    pub color: CasColor,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Text {
+    // Top-left corner of the first glyph
+    pub x: u32,
+    pub y: u32,
+    pub scale: u32,
+    pub color: CasColor,
+    pub text: String,
+}
+
+impl Text {
+    pub fn width(&self) -> u32 {
+        font::text_width(&self.text) * self.scale
+    }
+}
+
+// Shapes in paint order
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout {
+    pub width: u32,
+    pub height: u32,
+    pub background: CasColor,
+    pub rects: Vec<Rect>,
+    pub texts: Vec<Text>,
+}
+
+pub fn layout(palette: &Palette, options: &SheetOptions) -> Layout {
+    let background = CasColor::from_rgb(255, 255, 255);
+    let ink = CasColor::from_rgb(34, 34, 34);
+    let muted = CasColor::from_rgb(102, 102, 102);
+    let outline = CasColor::from_rgb(221, 221, 221);
+
+    let scale = options.text_scale.max(1);
+    let line = font::LINE_HEIGHT * scale;
+    let margin = options.gap * 2;
+    let columns = options.columns.unwrap_or(DEFAULT_COLUMNS).min(palette.len()).max(1);
+    let rows = palette.len().div_ceil(columns);
+
+    // Cards keep the same height whether or not their color has a name
+    let label_lines = if palette.entries.iter().any(|e| e.name.is_some()) { 3 } else { 2 };
+    let card_height = options.swatch_height + line / 2 + label_lines * line;
+    let max_chars = (options.swatch_width / (font::ADVANCE * scale)).max(1) as usize;
+
+    let mut layout = Layout {
+        width: margin * 2 + columns as u32 * options.swatch_width + (columns as u32 - 1) * options.gap,
+        height: margin * 2 + rows as u32 * card_height + (rows as u32).saturating_sub(1) * options.gap,
+        background,
+        rects: Vec::new(),
+        texts: Vec::new(),
+    };
+
+    let mut top = margin;
+    if let Some(name) = &palette.name {
+        let title_scale = scale + 1;
+        let title_chars = ((layout.width - margin * 2) / (font::ADVANCE * title_scale)).max(1) as usize;
+        layout.texts.push(Text { x: margin, y: margin, scale: title_scale, color: ink, text: fit(name, title_chars) });
+        top += font::LINE_HEIGHT * title_scale + options.gap;
+        layout.height += font::LINE_HEIGHT * title_scale + options.gap;
+    }
+
+    for (index, entry) in palette.entries.iter().enumerate() {
+        let x = margin + (index % columns) as u32 * (options.swatch_width + options.gap);
+        let y = top + (index / columns) as u32 * (card_height + options.gap);
+        let color = entry.color;
+
+        // A hairline outline keeps pale swatches visible on the white sheet
+        layout.rects.push(Rect {
+            x: x.saturating_sub(1),
+            y: y.saturating_sub(1),
+            width: options.swatch_width + 2,
+            height: options.swatch_height + 2,
+            color: outline,
+        });
+        layout.rects.push(Rect { x, y, width: options.swatch_width, height: options.swatch_height, color });
+
+        let mut label_y = y + options.swatch_height + line / 2;
+        let mut labels = Vec::new();
+        if let Some(name) = &entry.name {
+            labels.push((name.clone(), ink));
+        }
+        labels.push((color.to_hex(), if entry.name.is_some() { muted } else { ink }));
+        labels.push((oklch_label(&color), muted));
+        for (text, text_color) in labels {
+            layout.texts.push(Text { x, y: label_y, scale, color: text_color, text: fit(&text, max_chars) });
+            label_y += line;
+        }
+
+        if options.contrast {
+            // Two lines in the swatch's bottom-left corner, drawn in the text color they rate
+            let inset = 4 * scale;
+            let last_top = (y + options.swatch_height).saturating_sub(inset + font::GLYPH_HEIGHT * scale);
+            let white = CasColor::from_rgb(255, 255, 255);
+            let black = CasColor::from_rgb(0, 0, 0);
+            for (text_color, lines_up) in [(white, 1), (black, 0)] {
+                let ratio = text_color.contrast_ratio(&color);
+                layout.texts.push(Text {
+                    x: x + inset,
+                    y: last_top.saturating_sub(lines_up * line),
+                    scale,
+                    color: text_color,
+                    text: fit(&format!("Aa {:.1} {}", ratio, wcag_grade(ratio)), max_chars),
+                });
+            }
+        }
+    }
+
+    layout
+}
+
+fn oklch_label(color: &CasColor) -> String {
+    let (l, c, h) = color.to_oklch();
+    format!("oklch({:.1}% {:.3} {:.0})", l * 100.0, c, h)
+}
+
+// WCAG 2.x level reached by text at this contrast ratio
+pub fn wcag_grade(ratio: f32) -> &'static str {
+    if ratio >= 7.0 {
+        "AAA"
+    } else if ratio >= 4.5 {
+        "AA"
+    } else if ratio >= 3.0 {
+        "AA Large"
+    } else {
+        "Fail"
+    }
+}
+
+// Shorten to `max` characters, marking the cut with ".."
+fn fit(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max.saturating_sub(2)).collect();
+    format!("{}..", kept)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SheetFormat {
+    Png,
+    Svg,
+}
+
+impl SheetFormat {
+    pub const ALL: [SheetFormat; 2] = [SheetFormat::Png, SheetFormat::Svg];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            SheetFormat::Png => "png",
+            SheetFormat::Svg => "svg",
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        SheetFormat::ALL.iter().copied().find(|format| format.key() == extension)
+    }
+}
+
+impl fmt::Display for SheetFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for SheetFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        SheetFormat::ALL
+            .iter()
+            .copied()
+            .find(|format| format.key() == s)
+            .ok_or_else(|| format!("Unknown sheet format: {}", s))
+    }
+}
@@ -0,0 +1,55 @@
+// Rasterize a sheet layout with the embedded bitmap font
+
+use image::codecs::png::PngEncoder;
+use image::{ExtendedColorType, ImageEncoder, ImageError, Rgb, RgbImage};
+
+use super::font::{self, GLYPH_HEIGHT, GLYPH_WIDTH};
+use super::{Layout, Text};
+use crate::color::CasColor;
+
+pub fn render_png(layout: &Layout) -> Result<Vec<u8>, ImageError> {
+    let mut image = RgbImage::from_pixel(layout.width, layout.height, pixel(&layout.background));
+
+    for rect in &layout.rects {
+        fill(&mut image, rect.x, rect.y, rect.width, rect.height, pixel(&rect.color));
+    }
+    for text in &layout.texts {
+        draw_text(&mut image, text);
+    }
+
+    let mut bytes = Vec::new();
+    PngEncoder::new(&mut bytes).write_image(image.as_raw(), image.width(), image.height(), ExtendedColorType::Rgb8)?;
+    Ok(bytes)
+}
+
+fn draw_text(image: &mut RgbImage, text: &Text) {
+    let color = pixel(&text.color);
+    for (index, c) in text.text.chars().enumerate() {
+        let left = text.x + index as u32 * font::ADVANCE * text.scale;
+        for (row, bits) in font::glyph(c).iter().enumerate().take(GLYPH_HEIGHT as usize) {
+            for col in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - col)) != 0 {
+                    let x = left + col * text.scale;
+                    let y = text.y + row as u32 * text.scale;
+                    fill(image, x, y, text.scale, text.scale, color);
+                }
+            }
+        }
+    }
+}
+
+// Fill a rectangle, clipped to the image
+fn fill(image: &mut RgbImage, x: u32, y: u32, width: u32, height: u32, color: Rgb<u8>) {
+    let right = x.saturating_add(width).min(image.width());
+    let bottom = y.saturating_add(height).min(image.height());
+    for py in y..bottom {
+        for px in x..right {
+            image.put_pixel(px, py, color);
+        }
+    }
+}
+
+fn pixel(color: &CasColor) -> Rgb<u8> {
+    let (r, g, b) = color.to_rgb();
+    Rgb([r, g, b])
+}
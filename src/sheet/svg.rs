@@ -0,0 +1,48 @@
+// Write a sheet layout as SVG. Text uses a system monospace font stretched to
+// the bitmap font's width, so line lengths match the PNG.
+
+use super::font::{ADVANCE, GLYPH_HEIGHT};
+use super::Layout;
+
+pub fn render_svg(layout: &Layout) -> String {
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n  <rect width=\"{w}\" height=\"{h}\" fill=\"{}\"/>\n",
+        layout.background.to_hex(),
+        w = layout.width,
+        h = layout.height
+    );
+
+    for rect in &layout.rects {
+        out.push_str(&format!(
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+            rect.x,
+            rect.y,
+            rect.width,
+            rect.height,
+            rect.color.to_hex()
+        ));
+    }
+
+    for text in &layout.texts {
+        if text.text.is_empty() {
+            continue;
+        }
+        // A monospace advance is about 0.6em, so this font size matches ADVANCE
+        out.push_str(&format!(
+            "  <text x=\"{}\" y=\"{}\" font-family=\"ui-monospace, Menlo, Consolas, monospace\" font-size=\"{}\" textLength=\"{}\" lengthAdjust=\"spacingAndGlyphs\" fill=\"{}\">{}</text>\n",
+            text.x,
+            text.y + GLYPH_HEIGHT * text.scale,
+            ADVANCE * text.scale * 10 / 6,
+            text.width(),
+            text.color.to_hex(),
+            escape(&text.text)
+        ));
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,148 @@
+// Terminal color support detection and ANSI swatch rendering for CLI output
+
+use std::collections::HashMap;
+
+use crate::color::CasColor;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorSupport {
+    None,
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+// Decide how much color the terminal can show, from its environment and
+// whether stdout is a TTY. Follows the NO_COLOR and CLICOLOR_FORCE
+// conventions; COLORTERM and TERM stand in for a terminfo lookup.
+pub fn detect_color_support(env: &HashMap<String, String>, is_tty: bool) -> ColorSupport {
+    let var = |name: &str| env.get(name).map(|v| v.as_str()).filter(|v| !v.is_empty());
+
+    if var("NO_COLOR").is_some() {
+        return ColorSupport::None;
+    }
+
+    let forced = var("CLICOLOR_FORCE").is_some_and(|v| v != "0");
+    if !is_tty && !forced {
+        return ColorSupport::None;
+    }
+
+    let term = var("TERM").unwrap_or("").to_ascii_lowercase();
+    if term == "dumb" {
+        return ColorSupport::None;
+    }
+
+    let colorterm = var("COLORTERM").unwrap_or("").to_ascii_lowercase();
+    if colorterm == "truecolor" || colorterm == "24bit" || term.ends_with("-direct") {
+        return ColorSupport::TrueColor;
+    }
+
+    // Terminals known to support 24-bit color without advertising it
+    if var("WT_SESSION").is_some()
+        || matches!(var("TERM_PROGRAM"), Some("iTerm.app" | "WezTerm" | "vscode" | "Hyper"))
+    {
+        return ColorSupport::TrueColor;
+    }
+
+    if term.contains("256color") {
+        return ColorSupport::Ansi256;
+    }
+
+    ColorSupport::Ansi16
+}
+
+// Detect support for the current process's stdout
+pub fn stdout_color_support() -> ColorSupport {
+    use std::io::IsTerminal;
+
+    let env: HashMap<String, String> = std::env::vars().collect();
+    detect_color_support(&env, std::io::stdout().is_terminal())
+}
+
+// `width` cells of solid color, or an empty string without color support
+pub fn swatch(color: &CasColor, support: ColorSupport, width: usize) -> String {
+    match background_code(color, support) {
+        Some(code) => format!("\x1b[{}m{}\x1b[0m", code, " ".repeat(width)),
+        None => String::new(),
+    }
+}
+
+fn background_code(color: &CasColor, support: ColorSupport) -> Option<String> {
+    let (r, g, b) = color.to_rgb();
+
+    match support {
+        ColorSupport::None => None,
+        ColorSupport::TrueColor => Some(format!("48;2;{};{};{}", r, g, b)),
+        ColorSupport::Ansi256 => Some(format!("48;5;{}", to_ansi256(r, g, b))),
+        ColorSupport::Ansi16 => {
+            let index = to_ansi16(r, g, b);
+            // 40-47 for the normal colors, 100-107 for the bright ones
+            let code = if index < 8 { 40 + index } else { 100 + index - 8 };
+            Some(code.to_string())
+        }
+    }
+}
+
+// xterm's default values for the 16 basic colors
+const ANSI16: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+// Levels of the 6x6x6 color cube occupying indices 16-231
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+pub fn to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let nearest_level = |v: u8| {
+        CUBE_LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, level)| (**level as i32 - v as i32).abs())
+            .map(|(i, _)| i as u8)
+            .unwrap_or(0)
+    };
+
+    let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+    let cube = (CUBE_LEVELS[ri as usize], CUBE_LEVELS[gi as usize], CUBE_LEVELS[bi as usize]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    // Grayscale ramp 232-255 covers 8..=238 in steps of 10
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray_value = 8 + 10 * gray_step;
+    let gray = (gray_value, gray_value, gray_value);
+
+    if distance_sq((r, g, b), gray) < distance_sq((r, g, b), cube) {
+        232 + gray_step
+    } else {
+        cube_index
+    }
+}
+
+pub fn to_ansi16(r: u8, g: u8, b: u8) -> u8 {
+    ANSI16
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, c)| distance_sq((r, g, b), **c))
+        .map(|(i, _)| i as u8)
+        .unwrap_or(0)
+}
+
+fn distance_sq(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
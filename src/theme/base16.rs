@@ -0,0 +1,61 @@
+// base16 and base24 scheme YAML (tinted-theming styling spec 0.11).
+// The ANSI slots map onto base16 the way base16-shell maps them back, and
+// the in-between shades are mixed in Oklab.
+
+use super::TerminalTheme;
+use crate::color::CasColor;
+
+pub fn scheme(theme: &TerminalTheme, base24: bool) -> String {
+    let ansi = &theme.ansi;
+    let (background, foreground) = (theme.background, theme.foreground);
+    let black = CasColor::from_rgb(0, 0, 0);
+
+    let mut palette = vec![
+        background,
+        background.mix(&foreground, 0.1),
+        theme.selection,
+        ansi[8],
+        ansi[8].mix(&foreground, 0.5),
+        foreground,
+        foreground.mix(&ansi[15], 0.5),
+        ansi[15],
+        ansi[1],
+        // Orange and brown have no ANSI slot
+        ansi[1].mix(&ansi[3], 0.5),
+        ansi[3],
+        ansi[2],
+        ansi[6],
+        ansi[4],
+        ansi[5],
+        ansi[1].mix(&background, 0.5),
+    ];
+
+    if base24 {
+        palette.extend([
+            background.mix(&black, 0.3),
+            background.mix(&black, 0.6),
+            ansi[9],
+            ansi[11],
+            ansi[10],
+            ansi[14],
+            ansi[12],
+            ansi[13],
+        ]);
+    }
+
+    let mut out = format!(
+        "system: \"{}\"\nname: {}\nauthor: \"cascolor\"\nvariant: \"{}\"\npalette:\n",
+        if base24 { "base24" } else { "base16" },
+        yaml_string(&theme.name),
+        if theme.is_dark() { "dark" } else { "light" }
+    );
+    for (index, color) in palette.iter().enumerate() {
+        out.push_str(&format!("  base{:02X}: \"{}\"\n", index, color.to_hex().to_lowercase()));
+    }
+    out
+}
+
+// Double-quoted YAML scalars use JSON's escapes
+fn yaml_string(text: &str) -> String {
+    serde_json::Value::from(text).to_string()
+}
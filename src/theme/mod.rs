@@ -0,0 +1,236 @@
+// Terminal color schemes generated from a 16-color palette
+
+mod base16;
+mod terminal;
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::color::CasColor;
+use crate::palette::Palette;
+
+// ANSI slot names in color0-color15 order
+pub const ANSI_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright-black",
+    "bright-red",
+    "bright-green",
+    "bright-yellow",
+    "bright-blue",
+    "bright-magenta",
+    "bright-cyan",
+    "bright-white",
+];
+
+// WCAG AA for body text, required of the foreground
+pub const FOREGROUND_CONTRAST: f32 = 4.5;
+// WCAG AA for large text and UI components, the default for ANSI colors
+pub const DEFAULT_MIN_CONTRAST: f32 = 3.0;
+
+#[derive(Debug)]
+pub enum ThemeError {
+    TooFewColors { found: usize },
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::TooFewColors { found } => {
+                write!(f, "A terminal theme needs 16 ANSI colors, the palette has {}", found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminalTheme {
+    pub name: String,
+    pub background: CasColor,
+    pub foreground: CasColor,
+    pub cursor: CasColor,
+    pub selection: CasColor,
+    pub ansi: [CasColor; 16],
+    // Palette colors left over once every slot was taken
+    pub unused: Vec<CasColor>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastIssue {
+    // "foreground" or an ANSI slot name
+    pub slot: &'static str,
+    pub color: CasColor,
+    pub ratio: f32,
+    pub required: f32,
+}
+
+impl TerminalTheme {
+    // Entries named after a slot ("bright red", "brightRed", "background", ...)
+    // go to that slot; the rest fill the remaining ANSI slots in order.
+    // Background and foreground default to black and white, the cursor to the
+    // foreground and the selection to bright black. Colors that don't fit
+    // are kept in `unused`.
+    pub fn from_palette(palette: &Palette) -> Result<Self, ThemeError> {
+        let mut ansi: [Option<CasColor>; 16] = [None; 16];
+        let (mut background, mut foreground, mut cursor, mut selection) = (None, None, None, None);
+        let mut unassigned = Vec::new();
+
+        for entry in &palette.entries {
+            let name = entry.name.as_deref().map(normalize).unwrap_or_default();
+            let role = match name.as_str() {
+                "background" | "bg" => &mut background,
+                "foreground" | "fg" => &mut foreground,
+                "cursor" => &mut cursor,
+                "selection" => &mut selection,
+                _ => match ANSI_NAMES.iter().position(|slot| normalize(slot) == name) {
+                    Some(index) => &mut ansi[index],
+                    None => {
+                        unassigned.push(entry.color);
+                        continue;
+                    }
+                },
+            };
+
+            if role.is_none() {
+                *role = Some(entry.color);
+            } else {
+                unassigned.push(entry.color);
+            }
+        }
+
+        let mut unassigned = unassigned.into_iter();
+        for slot in ansi.iter_mut().filter(|slot| slot.is_none()) {
+            *slot = unassigned.next();
+        }
+
+        let ansi: [CasColor; 16] = ansi
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .try_into()
+            .map_err(|found: Vec<_>| ThemeError::TooFewColors { found: found.len() })?;
+        let foreground = foreground.unwrap_or(ansi[7]);
+
+        Ok(Self {
+            name: palette.name.clone().unwrap_or_else(|| "cascolor".to_string()),
+            background: background.unwrap_or(ansi[0]),
+            foreground,
+            cursor: cursor.unwrap_or(foreground),
+            selection: selection.unwrap_or(ansi[8]),
+            ansi,
+            unused: unassigned.collect(),
+        })
+    }
+
+    pub fn is_dark(&self) -> bool {
+        self.background.relative_luminance() < self.foreground.relative_luminance()
+    }
+
+    // Colors too close to the background to read. Black is exempt in dark
+    // themes, and white and bright white in light ones, as they are meant to
+    // blend in.
+    pub fn contrast_issues(&self, min_contrast: f32) -> Vec<ContrastIssue> {
+        let exempt: &[usize] = if self.is_dark() { &[0] } else { &[7, 15] };
+        let checks = std::iter::once(("foreground", self.foreground, FOREGROUND_CONTRAST)).chain(
+            ANSI_NAMES
+                .iter()
+                .zip(self.ansi)
+                .enumerate()
+                .filter(|(index, _)| !exempt.contains(index))
+                .map(|(_, (slot, color))| (*slot, color, min_contrast)),
+        );
+
+        checks
+            .filter_map(|(slot, color, required)| {
+                let ratio = color.contrast_ratio(&self.background);
+                (ratio < required).then_some(ContrastIssue { slot, color, ratio, required })
+            })
+            .collect()
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeFormat {
+    Alacritty,
+    Kitty,
+    Wezterm,
+    Foot,
+    WindowsTerminal,
+    Xresources,
+    Iterm2,
+    Base16,
+    Base24,
+}
+
+impl ThemeFormat {
+    pub const ALL: [ThemeFormat; 9] = [
+        ThemeFormat::Alacritty,
+        ThemeFormat::Kitty,
+        ThemeFormat::Wezterm,
+        ThemeFormat::Foot,
+        ThemeFormat::WindowsTerminal,
+        ThemeFormat::Xresources,
+        ThemeFormat::Iterm2,
+        ThemeFormat::Base16,
+        ThemeFormat::Base24,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            ThemeFormat::Alacritty => "alacritty",
+            ThemeFormat::Kitty => "kitty",
+            ThemeFormat::Wezterm => "wezterm",
+            ThemeFormat::Foot => "foot",
+            ThemeFormat::WindowsTerminal => "windows-terminal",
+            ThemeFormat::Xresources => "xresources",
+            ThemeFormat::Iterm2 => "iterm2",
+            ThemeFormat::Base16 => "base16",
+            ThemeFormat::Base24 => "base24",
+        }
+    }
+}
+
+impl fmt::Display for ThemeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for ThemeFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        ThemeFormat::ALL
+            .iter()
+            .copied()
+            .find(|format| format.key() == s)
+            .ok_or_else(|| format!("Unknown theme format: {}", s))
+    }
+}
+
+pub fn render(theme: &TerminalTheme, format: ThemeFormat) -> String {
+    match format {
+        ThemeFormat::Alacritty => terminal::alacritty(theme),
+        ThemeFormat::Kitty => terminal::kitty(theme),
+        ThemeFormat::Wezterm => terminal::wezterm(theme),
+        ThemeFormat::Foot => terminal::foot(theme),
+        ThemeFormat::WindowsTerminal => terminal::windows_terminal(theme),
+        ThemeFormat::Xresources => terminal::xresources(theme),
+        ThemeFormat::Iterm2 => terminal::iterm2(theme),
+        ThemeFormat::Base16 => base16::scheme(theme, false),
+        ThemeFormat::Base24 => base16::scheme(theme, true),
+    }
+}
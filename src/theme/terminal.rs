@@ -0,0 +1,189 @@
+// Renderers for terminal emulator color scheme files
+
+use serde_json::{json, Map, Value};
+
+use super::TerminalTheme;
+use crate::color::CasColor;
+
+// Slot names without the "bright-" prefix, as Alacritty uses them
+const BASE_NAMES: [&str; 8] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+
+// Windows Terminal calls magenta "purple"
+const WINDOWS_TERMINAL_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "purple",
+    "cyan",
+    "white",
+    "brightBlack",
+    "brightRed",
+    "brightGreen",
+    "brightYellow",
+    "brightBlue",
+    "brightPurple",
+    "brightCyan",
+    "brightWhite",
+];
+
+pub fn alacritty(theme: &TerminalTheme) -> String {
+    let mut out = format!(
+        "# {}\n\n[colors.primary]\nbackground = \"{}\"\nforeground = \"{}\"\n\n[colors.cursor]\ntext = \"{}\"\ncursor = \"{}\"\n\n[colors.selection]\ntext = \"{}\"\nbackground = \"{}\"\n",
+        comment(&theme.name),
+        hex(&theme.background),
+        hex(&theme.foreground),
+        hex(&theme.background),
+        hex(&theme.cursor),
+        hex(&theme.foreground),
+        hex(&theme.selection)
+    );
+
+    for (section, colors) in [("normal", &theme.ansi[..8]), ("bright", &theme.ansi[8..])] {
+        out.push_str(&format!("\n[colors.{}]\n", section));
+        for (name, color) in BASE_NAMES.iter().zip(colors) {
+            out.push_str(&format!("{} = \"{}\"\n", name, hex(color)));
+        }
+    }
+    out
+}
+
+pub fn kitty(theme: &TerminalTheme) -> String {
+    let mut out = format!(
+        "# {}\n\nforeground {}\nbackground {}\nselection_foreground {}\nselection_background {}\ncursor {}\ncursor_text_color {}\n\n",
+        comment(&theme.name),
+        hex(&theme.foreground),
+        hex(&theme.background),
+        hex(&theme.foreground),
+        hex(&theme.selection),
+        hex(&theme.cursor),
+        hex(&theme.background)
+    );
+
+    for (index, color) in theme.ansi.iter().enumerate() {
+        out.push_str(&format!("color{} {}\n", index, hex(color)));
+    }
+    out
+}
+
+// A color scheme file for WezTerm's color_schemes directory
+pub fn wezterm(theme: &TerminalTheme) -> String {
+    let list = |colors: &[CasColor]| colors.iter().map(|c| format!("\"{}\"", hex(c))).collect::<Vec<_>>().join(", ");
+
+    format!(
+        "[colors]\nforeground = \"{}\"\nbackground = \"{}\"\ncursor_bg = \"{}\"\ncursor_border = \"{}\"\ncursor_fg = \"{}\"\nselection_bg = \"{}\"\nselection_fg = \"{}\"\nansi = [{}]\nbrights = [{}]\n\n[metadata]\nname = {}\n",
+        hex(&theme.foreground),
+        hex(&theme.background),
+        hex(&theme.cursor),
+        hex(&theme.cursor),
+        hex(&theme.background),
+        hex(&theme.selection),
+        hex(&theme.foreground),
+        list(&theme.ansi[..8]),
+        list(&theme.ansi[8..]),
+        toml_string(&theme.name)
+    )
+}
+
+// foot.ini sections; foot takes colors without the leading #
+pub fn foot(theme: &TerminalTheme) -> String {
+    let bare = |color: &CasColor| color.to_hex().trim_start_matches('#').to_lowercase();
+    let mut out = format!(
+        "# {}\n\n[cursor]\ncolor={} {}\n\n[colors]\nforeground={}\nbackground={}\nselection-foreground={}\nselection-background={}\n",
+        comment(&theme.name),
+        bare(&theme.background),
+        bare(&theme.cursor),
+        bare(&theme.foreground),
+        bare(&theme.background),
+        bare(&theme.foreground),
+        bare(&theme.selection)
+    );
+
+    for (index, color) in theme.ansi.iter().enumerate() {
+        let (kind, slot) = if index < 8 { ("regular", index) } else { ("bright", index - 8) };
+        out.push_str(&format!("{}{}={}\n", kind, slot, bare(color)));
+    }
+    out
+}
+
+// One entry for the "schemes" list in Windows Terminal's settings.json
+pub fn windows_terminal(theme: &TerminalTheme) -> String {
+    let mut scheme = Map::new();
+    scheme.insert("name".to_string(), json!(theme.name));
+    scheme.insert("background".to_string(), json!(theme.background.to_hex()));
+    scheme.insert("foreground".to_string(), json!(theme.foreground.to_hex()));
+    scheme.insert("cursorColor".to_string(), json!(theme.cursor.to_hex()));
+    scheme.insert("selectionBackground".to_string(), json!(theme.selection.to_hex()));
+    for (name, color) in WINDOWS_TERMINAL_NAMES.iter().zip(&theme.ansi) {
+        scheme.insert(name.to_string(), json!(color.to_hex()));
+    }
+
+    let mut out = serde_json::to_string_pretty(&Value::Object(scheme)).unwrap_or_default();
+    out.push('\n');
+    out
+}
+
+pub fn xresources(theme: &TerminalTheme) -> String {
+    let mut out = format!(
+        "! {}\n\n*.foreground: {}\n*.background: {}\n*.cursorColor: {}\n",
+        comment(&theme.name),
+        hex(&theme.foreground),
+        hex(&theme.background),
+        hex(&theme.cursor)
+    );
+
+    for (index, color) in theme.ansi.iter().enumerate() {
+        out.push_str(&format!("*.color{}: {}\n", index, hex(color)));
+    }
+    out
+}
+
+// An iTerm2 .itermcolors property list
+pub fn iterm2(theme: &TerminalTheme) -> String {
+    let mut colors: Vec<(String, CasColor)> = theme
+        .ansi
+        .iter()
+        .enumerate()
+        .map(|(index, color)| (format!("Ansi {} Color", index), *color))
+        .collect();
+    colors.extend([
+        ("Background Color".to_string(), theme.background),
+        ("Bold Color".to_string(), theme.foreground),
+        ("Cursor Color".to_string(), theme.cursor),
+        ("Cursor Text Color".to_string(), theme.background),
+        ("Foreground Color".to_string(), theme.foreground),
+        ("Selected Text Color".to_string(), theme.foreground),
+        ("Selection Color".to_string(), theme.selection),
+    ]);
+
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n<plist version=\"1.0\">\n<dict>\n",
+    );
+    for (key, color) in colors {
+        let (r, g, b) = color.to_rgb();
+        let unit = |channel: u8| channel as f32 / 255.0;
+        out.push_str(&format!(
+            "\t<key>{}</key>\n\t<dict>\n\t\t<key>Alpha Component</key>\n\t\t<real>1</real>\n\t\t<key>Blue Component</key>\n\t\t<real>{}</real>\n\t\t<key>Color Space</key>\n\t\t<string>sRGB</string>\n\t\t<key>Green Component</key>\n\t\t<real>{}</real>\n\t\t<key>Red Component</key>\n\t\t<real>{}</real>\n\t</dict>\n",
+            key,
+            unit(b),
+            unit(g),
+            unit(r)
+        ));
+    }
+    out.push_str("</dict>\n</plist>\n");
+    out
+}
+
+fn hex(color: &CasColor) -> String {
+    color.to_hex().to_lowercase()
+}
+
+// The name on one line, for the comment heading a file
+fn comment(name: &str) -> String {
+    name.replace(['\r', '\n'], " ")
+}
+
+fn toml_string(text: &str) -> String {
+    toml::Value::from(text).to_string()
+}
@@ -1,15 +1,34 @@
 use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{backend::Backend, Terminal};
+use std::fs;
 use std::io;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use crate::color::{CasColor, ColorFormat};
-use crate::config::Config;
+use crate::config::{self, Action, Config, Key, KeyName, Sources};
+use crate::export;
+use crate::extract::{self, ExtractOptions};
+use crate::history::{self, History, HistoryEntry, Source};
+use crate::palette::Palette;
+use crate::project::{ContrastTarget, FormatTemplate, Project};
+
+use super::export::{ExportAction, ExportDialog, ExportSource};
+use super::viewer::{ImageViewer, ViewerAction};
+use super::watch::FileWatcher;
+
+// The formats in the Color Formats panel, copied with keys 1-5
+pub const QUICK_FORMATS: [ColorFormat; 5] =
+    [ColorFormat::Hex, ColorFormat::Rgb, ColorFormat::Hsl, ColorFormat::Hsv, ColorFormat::Cmyk];
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum InputMode {
     Normal,
     Editing,
+    OpenPalette,
+    Export,
+    OpenImage,
+    Image,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -22,12 +41,15 @@ pub enum ActivePanel {
 pub struct TuiApp {
     pub config: Config,
     pub current_color: CasColor,
+    // Where the current color came from, for the history
+    pub color_source: Source,
     pub input_mode: InputMode,
     pub input_text: String,
     pub status_message: String,
     pub should_quit: bool,
     pub active_panel: ActivePanel,
-    // Palette navigation (10 cols × 30 rows)
+    // Palette shown in the left grid and the selected cell
+    pub palette: Palette,
     pub palette_col: usize,
     pub palette_row: usize,
     // Gradient navigation (saturation × lightness)
@@ -35,26 +57,70 @@ pub struct TuiApp {
     pub gradient_y: f32, // 0.0 to 1.0 (lightness/value)
     // Format list selection
     pub format_index: usize,
+    pub export_dialog: Option<ExportDialog>,
+    pub image_viewer: Option<ImageViewer>,
+    // Relative palette paths are opened from and exported to here in
+    // portable mode
+    pub palettes_dir: Option<PathBuf>,
+    // Copy formats and contrast targets from the project's .cascolor.toml,
+    // listed after the built-in formats
+    pub templates: Vec<FormatTemplate>,
+    pub contrast: Option<ContrastTarget>,
+    pub project_root: Option<PathBuf>,
+    pub project_palette_file: Option<PathBuf>,
+    // Whether the grid shows the project palette, which a reload replaces
+    pub project_palette: bool,
+    // Where the config was loaded from, to load it again when a file changes
+    pub sources: Sources,
+    pub watcher: Option<FileWatcher>,
+    // Shown in place of the status message until the next key press
+    pub status_error: Option<String>,
 }
 
 impl TuiApp {
-    pub fn new(config: Config) -> Self {
+    pub fn new(config: Config, palette: Palette, project: Option<Project>, sources: Sources) -> Self {
         let current_color = CasColor::from_rgb(128, 128, 200);
-        
-        Self {
+        let project_palette = project.as_ref().is_some_and(|project| project.palette.as_ref() == Some(&palette));
+        let project = project.unwrap_or_default();
+
+        let mut app = Self {
             config,
             current_color,
+            color_source: Source::Gradient,
             input_mode: InputMode::Normal,
             input_text: String::new(),
-            status_message: String::from("cascolor v0.1.0 | Tab: switch panel | h/j/k/l: navigate | c: copy | i: input | t: theme | q: quit"),
+            status_message: String::new(),
             should_quit: false,
             active_panel: ActivePanel::Gradient,
+            palette,
             palette_col: 5,
             palette_row: 15,
             gradient_x: 0.5,
             gradient_y: 0.5,
             format_index: 0,
+            export_dialog: None,
+            image_viewer: None,
+            palettes_dir: config::palettes_dir(),
+            templates: project.formats,
+            contrast: project.contrast,
+            project_root: Some(project.root).filter(|root| !root.as_os_str().is_empty()),
+            project_palette_file: project.palette_file,
+            project_palette,
+            sources,
+            watcher: None,
+            status_error: None,
+        };
+        app.status_message = format!("cascolor v0.1.0 | {}", app.help());
+        if let Some(root) = &app.project_root {
+            app.status_message = format!("Project settings from {} | {}", root.display(), app.status_message);
         }
+        match FileWatcher::new(app.watched_files()) {
+            Ok(watcher) => app.watcher = Some(watcher),
+            Err(e) => app.status_error = Some(format!("Not watching the config for changes: {}", e)),
+        }
+        app.status_error = app.key_conflicts().into_iter().next().or(app.status_error.take());
+        app.clamp_palette_cursor();
+        app
     }
 
     pub fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
@@ -76,22 +142,27 @@ impl TuiApp {
             if self.should_quit {
                 return Ok(());
             }
+            self.reload_changed_files();
         }
     }
 
     fn handle_key_event(&mut self, key: KeyCode, modifiers: KeyModifiers) {
-        if self.input_mode == InputMode::Editing {
+        self.status_error = None;
+        if self.input_mode == InputMode::Export {
+            self.handle_export_key(key);
+            return;
+        }
+        if self.input_mode == InputMode::Image {
+            self.handle_viewer_key(key, modifiers);
+            return;
+        }
+
+        if self.input_mode != InputMode::Normal {
             match key {
                 KeyCode::Enter => {
-                    // Try to parse input as color
-                    if let Some(color) = self.parse_color_input(&self.input_text) {
-                        self.current_color = color;
-                        self.status_message = format!("Color set to: {}", self.input_text);
-                    } else {
-                        self.status_message = format!("Invalid color format: {}", self.input_text);
-                    }
+                    let mode = std::mem::replace(&mut self.input_mode, InputMode::Normal);
+                    self.submit_input(mode);
                     self.input_text.clear();
-                    self.input_mode = InputMode::Normal;
                 }
                 KeyCode::Esc => {
                     self.input_text.clear();
@@ -109,15 +180,35 @@ impl TuiApp {
             return;
         }
 
-        // Normal mode key handling
+        // Normal mode key handling: the configured bindings, then the keys
+        // that always work
+        if let Some(action) = pressed(key, modifiers).and_then(|pressed| self.config.keys.action(pressed)) {
+            self.run_action(action);
+            return;
+        }
         match key {
-            KeyCode::Char('q') | KeyCode::Esc if modifiers.contains(KeyModifiers::NONE) => {
-                self.should_quit = true;
-            }
-            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+            KeyCode::Esc => self.should_quit = true,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => self.should_quit = true,
+            KeyCode::Left => self.move_left(),
+            KeyCode::Down => self.move_down(),
+            KeyCode::Up => self.move_up(),
+            KeyCode::Right => self.move_right(),
+            // Quick copy shortcuts
+            KeyCode::Char('1') => self.copy_format(ColorFormat::Hex),
+            KeyCode::Char('2') => self.copy_format(ColorFormat::Rgb),
+            KeyCode::Char('3') => self.copy_format(ColorFormat::Hsl),
+            KeyCode::Char('4') => self.copy_format(ColorFormat::Hsv),
+            KeyCode::Char('5') => self.copy_format(ColorFormat::Cmyk),
+            _ => {}
+        }
+    }
+
+    fn run_action(&mut self, action: Action) {
+        match action {
+            Action::Quit => {
                 self.should_quit = true;
             }
-            KeyCode::Tab => {
+            Action::NextPanel => {
                 self.active_panel = match self.active_panel {
                     ActivePanel::Palette => ActivePanel::Gradient,
                     ActivePanel::Gradient => ActivePanel::FormatList,
@@ -125,28 +216,295 @@ impl TuiApp {
                 };
                 self.status_message = format!("Switched to: {:?} panel", self.active_panel);
             }
-            KeyCode::Char('i') => {
+            Action::Input => {
                 self.input_mode = InputMode::Editing;
                 self.status_message = "Enter color (HEX, RGB, HSL, etc.) | Enter: apply | Esc: cancel".to_string();
             }
-            KeyCode::Char('t') => {
-                self.toggle_theme();
+            Action::OpenPalette => {
+                self.input_mode = InputMode::OpenPalette;
+                self.status_message = "Enter palette file (.gpl, .ase, .aco, .tokens.json) or image to extract from | Enter: load | Esc: cancel".to_string();
             }
-            KeyCode::Char('c') => {
-                self.copy_current_format();
+            Action::PickImage => {
+                self.input_mode = InputMode::OpenImage;
+                self.status_message = "Enter image file to pick from (.png, .jpg, ...) | Enter: open | Esc: cancel".to_string();
             }
-            // Vim navigation
-            KeyCode::Char('h') | KeyCode::Left => self.move_left(),
-            KeyCode::Char('j') | KeyCode::Down => self.move_down(),
-            KeyCode::Char('k') | KeyCode::Up => self.move_up(),
-            KeyCode::Char('l') | KeyCode::Right => self.move_right(),
-            // Quick copy shortcuts
-            KeyCode::Char('1') => self.copy_format(ColorFormat::Hex),
-            KeyCode::Char('2') => self.copy_format(ColorFormat::Rgb),
-            KeyCode::Char('3') => self.copy_format(ColorFormat::Hsl),
-            KeyCode::Char('4') => self.copy_format(ColorFormat::Hsv),
-            KeyCode::Char('5') => self.copy_format(ColorFormat::Cmyk),
-            _ => {}
+            Action::Export => {
+                self.export_dialog = Some(ExportDialog::new(self.config.ui.default_color_format));
+                self.input_mode = InputMode::Export;
+                self.status_message = "Export | ↑/↓: field | ←/→: change | Enter: write file | Esc: cancel".to_string();
+            }
+            Action::Theme => self.toggle_theme(),
+            Action::Copy => self.copy_current_format(),
+            Action::Left => self.move_left(),
+            Action::Down => self.move_down(),
+            Action::Up => self.move_up(),
+            Action::Right => self.move_right(),
+        }
+    }
+
+    fn submit_input(&mut self, mode: InputMode) {
+        match mode {
+            InputMode::Editing => {
+                // Try to parse input as color
+                if let Some(color) = self.parse_color_input(&self.input_text) {
+                    self.current_color = color;
+                    self.color_source = Source::Input;
+                    self.status_message = format!("Color set to: {}", self.input_text);
+                    self.remember(Source::Input, history::input_format(&self.input_text));
+                } else {
+                    self.status_message = format!("Invalid color format: {}", self.input_text);
+                }
+            }
+            InputMode::OpenPalette => {
+                let path = self.input_text.trim().to_string();
+                let file = self.palette_path(&path);
+                let loaded = if extract::is_image(&file) {
+                    extract::load_palette(&file, &ExtractOptions::default()).map_err(|e| e.to_string())
+                } else {
+                    Palette::load(&file).map_err(|e| e.to_string())
+                };
+                match loaded {
+                    Ok(palette) if palette.is_empty() => {
+                        self.status_message = format!("Palette has no colors: {}", path);
+                    }
+                    Ok(palette) => {
+                        self.project_palette = false;
+                        self.set_palette(palette);
+                    }
+                    Err(e) => self.status_message = format!("Failed to load {}: {}", path, e),
+                }
+            }
+            InputMode::OpenImage => {
+                let path = self.input_text.trim().to_string();
+                let file = expand_home(&path);
+                match image::open(&file) {
+                    Ok(image) if image.width() == 0 || image.height() == 0 => {
+                        self.status_message = format!("Image has no pixels: {}", path);
+                    }
+                    Ok(image) => {
+                        let name = file.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or(path);
+                        self.image_viewer = Some(ImageViewer::new(name, image.to_rgba8()));
+                        self.input_mode = InputMode::Image;
+                        self.status_message =
+                            "h/j/k/l: move | H/J/K/L: move 10 | +/-: sample radius | Enter: pick | Esc: close".to_string();
+                    }
+                    Err(e) => self.status_message = format!("Failed to open {}: {}", path, e),
+                }
+            }
+            InputMode::Normal | InputMode::Export | InputMode::Image => {}
+        }
+    }
+
+    fn handle_viewer_key(&mut self, key: KeyCode, modifiers: KeyModifiers) {
+        let Some(viewer) = self.image_viewer.as_mut() else {
+            self.input_mode = InputMode::Normal;
+            return;
+        };
+
+        let picked = match viewer.handle_key(key, modifiers, &self.config.keys) {
+            ViewerAction::None => return,
+            ViewerAction::Cancel => {
+                self.status_message = "Image closed".to_string();
+                false
+            }
+            ViewerAction::Pick => match viewer.color() {
+                Some(color) => {
+                    self.current_color = color;
+                    self.color_source = Source::Eyedropper;
+                    self.status_message = format!("Picked {} at {} in {}", color.to_hex(), viewer.region(), viewer.name);
+                    true
+                }
+                None => {
+                    self.status_message = format!("Nothing to pick at {}: it is transparent", viewer.region());
+                    return;
+                }
+            },
+        };
+
+        self.image_viewer = None;
+        self.input_mode = InputMode::Normal;
+        if picked {
+            self.remember(Source::Eyedropper, Some(self.config.ui.default_color_format));
+        }
+    }
+
+    // Add the current color to the history file
+    fn remember(&mut self, source: Source, format: Option<ColorFormat>) {
+        let entry = HistoryEntry::new(&self.current_color, source, format);
+        if let Err(e) = History::open(&self.config).and_then(|history| history.record(entry)) {
+            self.status_message = format!("{} (history not saved: {})", self.status_message, e);
+        }
+    }
+
+    // The key bindings for the status bar
+    fn help(&self) -> String {
+        let keys = &self.config.keys;
+        let navigate: Vec<String> =
+            [Action::Left, Action::Down, Action::Up, Action::Right].map(|action| keys.binding(action).to_string()).to_vec();
+        format!(
+            "{}: switch panel | {}: navigate | {}: copy | {}: input | {}: open palette | {}: pick from image | {}: export | {}: theme | {}: quit",
+            keys.next_panel, navigate.join("/"), keys.copy, keys.input, keys.open_palette, keys.pick_image, keys.export, keys.theme, keys.quit
+        )
+    }
+
+    fn key_conflicts(&self) -> Vec<String> {
+        let keys = &self.config.keys;
+        keys.conflicts()
+            .into_iter()
+            .map(|(first, second)| {
+                format!("Key {} is bound to both {} and {}; keys.{} has no effect", keys.binding(first), first, second, second)
+            })
+            .collect()
+    }
+
+    // The config layers and the project palette file
+    fn watched_files(&self) -> Vec<PathBuf> {
+        let sources = [&self.sources.system, &self.sources.user, &self.sources.project, &self.project_palette_file];
+        let mut files: Vec<PathBuf> = sources.into_iter().flatten().cloned().collect();
+        files.dedup();
+        files
+    }
+
+    fn reload_changed_files(&mut self) {
+        let Some(watcher) = self.watcher.as_mut() else {
+            return;
+        };
+        let changed = watcher.changed();
+        if !changed.is_empty() {
+            self.reload(&changed);
+        }
+    }
+
+    // Load the config and project again after `changed` were edited. Values
+    // that no longer validate fall back as they do at startup; while any
+    // config file can't be read at all, such as one saved half-typed, the
+    // settings loaded before are kept.
+    fn reload(&mut self, changed: &[PathBuf]) {
+        let mut problems = Vec::new();
+        match Config::load_layered(&self.sources) {
+            Ok(layered) => {
+                problems.extend(layered.problems);
+                if layered.unreadable.is_empty() {
+                    self.config = layered.config;
+                }
+            }
+            Err(e) => problems.push(e.to_string()),
+        }
+        if let Some(root) = self.project_root.clone() {
+            match Project::load(&root) {
+                Ok(project) => self.apply_project(project),
+                Err(e) => problems.push(e.to_string()),
+            }
+        }
+        problems.extend(self.key_conflicts());
+        let files = self.watched_files();
+        if let Some(watcher) = self.watcher.as_mut() {
+            watcher.set_files(files);
+        }
+
+        let names: Vec<String> =
+            changed.iter().map(|file| file.file_name().unwrap_or_default().to_string_lossy().into_owned()).collect();
+        match problems.as_slice() {
+            [] => {
+                self.status_error = None;
+                self.status_message = format!("Reloaded {}", names.join(", "));
+            }
+            [problem] => self.status_error = Some(format!("Reloaded {}: {}", names.join(", "), problem)),
+            [problem, rest @ ..] => {
+                self.status_error =
+                    Some(format!("Reloaded {}: {} (+{} more)", names.join(", "), problem, rest.len()))
+            }
+        }
+    }
+
+    fn apply_project(&mut self, project: Project) {
+        self.templates = project.formats;
+        self.contrast = project.contrast;
+        self.project_palette_file = project.palette_file;
+        self.format_index = self.format_index.min(QUICK_FORMATS.len() + self.templates.len() - 1);
+        if self.project_palette {
+            self.palette = project.palette.unwrap_or_else(Palette::builtin);
+            self.clamp_palette_cursor();
+        }
+    }
+
+    fn handle_export_key(&mut self, key: KeyCode) {
+        let Some(dialog) = self.export_dialog.as_mut() else {
+            self.input_mode = InputMode::Normal;
+            return;
+        };
+
+        match dialog.handle_key(key) {
+            ExportAction::None => return,
+            ExportAction::Cancel => self.status_message = "Export cancelled".to_string(),
+            ExportAction::Submit => self.write_export(),
+        }
+
+        self.export_dialog = None;
+        self.input_mode = InputMode::Normal;
+    }
+
+    fn write_export(&mut self) {
+        let Some(dialog) = &self.export_dialog else {
+            return;
+        };
+
+        let history;
+        let palette = match dialog.source {
+            ExportSource::Palette => &self.palette,
+            ExportSource::History => {
+                match History::open(&self.config).and_then(|history| history.load()) {
+                    Ok(entries) => history = export::history_palette(&entries),
+                    Err(e) => {
+                        self.status_message = format!("Export failed: history: {}", e);
+                        return;
+                    }
+                }
+                &history
+            }
+        };
+
+        let path = self.palette_path(dialog.path.trim());
+        let saved = match (&self.palettes_dir, path.parent()) {
+            (Some(dir), Some(parent)) if parent.starts_with(dir) => fs::create_dir_all(parent),
+            _ => Ok(()),
+        };
+        self.status_message = match saved.and_then(|()| export::save(palette, &dialog.options, &path)) {
+            Ok(()) => format!("Exported {} colors to {}", palette.len(), path.display()),
+            Err(e) => format!("Export failed: {}: {}", path.display(), e),
+        };
+    }
+
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.status_message = format!(
+            "Loaded palette {} ({} colors)",
+            palette.name.as_deref().unwrap_or("(unnamed)"),
+            palette.len()
+        );
+        self.palette = palette;
+        self.palette_row = 0;
+        self.palette_col = 0;
+        self.active_panel = ActivePanel::Palette;
+        if let Some(entry) = self.palette.get(0, 0) {
+            self.current_color = entry.color;
+        }
+    }
+
+    // Keep the cursor on an existing cell; the last grid row may be partial
+    fn clamp_palette_cursor(&mut self) {
+        let columns = self.palette.grid_columns();
+        self.palette_row = self.palette_row.min(self.palette.grid_rows().saturating_sub(1));
+        self.palette_col = self.palette_col.min(columns - 1);
+        while self.palette_col > 0 && self.palette.get(self.palette_row, self.palette_col).is_none() {
+            self.palette_col -= 1;
+        }
+    }
+
+    fn move_palette_cursor(&mut self, row: usize, col: usize) {
+        if self.palette.get(row, col).is_some() {
+            self.palette_row = row;
+            self.palette_col = col;
+            self.update_color_from_palette();
         }
     }
 
@@ -154,8 +512,7 @@ impl TuiApp {
         match self.active_panel {
             ActivePanel::Palette => {
                 if self.palette_col > 0 {
-                    self.palette_col -= 1;
-                    self.update_color_from_palette();
+                    self.move_palette_cursor(self.palette_row, self.palette_col - 1);
                 }
             }
             ActivePanel::Gradient => {
@@ -168,12 +525,7 @@ impl TuiApp {
 
     fn move_right(&mut self) {
         match self.active_panel {
-            ActivePanel::Palette => {
-                if self.palette_col < 9 {
-                    self.palette_col += 1;
-                    self.update_color_from_palette();
-                }
-            }
+            ActivePanel::Palette => self.move_palette_cursor(self.palette_row, self.palette_col + 1),
             ActivePanel::Gradient => {
                 self.gradient_x = (self.gradient_x + 0.05).min(1.0);
                 self.update_color_from_gradient();
@@ -186,8 +538,7 @@ impl TuiApp {
         match self.active_panel {
             ActivePanel::Palette => {
                 if self.palette_row > 0 {
-                    self.palette_row -= 1;
-                    self.update_color_from_palette();
+                    self.move_palette_cursor(self.palette_row - 1, self.palette_col);
                 }
             }
             ActivePanel::Gradient => {
@@ -204,18 +555,13 @@ impl TuiApp {
 
     fn move_down(&mut self) {
         match self.active_panel {
-            ActivePanel::Palette => {
-                if self.palette_row < 29 {
-                    self.palette_row += 1;
-                    self.update_color_from_palette();
-                }
-            }
+            ActivePanel::Palette => self.move_palette_cursor(self.palette_row + 1, self.palette_col),
             ActivePanel::Gradient => {
                 self.gradient_y = (self.gradient_y + 0.05).min(1.0);
                 self.update_color_from_gradient();
             }
             ActivePanel::FormatList => {
-                if self.format_index < 4 {
+                if self.format_index + 1 < QUICK_FORMATS.len() + self.templates.len() {
                     self.format_index += 1;
                 }
             }
@@ -223,13 +569,16 @@ impl TuiApp {
     }
 
     fn update_color_from_palette(&mut self) {
-        // Generate color from palette position
-        let hue = (self.palette_row as f32 / 30.0) * 360.0;
-        let lightness = 0.2 + (self.palette_col as f32 / 10.0) * 0.6;
-        let saturation = 0.8;
-        
-        self.current_color = CasColor::from_hsl(hue, saturation, lightness);
-        self.status_message = format!("Palette: col={} row={}", self.palette_col, self.palette_row);
+        let Some(entry) = self.palette.get(self.palette_row, self.palette_col) else {
+            return;
+        };
+
+        self.current_color = entry.color;
+        self.color_source = Source::Palette;
+        self.status_message = match &entry.name {
+            Some(name) => format!("Palette: {} ({})", name, entry.color.to_hex()),
+            None => format!("Palette: col={} row={}", self.palette_col, self.palette_row),
+        };
     }
 
     fn update_color_from_gradient(&mut self) {
@@ -241,6 +590,7 @@ impl TuiApp {
         let lightness = 1.0 - self.gradient_y; // Invert Y for natural feel
         
         self.current_color = CasColor::from_hsl(h, saturation, lightness);
+        self.color_source = Source::Gradient;
         self.status_message = format!("Gradient: sat={:.2} light={:.2}", saturation, lightness);
     }
 
@@ -256,88 +606,80 @@ impl TuiApp {
             _ => ThemeMode::Dark,
         };
         self.status_message = format!("Theme: {:?}", self.config.general.theme);
+        match self.config.save() {
+            Ok(Some(note)) => self.status_message = format!("{} | {}", self.status_message, note),
+            Ok(None) => {}
+            Err(e) => self.status_message = format!("{} (not saved: {})", self.status_message, e),
+        }
+        // Our own write is not an edit to reload
+        if let Some(watcher) = self.watcher.as_mut() {
+            watcher.refresh();
+        }
     }
 
     fn copy_current_format(&mut self) {
-        let formats = [
-            ColorFormat::Hex,
-            ColorFormat::Rgb,
-            ColorFormat::Hsl,
-            ColorFormat::Hsv,
-            ColorFormat::Cmyk,
-        ];
-        
-        if let Some(format) = formats.get(self.format_index) {
+        if let Some(format) = QUICK_FORMATS.get(self.format_index) {
             self.copy_format(*format);
+        } else if let Some(template) = self.templates.get(self.format_index - QUICK_FORMATS.len()) {
+            let (name, text) = (template.name.clone(), template.render(&self.current_color));
+            self.copy_text(&name, &text, None);
         }
     }
 
     fn copy_format(&mut self, format: ColorFormat) {
-        let text = match format {
-            ColorFormat::Hex => self.current_color.to_hex(),
-            ColorFormat::Rgb => {
-                let (r, g, b) = self.current_color.to_rgb();
-                format!("rgb({}, {}, {})", r, g, b)
-            }
-            ColorFormat::Hsl => {
-                let (h, s, l) = self.current_color.to_hsl();
-                format!("hsl({:.0}, {:.0}%, {:.0}%)", h, s * 100.0, l * 100.0)
-            }
-            ColorFormat::Hsv => {
-                let (h, s, v) = self.current_color.to_hsv();
-                format!("hsv({:.0}, {:.0}%, {:.0}%)", h, s * 100.0, v * 100.0)
-            }
-            ColorFormat::Cmyk => {
-                let (c, m, y, k) = self.current_color.to_cmyk();
-                format!("cmyk({:.0}%, {:.0}%, {:.0}%, {:.0}%)", c * 100.0, m * 100.0, y * 100.0, k * 100.0)
-            }
-        };
+        let text = self.current_color.format(format);
+        self.copy_text(&format.to_string(), &text, Some(format));
+    }
 
-        if let Err(e) = crate::clipboard::copy_to_clipboard(&text) {
+    // Copy `text` and remember the color it was made from
+    fn copy_text(&mut self, name: &str, text: &str, format: Option<ColorFormat>) {
+        if let Err(e) = crate::clipboard::copy_to_clipboard(text) {
             self.status_message = format!("Copy failed: {}", e);
         } else {
-            self.status_message = format!("Copied {} to clipboard: {}", format, text);
+            self.status_message = format!("Copied {} to clipboard: {}", name, text);
+            self.remember(self.color_source, format);
         }
     }
 
-    fn parse_color_input(&self, input: &str) -> Option<CasColor> {
-        let input = input.trim();
-        
-        // Try HEX
-        if input.starts_with('#') && (input.len() == 7 || input.len() == 4) {
-            return CasColor::from_hex(input).ok();
-        }
-        
-        // Try RGB
-        if input.starts_with("rgb(") && input.ends_with(')') {
-            let inner = &input[4..input.len() - 1];
-            let parts: Vec<&str> = inner.split(',').map(|s| s.trim()).collect();
-            if parts.len() == 3 {
-                if let (Ok(r), Ok(g), Ok(b)) = (
-                    parts[0].parse::<u8>(),
-                    parts[1].parse::<u8>(),
-                    parts[2].parse::<u8>(),
-                ) {
-                    return Some(CasColor::from_rgb(r, g, b));
-                }
-            }
-        }
-        
-        // Try HSL
-        if input.starts_with("hsl(") && input.ends_with(')') {
-            let inner = &input[4..input.len() - 1];
-            let parts: Vec<&str> = inner.split(',').map(|s| s.trim().trim_end_matches('%')).collect();
-            if parts.len() == 3 {
-                if let (Ok(h), Ok(s), Ok(l)) = (
-                    parts[0].parse::<f32>(),
-                    parts[1].parse::<f32>(),
-                    parts[2].parse::<f32>(),
-                ) {
-                    return Some(CasColor::from_hsl(h, s / 100.0, l / 100.0));
-                }
-            }
+    fn palette_path(&self, path: &str) -> PathBuf {
+        let file = expand_home(path);
+        match &self.palettes_dir {
+            Some(dir) if file.is_relative() => dir.join(file),
+            _ => file,
         }
-        
-        None
+    }
+
+    fn parse_color_input(&self, input: &str) -> Option<CasColor> {
+        crate::color::parse_color(input).ok()
+    }
+}
+
+// A key press as the config describes keys; None for keys it has no name for
+pub(super) fn pressed(code: KeyCode, modifiers: KeyModifiers) -> Option<Key> {
+    let name = match code {
+        KeyCode::Char(c) => KeyName::Char(c),
+        KeyCode::Tab => KeyName::Tab,
+        KeyCode::Enter => KeyName::Enter,
+        KeyCode::Esc => KeyName::Esc,
+        KeyCode::Backspace => KeyName::Backspace,
+        KeyCode::Up => KeyName::Up,
+        KeyCode::Down => KeyName::Down,
+        KeyCode::Left => KeyName::Left,
+        KeyCode::Right => KeyName::Right,
+        KeyCode::F(n) => KeyName::F(n),
+        _ => return None,
+    };
+    Some(Key {
+        name,
+        ctrl: modifiers.contains(KeyModifiers::CONTROL),
+        alt: modifiers.contains(KeyModifiers::ALT),
+    })
+}
+
+// The input box is not a shell, so expand a leading ~ ourselves
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
     }
 }
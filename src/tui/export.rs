@@ -0,0 +1,162 @@
+// Export dialog state: what to export, how, and where
+
+use crossterm::event::KeyCode;
+
+use crate::color::ColorFormat;
+use crate::export::{ExportFormat, ExportOptions};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportSource {
+    Palette,
+    History,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportField {
+    Source,
+    Format,
+    Naming,
+    ColorFormat,
+    Prefix,
+    Path,
+}
+
+impl ExportField {
+    pub const ALL: [ExportField; 6] = [
+        ExportField::Source,
+        ExportField::Format,
+        ExportField::Naming,
+        ExportField::ColorFormat,
+        ExportField::Prefix,
+        ExportField::Path,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportField::Source => "Source",
+            ExportField::Format => "Format",
+            ExportField::Naming => "Naming",
+            ExportField::ColorFormat => "Values",
+            ExportField::Prefix => "Prefix",
+            ExportField::Path => "File",
+        }
+    }
+
+    pub fn is_text(&self) -> bool {
+        matches!(self, ExportField::Prefix | ExportField::Path)
+    }
+}
+
+pub enum ExportAction {
+    None,
+    Cancel,
+    Submit,
+}
+
+pub struct ExportDialog {
+    pub field: usize,
+    pub source: ExportSource,
+    pub options: ExportOptions,
+    pub path: String,
+}
+
+impl ExportDialog {
+    pub fn new(color_format: ColorFormat) -> Self {
+        let mut options = ExportOptions::new(ExportFormat::Css);
+        options.color_format = color_format;
+
+        Self {
+            field: 0,
+            source: ExportSource::Palette,
+            path: options.format.default_file_name().to_string(),
+            options,
+        }
+    }
+
+    pub fn selected(&self) -> ExportField {
+        ExportField::ALL[self.field]
+    }
+
+    // Display value for a field
+    pub fn value(&self, field: ExportField) -> String {
+        match field {
+            ExportField::Source => match self.source {
+                ExportSource::Palette => "palette".to_string(),
+                ExportSource::History => "history".to_string(),
+            },
+            ExportField::Format => self.options.format.key().to_string(),
+            ExportField::Naming => self.options.case.key().to_string(),
+            ExportField::ColorFormat => self.options.color_format.key().to_string(),
+            ExportField::Prefix => self.options.prefix.clone(),
+            ExportField::Path => self.path.clone(),
+        }
+    }
+
+    // Up/Down move between fields, Left/Right cycle choices, text fields take typing
+    pub fn handle_key(&mut self, key: KeyCode) -> ExportAction {
+        match key {
+            KeyCode::Esc => return ExportAction::Cancel,
+            KeyCode::Enter => return ExportAction::Submit,
+            KeyCode::Up | KeyCode::BackTab => self.field = (self.field + ExportField::ALL.len() - 1) % ExportField::ALL.len(),
+            KeyCode::Down | KeyCode::Tab => self.field = (self.field + 1) % ExportField::ALL.len(),
+            KeyCode::Left => self.cycle(false),
+            KeyCode::Right => self.cycle(true),
+            KeyCode::Backspace => {
+                if let Some(text) = self.text_mut() {
+                    text.pop();
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Some(text) = self.text_mut() {
+                    text.push(c);
+                }
+            }
+            _ => {}
+        }
+        ExportAction::None
+    }
+
+    fn text_mut(&mut self) -> Option<&mut String> {
+        match self.selected() {
+            ExportField::Prefix => Some(&mut self.options.prefix),
+            ExportField::Path => Some(&mut self.path),
+            _ => None,
+        }
+    }
+
+    fn cycle(&mut self, forward: bool) {
+        match self.selected() {
+            ExportField::Source => {
+                self.source = match self.source {
+                    ExportSource::Palette => ExportSource::History,
+                    ExportSource::History => ExportSource::Palette,
+                };
+            }
+            ExportField::Format => {
+                let previous = self.options.format;
+                let format = next(&ExportFormat::ALL, previous, forward);
+                self.options.format = format;
+                self.options.case = format.default_case();
+                // Follow the format's file name unless the user typed their own
+                if self.path == previous.default_file_name() {
+                    self.path = format.default_file_name().to_string();
+                }
+            }
+            ExportField::Naming => self.options.case = next(self.options.format.cases(), self.options.case, forward),
+            ExportField::ColorFormat => {
+                self.options.color_format = next(&ColorFormat::ALL, self.options.color_format, forward)
+            }
+            ExportField::Prefix | ExportField::Path => {}
+        }
+    }
+}
+
+fn next<T: Copy + PartialEq>(values: &[T], current: T, forward: bool) -> T {
+    let index = values.iter().position(|v| *v == current).unwrap_or(0);
+    let len = values.len();
+    if forward {
+        values[(index + 1) % len]
+    } else {
+        values[(index + len - 1) % len]
+    }
+}
@@ -1,5 +1,8 @@
 mod app;
+mod export;
 mod ui;
+mod viewer;
+mod watch;
 
 pub use app::TuiApp;
 
@@ -12,7 +15,10 @@ use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
 use std::fmt;
 
-use crate::config::Config;
+use crate::config::{Config, Sources};
+use crate::palette::Palette;
+use crate::project::Project;
+use crate::exit::{CliError, ExitCode};
 
 #[derive(Debug)]
 pub enum TuiError {
@@ -39,7 +45,13 @@ impl From<io::Error> for TuiError {
     }
 }
 
-pub fn run(config: Config) -> Result<(), TuiError> {
+impl From<TuiError> for CliError {
+    fn from(e: TuiError) -> Self {
+        CliError::new(ExitCode::Terminal, e.to_string())
+    }
+}
+
+pub fn run(config: Config, palette: Palette, project: Option<Project>, sources: Sources) -> Result<(), TuiError> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -48,7 +60,7 @@ pub fn run(config: Config) -> Result<(), TuiError> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create app
-    let mut app = TuiApp::new(config);
+    let mut app = TuiApp::new(config, palette, project, sources);
     let res = app.run(&mut terminal);
 
     // Restore terminal (always restore, even on error)
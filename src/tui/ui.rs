@@ -3,12 +3,15 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 
-use super::app::{ActivePanel, InputMode, TuiApp};
-use crate::config::ThemeMode;
+use super::app::{ActivePanel, InputMode, TuiApp, QUICK_FORMATS};
+use super::export::{ExportDialog, ExportField};
+use super::viewer::ImageViewer;
+use crate::color::CasColor;
+use crate::config::{Action, KeysConfig, ThemeMode};
 
 pub fn draw<B: Backend>(f: &mut Frame, app: &TuiApp) {
     let is_dark = matches!(app.config.general.theme, ThemeMode::Dark);
@@ -48,7 +51,11 @@ pub fn draw<B: Backend>(f: &mut Frame, app: &TuiApp) {
     draw_status_bar::<B>(f, app, right_chunks[2], bg, fg, border);
 
     // Input overlay if in edit mode
-    if app.input_mode == InputMode::Editing {
+    if let Some(viewer) = app.image_viewer.as_ref().filter(|_| app.input_mode == InputMode::Image) {
+        draw_image_viewer(f, viewer, &app.config.keys, bg, fg, accent);
+    } else if let Some(dialog) = app.export_dialog.as_ref().filter(|_| app.input_mode == InputMode::Export) {
+        draw_export_overlay(f, dialog, bg, fg, accent);
+    } else if app.input_mode != InputMode::Normal {
         draw_input_overlay::<B>(f, app, bg, fg, border, accent);
     }
 }
@@ -69,8 +76,13 @@ fn draw_palette_grid<B: Backend>(
         Style::default().fg(border)
     };
 
+    let title = match &app.palette.name {
+        Some(name) => format!(" Palette: {} ", name),
+        None => " Color Palette ".to_string(),
+    };
+
     let block = Block::default()
-        .title(" Color Palette ")
+        .title(title)
         .borders(Borders::ALL)
         .border_style(border_style)
         .style(Style::default().bg(bg));
@@ -78,26 +90,26 @@ fn draw_palette_grid<B: Backend>(
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    // Draw 10x30 color grid
-    let cell_width = (inner.width.saturating_sub(2)) / 10;
+    // Draw the palette grid, scrolled so the selected row stays visible
+    let columns = app.palette.grid_columns();
+    let cell_width = ((inner.width.saturating_sub(2)) / columns as u16).max(1);
     let cell_height = 1;
+    let visible_rows = inner.height as usize;
+    let first_row = (app.palette_row + 1).saturating_sub(visible_rows);
     
-    for row in 0..30.min(inner.height as usize) {
-        for col in 0..10 {
+    for row in first_row..app.palette.grid_rows().min(first_row + visible_rows) {
+        for col in 0..columns {
             let x = inner.x + (col as u16 * cell_width);
-            let y = inner.y + row as u16;
+            let y = inner.y + (row - first_row) as u16;
             
             if x >= inner.x + inner.width || y >= inner.y + inner.height {
                 break;
             }
 
-            // Generate color for this palette cell
-            let hue = (row as f32 / 30.0) * 360.0;
-            let lightness = 0.2 + (col as f32 / 10.0) * 0.6;
-            let saturation = 0.8;
-            
-            let color = crate::color::CasColor::from_hsl(hue, saturation, lightness);
-            let (r, g, b) = color.to_rgb();
+            let Some(entry) = app.palette.get(row, col) else {
+                break;
+            };
+            let (r, g, b) = entry.color.to_rgb();
             let cell_color = Color::Rgb(r, g, b);
 
             // Highlight selected cell
@@ -209,26 +221,18 @@ fn draw_format_list<B: Backend>(
         Line::from(""),
     ];
 
-    // Format list
-    let formats = vec![
-        ("1", "HEX", app.current_color.to_hex()),
-        ("2", "RGB", {
-            let (r, g, b) = app.current_color.to_rgb();
-            format!("rgb({}, {}, {})", r, g, b)
-        }),
-        ("3", "HSL", {
-            let (h, s, l) = app.current_color.to_hsl();
-            format!("hsl({:.0}, {:.0}%, {:.0}%)", h, s * 100.0, l * 100.0)
-        }),
-        ("4", "HSV", {
-            let (h, s, v) = app.current_color.to_hsv();
-            format!("hsv({:.0}, {:.0}%, {:.0}%)", h, s * 100.0, v * 100.0)
-        }),
-        ("5", "CMYK", {
-            let (c, m, y, k) = app.current_color.to_cmyk();
-            format!("cmyk({:.0}%, {:.0}%, {:.0}%, {:.0}%)", c * 100.0, m * 100.0, y * 100.0, k * 100.0)
-        }),
-    ];
+    // Format list, then the project's templates
+    let mut formats: Vec<(String, String, String)> = QUICK_FORMATS
+        .iter()
+        .enumerate()
+        .map(|(idx, format)| ((idx + 1).to_string(), format.to_string(), app.current_color.format(*format)))
+        .collect();
+    formats.extend(
+        app.templates
+            .iter()
+            .map(|template| ("c".to_string(), template.name.clone(), template.render(&app.current_color))),
+    );
+    let name_width = formats.iter().map(|(_, name, _)| name.chars().count()).max().unwrap_or(0).max(5);
 
     for (idx, (key, name, value)) in formats.iter().enumerate() {
         let is_selected = is_active && app.format_index == idx;
@@ -240,11 +244,25 @@ fn draw_format_list<B: Backend>(
 
         lines.push(Line::from(vec![
             Span::styled(format!(" [{}] ", key), Style::default().fg(accent)),
-            Span::styled(format!("{:5} ", name), style),
+            Span::styled(format!("{:width$} ", name, width = name_width), style),
             Span::styled(value, style),
         ]));
     }
 
+    if let Some(contrast) = &app.contrast {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(format!(" Contrast (min {}:1)", contrast.minimum), Style::default().fg(fg))));
+        for (background, ratio, passes) in contrast.check(&app.current_color) {
+            let (r, g, b) = background.to_rgb();
+            let (verdict, color) = if passes { ("pass", Color::Green) } else { ("fail", Color::Red) };
+            lines.push(Line::from(vec![
+                Span::styled("  ██ ", Style::default().fg(Color::Rgb(r, g, b))),
+                Span::styled(format!("{} {:>5.2}:1 ", background.to_hex(), ratio), Style::default().fg(fg)),
+                Span::styled(verdict, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            ]));
+        }
+    }
+
     let para = Paragraph::new(lines)
         .style(Style::default().bg(bg))
         .alignment(Alignment::Left);
@@ -268,8 +286,12 @@ fn draw_status_bar<B: Backend>(
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    let status = Paragraph::new(app.status_message.as_str())
-        .style(Style::default().fg(fg).bg(bg))
+    let (text, color) = match &app.status_error {
+        Some(error) => (error.as_str(), Color::Red),
+        None => (app.status_message.as_str(), fg),
+    };
+    let status = Paragraph::new(text)
+        .style(Style::default().fg(color).bg(bg))
         .alignment(Alignment::Left)
         .wrap(Wrap { trim: true });
     
@@ -287,8 +309,14 @@ fn draw_input_overlay<B: Backend>(
     // Center overlay
     let area = centered_rect(60, 20, f.area());
 
+    let title = match app.input_mode {
+        InputMode::OpenPalette => " Open Palette ",
+        InputMode::OpenImage => " Open Image ",
+        _ => " Enter Color ",
+    };
+
     let block = Block::default()
-        .title(" Enter Color ")
+        .title(title)
         .borders(Borders::ALL)
         .border_style(Style::default().fg(accent).add_modifier(Modifier::BOLD))
         .style(Style::default().bg(bg));
@@ -296,7 +324,15 @@ fn draw_input_overlay<B: Backend>(
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    let text = vec![
+    let examples: &[&str] = match app.input_mode {
+        InputMode::OpenPalette => {
+            &["~/palettes/brand.gpl", "~/Downloads/brand.ase", "design/colors.tokens.json", "/usr/share/gimp/2.0/palettes/Web.gpl"]
+        }
+        InputMode::OpenImage => &["~/Pictures/screenshot.png", "design/mockup.jpg"],
+        _ => &["#FF5733", "rgb(255, 87, 51)", "hsl(9, 100%, 60%)"],
+    };
+
+    let mut text = vec![
         Line::from(""),
         Line::from(vec![
             Span::styled("  Input: ", Style::default().fg(fg)),
@@ -305,15 +341,134 @@ fn draw_input_overlay<B: Backend>(
         ]),
         Line::from(""),
         Line::from(Span::styled("  Examples:", Style::default().fg(fg).add_modifier(Modifier::DIM))),
-        Line::from(Span::styled("    #FF5733", Style::default().fg(fg).add_modifier(Modifier::DIM))),
-        Line::from(Span::styled("    rgb(255, 87, 51)", Style::default().fg(fg).add_modifier(Modifier::DIM))),
-        Line::from(Span::styled("    hsl(9, 100%, 60%)", Style::default().fg(fg).add_modifier(Modifier::DIM))),
     ];
+    for example in examples {
+        text.push(Line::from(Span::styled(format!("    {}", example), Style::default().fg(fg).add_modifier(Modifier::DIM))));
+    }
 
     let para = Paragraph::new(text).alignment(Alignment::Left);
     f.render_widget(para, inner);
 }
 
+fn draw_export_overlay(f: &mut Frame, dialog: &ExportDialog, bg: Color, fg: Color, accent: Color) {
+    let area = centered_rect(60, 40, f.area());
+
+    let block = Block::default()
+        .title(" Export ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent).add_modifier(Modifier::BOLD))
+        .style(Style::default().bg(bg));
+
+    let inner = block.inner(area);
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+
+    let mut text = vec![Line::from("")];
+    for field in ExportField::ALL {
+        let is_selected = dialog.selected() == field;
+        let value = if !is_selected {
+            dialog.value(field)
+        } else if field.is_text() {
+            format!("{}_", dialog.value(field))
+        } else {
+            format!("< {} >", dialog.value(field))
+        };
+        let style = if is_selected {
+            Style::default().fg(accent).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(fg)
+        };
+
+        text.push(Line::from(vec![
+            Span::styled(format!("  {:8} ", field.label()), Style::default().fg(fg)),
+            Span::styled(value, style),
+        ]));
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        "  ↑/↓: field  ←/→: change  Enter: export  Esc: cancel",
+        Style::default().fg(fg).add_modifier(Modifier::DIM),
+    )));
+
+    f.render_widget(Paragraph::new(text).alignment(Alignment::Left), inner);
+}
+
+// The image drawn with half blocks, two pixels per cell, and a crosshair on
+// the cell holding the cursor pixel
+fn draw_image_viewer(f: &mut Frame, viewer: &ImageViewer, keys: &KeysConfig, bg: Color, fg: Color, accent: Color) {
+    let area = centered_rect(90, 90, f.area());
+    let (width, height) = viewer.image.dimensions();
+
+    let block = Block::default()
+        .title(format!(" {} ({}x{}) ", viewer.name, width, height))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(accent).add_modifier(Modifier::BOLD))
+        .style(Style::default().bg(bg));
+
+    let inner = block.inner(area);
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+    if inner.height < 2 || inner.width == 0 {
+        return;
+    }
+
+    let view = Rect { height: inner.height - 1, ..inner };
+    let scale = viewer.scale(view.width, view.height);
+    let cursor = ((viewer.x as f32 / scale) as u16, (viewer.y as f32 / scale / 2.0) as u16);
+    let pixel = |x: u32, y: u32| blend(viewer.image.get_pixel(x, y).0, bg);
+
+    let buffer = f.buffer_mut();
+    for row in 0..view.height {
+        for col in 0..view.width {
+            let x = (col as f32 * scale) as u32;
+            let top = (row as f32 * 2.0 * scale) as u32;
+            let bottom = ((row as f32 * 2.0 + 1.0) * scale) as u32;
+            if x >= width || top >= height {
+                continue;
+            }
+
+            let upper = pixel(x, top);
+            let lower = if bottom < height { pixel(x, bottom) } else { bg };
+            let cell = &mut buffer[(view.x + col, view.y + row)];
+            if (col, row) == cursor {
+                let [r, g, b, _] = viewer.image.get_pixel(viewer.x, viewer.y).0;
+                let light = CasColor::from_rgb(r, g, b).relative_luminance() > 0.18;
+                let ink = if light { Color::Black } else { Color::White };
+                cell.set_symbol("+").set_fg(ink).set_bg(upper);
+            } else {
+                cell.set_symbol("▀").set_fg(upper).set_bg(lower);
+            }
+        }
+    }
+
+    let mut footer = vec![Span::styled(format!(" {} ", viewer.region()), Style::default().fg(fg))];
+    match viewer.color() {
+        Some(color) => {
+            let (r, g, b) = color.to_rgb();
+            footer.push(Span::styled("██", Style::default().fg(Color::Rgb(r, g, b))));
+            footer.push(Span::styled(format!(" {}", color.to_hex()), Style::default().fg(fg).add_modifier(Modifier::BOLD)));
+        }
+        None => footer.push(Span::styled("transparent", Style::default().fg(fg).add_modifier(Modifier::DIM))),
+    }
+    let navigate: Vec<String> =
+        [Action::Left, Action::Down, Action::Up, Action::Right].map(|action| keys.binding(action).to_string()).to_vec();
+    footer.push(Span::styled(
+        format!("  {}: move  Shift: ×10  +/-: radius  Enter: pick  Esc: close", navigate.join("/")),
+        Style::default().fg(fg).add_modifier(Modifier::DIM),
+    ));
+    let footer_area = Rect { y: inner.y + inner.height - 1, height: 1, ..inner };
+    f.render_widget(Paragraph::new(Line::from(footer)), footer_area);
+}
+
+// Composite a possibly transparent pixel over the panel background
+fn blend([r, g, b, a]: [u8; 4], bg: Color) -> Color {
+    let Color::Rgb(br, bg_, bb) = bg else {
+        return Color::Rgb(r, g, b);
+    };
+    let mix = |c: u8, under: u8| ((c as u16 * a as u16 + under as u16 * (255 - a as u16)) / 255) as u8;
+    Color::Rgb(mix(r, br), mix(g, bg_), mix(b, bb))
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -0,0 +1,91 @@
+// Image viewer state: the loaded image and the pixel under the cursor
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use image::RgbaImage;
+
+use crate::color::CasColor;
+use crate::config::{Action, Key, KeyName, KeysConfig};
+use crate::extract::{self, Averaging, Region};
+
+use super::app::pressed;
+
+const MAX_RADIUS: u32 = 32;
+
+pub enum ViewerAction {
+    None,
+    Cancel,
+    Pick,
+}
+
+pub struct ImageViewer {
+    pub name: String,
+    pub image: RgbaImage,
+    // Cursor in image pixels
+    pub x: u32,
+    pub y: u32,
+    // 0 picks one pixel; otherwise the circle around it is averaged in linear light
+    pub radius: u32,
+}
+
+impl ImageViewer {
+    pub fn new(name: String, image: RgbaImage) -> Self {
+        let (x, y) = (image.width() / 2, image.height() / 2);
+        Self { name, image, x, y, radius: 0 }
+    }
+
+    // Arrows and the configured left/down/up/right keys move one pixel; with
+    // Shift, or a bound letter typed in upper case, ten
+    pub fn handle_key(&mut self, key: KeyCode, modifiers: KeyModifiers, keys: &KeysConfig) -> ViewerAction {
+        let mut step = if modifiers.contains(KeyModifiers::SHIFT) { 10 } else { 1 };
+        let action = match key {
+            KeyCode::Esc => return ViewerAction::Cancel,
+            KeyCode::Enter => return ViewerAction::Pick,
+            KeyCode::Left => Some(Action::Left),
+            KeyCode::Down => Some(Action::Down),
+            KeyCode::Up => Some(Action::Up),
+            KeyCode::Right => Some(Action::Right),
+            _ => pressed(key, modifiers).and_then(|pressed| match pressed.name {
+                KeyName::Char(c) if c.is_ascii_uppercase() && keys.action(pressed).is_none() => {
+                    step = 10;
+                    keys.action(Key { name: KeyName::Char(c.to_ascii_lowercase()), ..pressed })
+                }
+                _ => keys.action(pressed),
+            }),
+        };
+
+        let (width, height) = self.image.dimensions();
+        match action {
+            Some(Action::Quit) => return ViewerAction::Cancel,
+            Some(Action::Left) => self.x = self.x.saturating_sub(step),
+            Some(Action::Right) => self.x = (self.x + step).min(width.saturating_sub(1)),
+            Some(Action::Up) => self.y = self.y.saturating_sub(step),
+            Some(Action::Down) => self.y = (self.y + step).min(height.saturating_sub(1)),
+            _ => match key {
+                KeyCode::Char('+') | KeyCode::Char('=') => self.radius = (self.radius + 1).min(MAX_RADIUS),
+                KeyCode::Char('-') => self.radius = self.radius.saturating_sub(1),
+                _ => {}
+            },
+        }
+        ViewerAction::None
+    }
+
+    pub fn region(&self) -> Region {
+        match self.radius {
+            0 => Region::Pixel { x: self.x, y: self.y },
+            radius => Region::Circle { x: self.x, y: self.y, radius },
+        }
+    }
+
+    // None when every sampled pixel is transparent
+    pub fn color(&self) -> Option<CasColor> {
+        extract::sample(&self.image, self.region(), Averaging::Linear).ok()
+    }
+
+    // Image pixels per terminal cell column to fit a cols x rows area, where
+    // each cell shows two pixels stacked with a half block
+    pub fn scale(&self, cols: u16, rows: u16) -> f32 {
+        let (width, height) = self.image.dimensions();
+        let fit = (width as f32 / cols.max(1) as f32).max(height as f32 / (rows.max(1) as f32 * 2.0));
+        fit.max(1.0)
+    }
+}
@@ -0,0 +1,118 @@
+// Watch the config and project files while the TUI runs. Editors often save
+// by writing a new file and renaming it over the old one, so the directories
+// are watched rather than the files, and a file only counts as changed when
+// its contents differ from what was last seen.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+// Editors write in several steps; wait for them to settle
+const SETTLE: Duration = Duration::from_millis(150);
+// How often to look for directories that didn't exist, e.g. a config
+// directory created after the TUI started
+const RETRY: Duration = Duration::from_secs(1);
+
+pub struct FileWatcher {
+    watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<notify::Event>>,
+    dirs: BTreeSet<PathBuf>,
+    // Each watched file and its contents when last seen (None if missing)
+    files: Vec<(PathBuf, Option<Vec<u8>>)>,
+    pending: Option<Instant>,
+    retried: Instant,
+}
+
+impl FileWatcher {
+    pub fn new(files: Vec<PathBuf>) -> notify::Result<Self> {
+        let (sender, events) = mpsc::channel();
+        let watcher = notify::recommended_watcher(move |event| {
+            let _ = sender.send(event);
+        })?;
+        let mut watcher =
+            Self { watcher, events, dirs: BTreeSet::new(), files: Vec::new(), pending: None, retried: Instant::now() };
+        watcher.set_files(files);
+        Ok(watcher)
+    }
+
+    // Watch `files` from now on. Directories that don't exist yet are skipped
+    // until the next call.
+    pub fn set_files(&mut self, files: Vec<PathBuf>) {
+        let dirs: BTreeSet<PathBuf> = files.iter().filter_map(|file| file.parent()).map(Path::to_path_buf).collect();
+        for dir in self.dirs.difference(&dirs) {
+            let _ = self.watcher.unwatch(dir);
+        }
+        let mut watched = BTreeSet::new();
+        for dir in dirs {
+            if self.dirs.contains(&dir) || self.watcher.watch(&dir, RecursiveMode::NonRecursive).is_ok() {
+                watched.insert(dir);
+            }
+        }
+        self.dirs = watched;
+        self.files = files
+            .into_iter()
+            .map(|file| {
+                let seen = fs::read(&file).ok();
+                (file, seen)
+            })
+            .collect();
+    }
+
+    // Take the current contents as seen, e.g. after writing a file ourselves,
+    // and watch directories created since
+    pub fn refresh(&mut self) {
+        let files = self.files.drain(..).map(|(file, _)| file).collect();
+        self.set_files(files);
+    }
+
+    // Watch directories that have appeared since; their files may have been
+    // written before the watch started, so check them all
+    fn watch_missing(&mut self) {
+        let missing: BTreeSet<PathBuf> = self
+            .files
+            .iter()
+            .filter_map(|(file, _)| file.parent())
+            .filter(|dir| !self.dirs.contains(*dir) && dir.is_dir())
+            .map(Path::to_path_buf)
+            .collect();
+        for dir in missing {
+            if self.watcher.watch(&dir, RecursiveMode::NonRecursive).is_ok() {
+                self.dirs.insert(dir);
+                self.pending = Some(Instant::now());
+            }
+        }
+    }
+
+    // The files whose contents changed since last seen, once events have
+    // stopped arriving for a moment; called on every tick of the UI loop
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let now = Instant::now();
+        if now.duration_since(self.retried) >= RETRY {
+            self.retried = now;
+            self.watch_missing();
+        }
+        while let Ok(event) = self.events.try_recv() {
+            if event.is_ok() {
+                self.pending = Some(now + SETTLE);
+            }
+        }
+        if self.pending.is_none_or(|at| now < at) {
+            return Vec::new();
+        }
+        self.pending = None;
+
+        let mut changed = Vec::new();
+        for (file, seen) in &mut self.files {
+            let contents = fs::read(&*file).ok();
+            if contents != *seen {
+                *seen = contents;
+                changed.push(file.clone());
+            }
+        }
+        changed
+    }
+}
@@ -2,6 +2,40 @@
 
 include!(concat!(env!("OUT_DIR"), "/built.rs"));
 
+use serde::Serialize;
+
+// Schema version of the `--version --json` document
+pub const VERSION_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionInfo {
+    pub schema_version: u32,
+    pub name: &'static str,
+    pub version: &'static str,
+    pub commit: Option<&'static str>,
+    pub built_time_utc: &'static str,
+    pub target: &'static str,
+    pub profile: &'static str,
+    pub rustc_version: &'static str,
+    pub features: Vec<&'static str>,
+}
+
+impl VersionInfo {
+    pub fn current() -> Self {
+        Self {
+            schema_version: VERSION_SCHEMA_VERSION,
+            name: PKG_NAME,
+            version: PKG_VERSION,
+            commit: GIT_COMMIT_HASH,
+            built_time_utc: BUILT_TIME_UTC,
+            target: TARGET,
+            profile: PROFILE,
+            rustc_version: RUSTC_VERSION,
+            features: FEATURES.to_vec(),
+        }
+    }
+}
+
 pub fn print_version() {
     let version = PKG_VERSION;
     let commit = GIT_COMMIT_HASH.unwrap_or("unknown");
@@ -27,6 +61,11 @@ pub fn print_version() {
     println!("Built: {} {}", build_time, tz);
 }
 
+pub fn print_version_json() -> serde_json::Result<()> {
+    println!("{}", serde_json::to_string_pretty(&VersionInfo::current())?);
+    Ok(())
+}
+
 pub fn get_version() -> &'static str {
     PKG_VERSION
 }
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod batch_tests {
+    use cascolor::batch::{read_colors, write_table, BatchColumn, BatchInput, ColumnSelector, OutputFormat};
+    use std::io::Cursor;
+
+    fn columns(list: &str) -> Vec<BatchColumn> {
+        list.split(',').map(|c| c.parse().unwrap()).collect()
+    }
+
+    fn render(rows: &[cascolor::batch::BatchRow], list: &str, format: OutputFormat) -> String {
+        let mut out = Vec::new();
+        write_table(&mut out, rows, &columns(list), format).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_read_plain_lines() {
+        let input = "#ff5733\n\n# comment\ntomato\nnot-a-color\n";
+        let (rows, errors) = read_colors(Cursor::new(input), &BatchInput::default()).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].color.to_hex(), "#FF6347");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].to_string(), "line 5: Unrecognized color: not-a-color");
+    }
+
+    #[test]
+    fn test_read_csv_column_by_name() {
+        let input = "name,color\n\"Red, bright\",#FF0000\nBlue,\"rgb(0, 0, 255)\"\n";
+        let options = BatchInput {
+            delimiter: Some(','),
+            column: ColumnSelector::Name("Color".to_string()),
+            has_header: true,
+        };
+        let (rows, errors) = read_colors(Cursor::new(input), &options).unwrap();
+
+        assert!(errors.is_empty());
+        assert_eq!(rows[0].color.to_hex(), "#FF0000");
+        assert_eq!(rows[1].input, "rgb(0, 0, 255)");
+        assert_eq!(rows[1].line, 3);
+    }
+
+    #[test]
+    fn test_missing_header_column() {
+        let options = BatchInput {
+            delimiter: Some(','),
+            column: ColumnSelector::Name("hex".to_string()),
+            has_header: true,
+        };
+        assert!(read_colors(Cursor::new("name,color\n"), &options).is_err());
+    }
+
+    #[test]
+    fn test_output_formats() {
+        let (rows, _) = read_colors(Cursor::new("rgb(255, 0, 0)\n"), &BatchInput::default()).unwrap();
+
+        assert_eq!(render(&rows, "input,hex", OutputFormat::Csv), "input,hex\n\"rgb(255, 0, 0)\",#FF0000\n");
+        assert_eq!(render(&rows, "hex,name", OutputFormat::Tsv), "hex\tname\n#FF0000\tred\n");
+        assert_eq!(
+            render(&rows, "name,hex,contrast-white", OutputFormat::Jsonl),
+            "{\"name\":\"red\",\"hex\":\"#FF0000\",\"contrast-white\":4.0}\n"
+        );
+        assert_eq!(
+            render(&rows, "hex,name", OutputFormat::Markdown),
+            "| hex | name |\n|---|---|\n| #FF0000 | red |\n"
+        );
+    }
+
+    #[test]
+    fn test_column_keys() {
+        assert_eq!(columns("oklch,contrast-black")[0].key(), "oklch");
+        assert!("bogus".parse::<BatchColumn>().is_err());
+        assert_eq!("2".parse::<ColumnSelector>().unwrap(), ColumnSelector::Index(1));
+        assert!("0".parse::<ColumnSelector>().is_err());
+    }
+}
@@ -0,0 +1,12 @@
+// Fixtures shared by the integration test crates
+
+use cascolor::color::CasColor;
+use cascolor::palette::Palette;
+
+// A small named palette; the name and "Deep Navy" exercise case conversion
+pub fn brand() -> Palette {
+    let mut palette = Palette::new("Brand Colors");
+    palette.push(CasColor::from_rgb(255, 87, 51), Some("Primary".to_string()));
+    palette.push(CasColor::from_rgb(18, 52, 86), Some("Deep Navy".to_string()));
+    palette
+}
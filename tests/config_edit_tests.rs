@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod config_edit_tests {
+    use cascolor::color::ColorFormat;
+    use cascolor::config::{set_key, unset_key, Config, ConfigError, ThemeMode};
+
+    const COMMENTED: &str = r##"# My cascolor settings
+config_version = 3
+
+[general]
+theme = "dark"   # dark in the evening
+history_size = 20
+
+[ui]
+# how colors show up
+mode = "tui"
+"##;
+
+    #[test]
+    fn test_set_keeps_comments() {
+        let updated = set_key(COMMENTED, "general.theme", "Light").unwrap();
+        assert_eq!(updated, COMMENTED.replace("theme = \"dark\"", "theme = \"light\""));
+
+        let updated = set_key(&updated, "ui.default_color_format", "oklch").unwrap();
+        assert!(updated.contains("# how colors show up\nmode = \"tui\"\ndefault_color_format = \"oklch\"\n"));
+        let (config, diagnostics) = Config::parse(&updated);
+        assert!(diagnostics.is_empty());
+        assert_eq!(config.general.theme, ThemeMode::Light);
+        assert_eq!(config.ui.default_color_format, ColorFormat::Oklch);
+    }
+
+    #[test]
+    fn test_set_new_section_and_types() {
+        let updated = set_key("", "updates.check_on_startup", "false").unwrap();
+        let updated = set_key(&updated, "general.history_size", "50").unwrap();
+        assert_eq!(updated, "config_version = 3\n\n[updates]\ncheck_on_startup = false\n\n[general]\nhistory_size = 50\n");
+    }
+
+    #[test]
+    fn test_set_rejects_bad_keys_and_values() {
+        let error = set_key(COMMENTED, "general.history_size", "5000").unwrap_err();
+        assert_eq!(error.to_string(), "Invalid value for general.history_size: expected an integer from 0 to 1000, got 5000");
+
+        let error = set_key(COMMENTED, "updates.check_on_startup", "yes").unwrap_err();
+        assert!(matches!(error, ConfigError::InvalidValue(..)));
+        assert!(matches!(set_key(COMMENTED, "general.colour", "red"), Err(ConfigError::UnknownKey(_))));
+        assert!(matches!(set_key(COMMENTED, "general", "light"), Err(ConfigError::InvalidValue(..))));
+        assert!(matches!(set_key("[general\n", "general.theme", "light"), Err(ConfigError::SyntaxError(_))));
+    }
+
+    #[test]
+    fn test_unset_and_get() {
+        let updated = unset_key(COMMENTED, "general.theme").unwrap();
+        assert!(!updated.contains("theme"));
+        assert!(updated.contains("# My cascolor settings\n"));
+
+        let (config, _) = Config::parse(COMMENTED);
+        assert_eq!(config.get("general.theme").unwrap().as_str(), Some("dark"));
+        assert_eq!(config.get("updates.channel").unwrap().as_str(), Some("stable"));
+        assert_eq!(config.get("config_version").unwrap().as_integer(), Some(3));
+        // The history is no longer part of the config
+        assert!(matches!(config.get("color_history"), Err(ConfigError::UnknownKey(_))));
+        assert!(config.get("general").unwrap().is_table());
+        assert!(matches!(config.get("ui.colour"), Err(ConfigError::UnknownKey(_))));
+    }
+
+    #[test]
+    fn test_update_writes_only_changes() {
+        let (mut config, _) = Config::parse(COMMENTED);
+        assert_eq!(config.update(COMMENTED).unwrap(), COMMENTED);
+
+        config.general.history_size = 40;
+        config.ui.default_color_format = ColorFormat::Rgb;
+        let updated = config.update(&format!("{}extra = 1\n", COMMENTED)).unwrap();
+        assert!(updated.starts_with("# My cascolor settings\nconfig_version = 3\n\n[general]\n"));
+        assert!(updated.contains("default_color_format = \"rgb\"\n"));
+        assert!(updated.contains("theme = \"dark\"   # dark in the evening\nhistory_size = 40\n"));
+        assert!(updated.contains("extra = 1\n"));
+        assert!(!updated.contains("[updates]"));
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod config_keys_tests {
+    use cascolor::config::{set_key, Action, Config, ConfigError, Key, KeyName, KeysConfig, Severity, Sources};
+
+    #[test]
+    fn test_parse_and_display() {
+        let cases = [
+            ("q", Key::char('q'), "q"),
+            ("Q", Key::char('Q'), "Q"),
+            ("Tab", Key::named(KeyName::Tab), "tab"),
+            ("space", Key::char(' '), "space"),
+            ("F12", Key::named(KeyName::F(12)), "f12"),
+            ("ctrl+o", Key { name: KeyName::Char('o'), ctrl: true, alt: false }, "ctrl+o"),
+            ("Alt+Ctrl+left", Key { name: KeyName::Left, ctrl: true, alt: true }, "ctrl+alt+left"),
+            ("+", Key::char('+'), "+"),
+            ("ctrl++", Key { name: KeyName::Char('+'), ctrl: true, alt: false }, "ctrl++"),
+        ];
+        for (text, key, display) in cases {
+            assert_eq!(text.parse::<Key>(), Ok(key), "{}", text);
+            assert_eq!(key.to_string(), display);
+            assert_eq!(display.parse::<Key>(), Ok(key));
+        }
+
+        for bad in ["", "qq", "f13", "ctrl+", "hyper+x", "\t"] {
+            assert!(bad.parse::<Key>().is_err(), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn test_defaults_and_lookup() {
+        let keys = KeysConfig::default();
+        assert_eq!(keys.action(Key::char('q')), Some(Action::Quit));
+        assert_eq!(keys.action(Key::named(KeyName::Tab)), Some(Action::NextPanel));
+        assert_eq!(keys.action(Key::char('x')), None);
+        assert!(keys.conflicts().is_empty());
+        assert!(Action::ALL.iter().all(|action| keys.binding(*action) != Key::char('1')));
+    }
+
+    #[test]
+    fn test_config_section() {
+        let (config, diagnostics) =
+            Config::parse("[keys]\nquit = \"x\"\ntheme = \"ctrl+t\"\nleft = \"nope\"\nunknown = \"u\"\n");
+        assert_eq!(config.keys.quit, Key::char('x'));
+        assert_eq!(config.keys.theme.to_string(), "ctrl+t");
+        // The invalid binding falls back; the others keep their defaults
+        assert_eq!(config.keys.left, Key::char('h'));
+        assert_eq!(config.keys.copy, Key::char('c'));
+
+        let found: Vec<(Severity, &str)> = diagnostics.iter().map(|d| (d.severity, d.key.as_str())).collect();
+        assert_eq!(found, [(Severity::Error, "keys.left"), (Severity::Warning, "keys.unknown")]);
+        assert!(diagnostics[0].message.starts_with("unknown key \"nope\""));
+
+        let written = Config::default_contents();
+        assert!(written.contains("[keys]\nquit = \"q\"\nnext_panel = \"tab\"\n"), "{}", written);
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let (config, _) = Config::parse("[keys]\ncopy = \"q\"\nright = \"q\"\n");
+        let conflicts = config.keys.conflicts();
+        assert_eq!(conflicts, [(Action::Quit, Action::Copy), (Action::Quit, Action::Right), (Action::Copy, Action::Right)]);
+        assert_eq!(config.keys.action(Key::char('q')), Some(Action::Quit));
+    }
+
+    #[test]
+    fn test_set_and_layers() {
+        // Key names keep their case; `set` doesn't lowercase them like choices
+        assert_eq!(set_key("", "keys.quit", "Q").unwrap(), "config_version = 3\n\n[keys]\nquit = \"Q\"\n");
+        assert!(matches!(set_key("", "keys.quit", "hyper"), Err(ConfigError::InvalidValue(..))));
+
+        let sources = Sources {
+            env: vec![("CASCOLOR_KEYS_EXPORT".to_string(), "f5".to_string())],
+            overrides: vec![("keys.quit".to_string(), "ctrl+q".to_string())],
+            ..Sources::default()
+        };
+        let config = Config::load_layered(&sources).unwrap().config;
+        assert_eq!(config.keys.export, Key::named(KeyName::F(5)));
+        assert_eq!(config.keys.action("ctrl+q".parse().unwrap()), Some(Action::Quit));
+        assert_eq!(config.get("keys.quit").unwrap().as_str(), Some("ctrl+q"));
+    }
+}
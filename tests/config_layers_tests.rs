@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod config_layers_tests {
+    use cascolor::color::ColorFormat;
+    use cascolor::config::{env_name, Config, ConfigError, Layer, Sources, ThemeMode, UpdateChannel};
+    use cascolor::display::UiMode;
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    // System, user and project files in a temporary directory
+    fn files(system: &str, user: &str, project: &str) -> (TempDir, Sources) {
+        let dir = TempDir::new().unwrap();
+        let write = |name: &str, contents: &str| -> PathBuf {
+            let path = dir.path().join(name);
+            fs::write(&path, contents).unwrap();
+            path
+        };
+        let sources = Sources {
+            system: Some(write("system.toml", system)),
+            user: Some(write("user.toml", user)),
+            project: Some(write(".cascolor.toml", project)),
+            ..Sources::default()
+        };
+        (dir, sources)
+    }
+
+    fn layer_of(layered: &cascolor::config::Layered, key: &str) -> Layer {
+        layered.origins.iter().find(|(name, _)| name == key).unwrap().1.layer
+    }
+
+    #[test]
+    fn test_file_precedence() {
+        let (_dir, sources) = files(
+            "[general]\ntheme = \"light\"\nhistory_size = 5\n\n[updates]\nchannel = \"beta\"\n",
+            "config_version = 3\n\n[general]\ntheme = \"system\"\n",
+            "[general]\ntheme = \"auto\"\n",
+        );
+        let layered = Config::load_layered(&sources).unwrap();
+        assert!(layered.problems.is_empty());
+
+        let config = &layered.config;
+        assert_eq!(config.general.theme, ThemeMode::Auto);
+        assert_eq!(config.general.history_size, 5);
+        assert_eq!(config.updates.channel, UpdateChannel::Beta);
+        assert_eq!(config.ui.mode, UiMode::Auto);
+
+        assert_eq!(layer_of(&layered, "general.theme"), Layer::Project);
+        assert_eq!(layer_of(&layered, "general.history_size"), Layer::System);
+        assert_eq!(layer_of(&layered, "config_version"), Layer::User);
+        assert_eq!(layer_of(&layered, "ui.mode"), Layer::Default);
+        let origin = &layered.origins[1].1;
+        assert_eq!(origin.to_string(), format!("project {}", sources.project.as_ref().unwrap().display()));
+    }
+
+    #[test]
+    fn test_env_overrides_files() {
+        let (_dir, mut sources) = files("", "[ui]\nmode = \"gui\"\n", "");
+        sources.env = vec![
+            ("CASCOLOR_UI_MODE".to_string(), "TUI".to_string()),
+            ("CASCOLOR_UI_DEFAULT_COLOR_FORMAT".to_string(), "oklch".to_string()),
+            ("CASCOLOR_GENERAL_HISTORY_SIZE".to_string(), "lots".to_string()),
+            ("CASCOLOR_CONFIG_DIR".to_string(), "/somewhere".to_string()),
+        ];
+
+        let layered = Config::load_layered(&sources).unwrap();
+        assert_eq!(layered.config.ui.mode, UiMode::Tui);
+        assert_eq!(layered.config.ui.default_color_format, ColorFormat::Oklch);
+        assert_eq!(layered.config.general.history_size, 20);
+        assert_eq!(layer_of(&layered, "ui.mode"), Layer::Env);
+
+        assert_eq!(layered.problems.len(), 1);
+        assert!(layered.problems[0].starts_with("CASCOLOR_GENERAL_HISTORY_SIZE: Invalid value for general.history_size"));
+        assert_eq!(env_name("ui.default_color_format"), "CASCOLOR_UI_DEFAULT_COLOR_FORMAT");
+    }
+
+    #[test]
+    fn test_set_overrides_everything() {
+        let (_dir, mut sources) = files("", "", "[general]\ntheme = \"light\"\n");
+        sources.env = vec![("CASCOLOR_GENERAL_THEME".to_string(), "system".to_string())];
+        sources.overrides = vec![
+            ("general.theme".to_string(), "auto".to_string()),
+            ("general.theme".to_string(), "dark".to_string()),
+        ];
+
+        let layered = Config::load_layered(&sources).unwrap();
+        assert_eq!(layered.config.general.theme, ThemeMode::Dark);
+        let origin = &layered.origins.iter().find(|(key, _)| key == "general.theme").unwrap().1;
+        assert_eq!(origin.layer, Layer::Cli);
+        assert_eq!(origin.source.as_deref(), Some("--set general.theme=dark"));
+
+        sources.overrides = vec![("general.theme".to_string(), "purple".to_string())];
+        assert!(matches!(Config::load_layered(&sources), Err(ConfigError::InvalidValue(..))));
+        sources.overrides = vec![("general.colour".to_string(), "purple".to_string())];
+        assert!(matches!(Config::load_layered(&sources), Err(ConfigError::UnknownKey(_))));
+    }
+
+    #[test]
+    fn test_invalid_values_keep_lower_layers() {
+        let (_dir, sources) = files(
+            "",
+            "[general]\nhistory_size = 50\n",
+            "[general]\nhistory_size = 9999\n\n[ui]\nmode = \"tui\"\n",
+        );
+        let layered = Config::load_layered(&sources).unwrap();
+        assert_eq!(layered.config.general.history_size, 50);
+        assert_eq!(layered.config.ui.mode, UiMode::Tui);
+        assert_eq!(layer_of(&layered, "general.history_size"), Layer::User);
+
+        assert_eq!(layered.problems.len(), 1);
+        assert!(layered.problems[0].contains(".cascolor.toml:2:16: error: general.history_size"));
+        assert!(layered.unreadable.is_empty());
+
+        // A file that isn't TOML is reported as unreadable
+        let (_dir, sources) = files("", "[general]\nhistory_size = 50\n", "[general\nhistory_size = 9\n");
+        let layered = Config::load_layered(&sources).unwrap();
+        assert_eq!(layered.unreadable, [sources.project.clone().unwrap()]);
+        assert_eq!(layered.config.general.history_size, 50);
+
+        // Missing files are skipped
+        let sources = Sources { user: Some(PathBuf::from("/nonexistent/config.toml")), ..Sources::default() };
+        let layered = Config::load_layered(&sources).unwrap();
+        assert!(layered.problems.is_empty());
+        assert!(layered.unreadable.is_empty());
+        assert!(layered.origins.iter().all(|(_, origin)| origin.layer == Layer::Default));
+    }
+
+    #[test]
+    fn test_save_writes_only_own_changes() {
+        let user = "config_version = 3\n\n# mine\n[general]\ntheme = \"light\"\n";
+        let (_dir, mut sources) = files("[updates]\nchannel = \"daily\"\n", user, "[ui]\nmode = \"tui\"\n");
+        sources.env = vec![("CASCOLOR_GENERAL_HISTORY_SIZE".to_string(), "3".to_string())];
+
+        let mut config = Config::load_layered(&sources).unwrap().config;
+        assert_eq!(config.update(user).unwrap(), user);
+
+        // Values from other layers stay out of the user file
+        config.general.theme = ThemeMode::Dark;
+        assert_eq!(config.update(user).unwrap(), "config_version = 3\n\n# mine\n[general]\ntheme = \"dark\"\n");
+    }
+}
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod config_migration_tests {
+    use cascolor::color::ColorFormat;
+    use cascolor::config::{upgrade, upgrade_file, version, Config, Severity, Sources, ThemeMode, UpdateChannel, CONFIG_VERSION};
+    use cascolor::display::UiMode;
+    use std::fs;
+    use tempfile::TempDir;
+
+    // A config as written before versioning, when `updates.channel` and
+    // `ui.default_color_format` were free strings
+    const V1: &str = include_str!("fixtures/config/v1.toml");
+    // Typed values, with the history still in the config
+    const V2: &str = include_str!("fixtures/config/v2.toml");
+    const V3: &str = include_str!("fixtures/config/v3.toml");
+    const V1_TABLES_ONLY: &str = include_str!("fixtures/config/v1_tables_only.toml");
+    const V2_TABLES_ONLY: &str = include_str!("fixtures/config/v2_tables_only.toml");
+    const V3_TABLES_ONLY: &str = include_str!("fixtures/config/v3_tables_only.toml");
+
+    #[test]
+    fn test_upgrade_steps() {
+        assert_eq!(version(V1), Some(1));
+        assert_eq!(version(V2), Some(2));
+        assert_eq!(upgrade(V1).unwrap(), V3);
+        assert_eq!(upgrade(V2).unwrap(), V3);
+        assert_eq!(upgrade(V1_TABLES_ONLY).unwrap(), V3_TABLES_ONLY);
+        assert_eq!(upgrade(V2_TABLES_ONLY).unwrap(), V3_TABLES_ONLY);
+
+        let (config, diagnostics) = Config::parse(V3);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        assert_eq!(config.config_version, 3);
+        assert_eq!(config.general.theme, ThemeMode::Dark);
+        assert_eq!(config.updates.channel, UpdateChannel::Daily);
+        assert_eq!(config.ui.default_color_format, ColorFormat::Rgb);
+
+        let (config, _) = Config::parse(V3_TABLES_ONLY);
+        assert_eq!(config.ui.mode, UiMode::Tui);
+        assert_eq!(config.ui.default_color_format, ColorFormat::Hsl);
+    }
+
+    #[test]
+    fn test_current_and_newer_are_left_alone() {
+        assert_eq!(version(V3), Some(CONFIG_VERSION));
+        assert!(upgrade(V3).is_none());
+        assert!(upgrade("[general\n").is_none());
+
+        let newer = "config_version = 99\n\n[general]\ntheme = \"light\"\n";
+        assert!(upgrade(newer).is_none());
+        let (config, diagnostics) = Config::parse(newer);
+        assert_eq!(config.config_version, 99);
+        assert_eq!(config.general.theme, ThemeMode::Light);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].key, "config_version");
+    }
+
+    #[test]
+    fn test_upgrade_file_keeps_backup() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, V1).unwrap();
+
+        let migration = upgrade_file(&path).unwrap().unwrap();
+        assert_eq!(migration.from, 1);
+        assert_eq!(migration.backup, dir.path().join("config.toml.v1.bak"));
+        assert_eq!(fs::read_to_string(&migration.backup).unwrap(), V1);
+        assert_eq!(fs::read_to_string(&path).unwrap(), V3);
+
+        assert!(upgrade_file(&path).unwrap().is_none());
+        assert!(upgrade_file(&dir.path().join("missing.toml")).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_load_upgrades_in_memory() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, V1).unwrap();
+        let sources = Sources { user: Some(path.clone()), ..Sources::default() };
+
+        let layered = Config::load_layered(&sources).unwrap();
+        assert!(layered.problems.is_empty());
+        assert_eq!(layered.config.config_version, CONFIG_VERSION);
+        assert_eq!(layered.config.updates.channel, UpdateChannel::Daily);
+
+        // Reading leaves the file alone; the first save upgrades it
+        assert_eq!(fs::read_to_string(&path).unwrap(), V1);
+        let mut config = layered.config;
+        assert!(config.save().unwrap().unwrap().starts_with("Upgraded"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), V3);
+        // Without a history file to move it to, only the backup keeps the history
+        assert!(fs::read_to_string(dir.path().join("config.toml.v1.bak")).unwrap().contains("color_history"));
+    }
+
+    #[test]
+    fn test_version_field() {
+        let config = Config::default();
+        assert_eq!(config.config_version, CONFIG_VERSION);
+        assert!(Config::default_contents().starts_with(&format!("config_version = {}\n", CONFIG_VERSION)));
+
+        // Unversioned files are version 1
+        assert_eq!(Config::parse("[general]\n").0.config_version, 1);
+
+        let (config, diagnostics) = Config::parse("config_version = \"two\"\n");
+        assert_eq!(config.config_version, 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].message, format!("expected a version from 1 to {}, got \"two\"", CONFIG_VERSION));
+
+        // Version 0 is read as 1 and reported, not migrated from
+        let zero = "config_version = 0\n\n[updates]\nchannel = \"Nightly\"\n";
+        assert_eq!(version(zero), Some(1));
+        assert!(upgrade(zero).unwrap().starts_with("config_version = 3\n"));
+        let (config, diagnostics) = Config::parse(zero);
+        assert_eq!(config.config_version, 1);
+        assert_eq!(diagnostics[0].message, format!("expected a version from 1 to {}, got 0", CONFIG_VERSION));
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, zero).unwrap();
+        let layered = Config::load_layered(&Sources { user: Some(path), ..Sources::default() }).unwrap();
+        assert_eq!(layered.problems.len(), 1);
+        assert_eq!(layered.config.updates.channel, UpdateChannel::Daily);
+    }
+}
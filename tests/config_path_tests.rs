@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod config_path_tests {
+    use cascolor::config::paths::{portable_dir_for, PORTABLE_DIR, PORTABLE_MARKER};
+    use cascolor::config::{Config, Sources, ThemeMode, CONFIG_DIR_ENV};
+    use std::env;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_explicit_file_wins() {
+        let file = Path::new("/media/stick/colors.toml");
+        assert_eq!(Config::config_path(Some(file)).unwrap(), file);
+
+        let sources = Sources::discover(Some(file), &[]);
+        assert_eq!(sources.user.as_deref(), Some(file));
+    }
+
+    #[test]
+    fn test_config_dir_from_environment() {
+        // The only test in this file that touches the variable
+        let dir = TempDir::new().unwrap();
+        env::set_var(CONFIG_DIR_ENV, dir.path());
+        let path = Config::config_path(None);
+        env::set_var(CONFIG_DIR_ENV, "");
+        let fallback = Config::config_path(None);
+        env::remove_var(CONFIG_DIR_ENV);
+
+        assert_eq!(path.unwrap(), dir.path().join("config.toml"));
+        assert_ne!(fallback.unwrap(), dir.path().join("config.toml"));
+    }
+
+    #[test]
+    fn test_portable_marker() {
+        let dir = TempDir::new().unwrap();
+        let exe = dir.path().join("cascolor.exe");
+        assert_eq!(portable_dir_for(&exe), None);
+
+        fs::write(dir.path().join(PORTABLE_MARKER), "").unwrap();
+        assert_eq!(portable_dir_for(&exe), Some(dir.path().join(PORTABLE_DIR)));
+
+        // A directory of that name is not a marker
+        let other = TempDir::new().unwrap();
+        fs::create_dir(other.path().join(PORTABLE_MARKER)).unwrap();
+        assert_eq!(portable_dir_for(&other.path().join("cascolor")), None);
+    }
+
+    #[test]
+    fn test_save_writes_to_loaded_file() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("stick").join("config.toml");
+        let sources = Sources { user: Some(file.clone()), ..Sources::default() };
+
+        let mut config = Config::load_layered(&sources).unwrap().config;
+        config.general.theme = ThemeMode::Light;
+        config.save().unwrap();
+
+        let contents = fs::read_to_string(&file).unwrap();
+        assert!(contents.contains("theme = \"light\""));
+        assert_eq!(Config::load_layered(&sources).unwrap().config.general.theme, ThemeMode::Light);
+    }
+
+    #[test]
+    fn test_cli_overrides_apply_to_chosen_file() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("site.toml");
+        fs::write(&file, "config_version = 3\n\n[general]\ntheme = \"system\"\nhistory_size = 7\n").unwrap();
+
+        let overrides = vec![("general.theme".to_string(), "auto".to_string())];
+        let config = Config::load(Some(&file), &overrides).unwrap();
+        assert_eq!(config.general.theme, ThemeMode::Auto);
+        assert_eq!(config.general.history_size, 7);
+    }
+}
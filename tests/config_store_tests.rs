@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod config_store_tests {
+    use cascolor::config::{modify, store, ConfigError};
+    use std::fs;
+    use std::thread;
+    use std::time::{Duration, Instant};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_store_replaces_atomically() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nested").join("config.toml");
+        store(&path, "[general]\n").unwrap();
+        store(&path, "[ui]\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[ui]\n");
+
+        // No temp files are left behind
+        let names: Vec<String> =
+            fs::read_dir(path.parent().unwrap()).unwrap().map(|e| e.unwrap().file_name().to_string_lossy().into_owned()).collect();
+        assert_eq!(names, ["config.toml"]);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{symlink, PermissionsExt};
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+            let link = dir.path().join("link.toml");
+            symlink(&path, &link).unwrap();
+            store(&link, "[updates]\n").unwrap();
+            assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+            assert_eq!(fs::read_to_string(&path).unwrap(), "[updates]\n");
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn test_lock_is_exclusive() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+
+        // A second writer waits for the first to finish its change
+        let holder = {
+            let path = path.clone();
+            thread::spawn(move || {
+                modify(&path, |contents| {
+                    thread::sleep(Duration::from_millis(200));
+                    Ok(format!("{}# first\n", contents))
+                })
+                .unwrap()
+            })
+        };
+        thread::sleep(Duration::from_millis(50));
+        let started = Instant::now();
+        modify(&path, |contents| Ok(format!("{}# second\n", contents))).unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        holder.join().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# first\n# second\n");
+    }
+
+    #[test]
+    fn test_concurrent_modify_loses_nothing() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                thread::spawn(move || {
+                    for j in 0..5 {
+                        modify(&path, |contents| Ok(format!("{}# {} {}\n", contents, i, j))).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 40);
+
+        let error = modify(&path, |_| Err(ConfigError::UnknownKey("nope".to_string()))).unwrap_err();
+        assert!(matches!(error, ConfigError::UnknownKey(_)));
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 40);
+    }
+}
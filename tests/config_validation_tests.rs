@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod config_validation_tests {
+    use cascolor::color::ColorFormat;
+    use cascolor::config::{Config, Severity, ThemeMode, UpdateChannel};
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_valid_config_has_no_diagnostics() {
+        let (config, diagnostics) = Config::parse(
+            r##"
+[general]
+theme = "system"
+history_size = 1000
+
+[updates]
+channel = "daily"
+
+[ui]
+default_color_format = "oklch"
+"##,
+        );
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        assert_eq!(config.general.theme, ThemeMode::System);
+        assert_eq!(config.general.history_size, 1000);
+        assert_eq!(config.updates.channel, UpdateChannel::Daily);
+        assert_eq!(config.ui.default_color_format, ColorFormat::Oklch);
+    }
+
+    #[test]
+    fn test_invalid_values_fall_back_to_defaults() {
+        let contents = "[general]\ntheme = \"light\"\nhistory_size = 5000\n\n[updates]\nchannel = \"nightly\"\ncheck_on_startup = \"no\"\n";
+        let (config, diagnostics) = Config::parse(contents);
+
+        // Only the invalid keys are dropped; their neighbours are kept
+        assert_eq!(config.general.theme, ThemeMode::Light);
+        assert_eq!(config.general.history_size, 20);
+        assert_eq!(config.updates.channel, UpdateChannel::Stable);
+        assert!(config.updates.check_on_startup);
+
+        let keys: Vec<&str> = diagnostics.iter().map(|d| d.key.as_str()).collect();
+        assert_eq!(keys, ["general.history_size", "updates.channel", "updates.check_on_startup"]);
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+        assert_eq!(diagnostics[0].message, "expected an integer from 0 to 1000, got 5000");
+        assert_eq!(diagnostics[1].message, "expected one of stable, beta, daily, got \"nightly\"");
+    }
+
+    #[test]
+    fn test_positions() {
+        let contents = "[ui]\nmode = \"tui\"\ndefault_color_format = \"rgba\"\n";
+        let (_, diagnostics) = Config::parse(contents);
+        assert_eq!(diagnostics.len(), 1);
+
+        let diagnostic = &diagnostics[0];
+        assert_eq!((diagnostic.line, diagnostic.column), (3, 24));
+        assert_eq!(&contents[diagnostic.span.clone()], "\"rgba\"");
+        assert!(diagnostic.to_string().starts_with("3:24: error: ui.default_color_format: expected one of hex, rgb"));
+    }
+
+    #[test]
+    fn test_history_and_unknown_keys() {
+        let contents = "color_history = [\"#000000\", \"nope\", 7, \"#ffffff\"]\n\n[general]\ncolour = true\n\n[extras]\n";
+        let (_, diagnostics) = Config::parse(contents);
+
+        let found: Vec<(Severity, &str, usize)> =
+            diagnostics.iter().map(|d| (d.severity, d.key.as_str(), d.line)).collect();
+        assert_eq!(
+            found,
+            [
+                (Severity::Warning, "color_history", 1),
+                (Severity::Warning, "general.colour", 4),
+                (Severity::Warning, "extras", 6),
+            ]
+        );
+        // The history moved to its own file in config version 3
+        assert!(diagnostics[0].message.contains("cascolor history import"));
+        assert_eq!(diagnostics[1].message, "unknown key, ignored");
+    }
+
+    #[test]
+    fn test_syntax_error_and_positions() {
+        let (config, diagnostics) = Config::parse("[general\ntheme = \"light\"\n");
+        assert_eq!(config.general.theme, ThemeMode::Dark);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (1, 9));
+        assert!(diagnostics[0].key.is_empty());
+
+        let (config, diagnostics) = Config::parse("config_version = 3\n\n[general]\nhistory_size = -1\n");
+        assert_eq!(config.general.history_size, 20);
+        assert_eq!(diagnostics[0].line, 4);
+    }
+}
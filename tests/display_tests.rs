@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod display_tests {
+    use cascolor::display::{detect_mode, Environment, UiMode};
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)], stdout_is_tty: bool) -> Environment {
+        Environment {
+            vars: vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<_, _>>(),
+            stdout_is_tty,
+            native_display: false,
+        }
+    }
+
+    fn detect(environment: &Environment, display_works: bool) -> UiMode {
+        detect_mode(UiMode::Auto, environment, |_| display_works)
+    }
+
+    #[test]
+    fn test_forced_modes_skip_detection() {
+        let environment = env(&[], false);
+        for mode in [UiMode::Gui, UiMode::Tui, UiMode::Headless] {
+            assert_eq!(detect_mode(mode, &environment, |_| panic!("probed")), mode);
+        }
+    }
+
+    #[test]
+    fn test_local_desktop_sessions() {
+        assert_eq!(detect(&env(&[("DISPLAY", ":0")], true), true), UiMode::Gui);
+        assert_eq!(detect(&env(&[("WAYLAND_DISPLAY", "wayland-0")], true), true), UiMode::Gui);
+        assert_eq!(
+            detect(&env(&[("XDG_SESSION_TYPE", "wayland"), ("WAYLAND_DISPLAY", "wayland-1")], false), true),
+            UiMode::Gui
+        );
+    }
+
+    #[test]
+    fn test_empty_display_is_no_display() {
+        assert_eq!(detect(&env(&[("DISPLAY", ""), ("WAYLAND_DISPLAY", "")], true), true), UiMode::Tui);
+    }
+
+    #[test]
+    fn test_failed_probe_falls_back() {
+        assert_eq!(detect(&env(&[("DISPLAY", ":0")], true), false), UiMode::Tui);
+        assert_eq!(detect(&env(&[("DISPLAY", ":0")], false), false), UiMode::Headless);
+    }
+
+    #[test]
+    fn test_ssh_sessions() {
+        let plain = env(&[("SSH_CONNECTION", "10.0.0.2 5000 10.0.0.1 22")], true);
+        assert_eq!(detect(&plain, true), UiMode::Tui);
+
+        let forwarded = env(&[("SSH_CONNECTION", "10.0.0.2 5000 10.0.0.1 22"), ("DISPLAY", "localhost:10.0")], true);
+        assert_eq!(detect(&forwarded, true), UiMode::Gui);
+        assert_eq!(detect(&forwarded, false), UiMode::Tui);
+    }
+
+    #[test]
+    fn test_tty_session_ignores_stale_display() {
+        let console = env(&[("XDG_SESSION_TYPE", "tty"), ("DISPLAY", ":0")], true);
+        assert_eq!(detect(&console, true), UiMode::Tui);
+
+        let forwarded = env(&[("XDG_SESSION_TYPE", "tty"), ("SSH_TTY", "/dev/pts/1"), ("DISPLAY", "localhost:10.0")], true);
+        assert_eq!(detect(&forwarded, true), UiMode::Gui);
+    }
+
+    #[test]
+    fn test_no_display_no_tty_is_headless() {
+        assert_eq!(detect(&env(&[], false), true), UiMode::Headless);
+    }
+
+    #[test]
+    fn test_native_display() {
+        let mut environment = env(&[], false);
+        environment.native_display = true;
+        assert_eq!(detect(&environment, true), UiMode::Gui);
+    }
+
+    #[test]
+    fn test_mode_parsing() {
+        assert_eq!("TUI".parse::<UiMode>().unwrap(), UiMode::Tui);
+        assert!("window".parse::<UiMode>().is_err());
+        let config: cascolor::config::Config = toml::from_str("[ui]\nmode = \"headless\"").unwrap();
+        assert_eq!(config.ui.mode, UiMode::Headless);
+    }
+}
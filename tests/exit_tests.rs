@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod exit_tests {
+    use cascolor::batch::BatchError;
+    use cascolor::clipboard::ClipboardError;
+    use cascolor::config::ConfigError;
+    use cascolor::exit::{CliError, ErrorFormat, ExitCode};
+    use cascolor::update::UpdateError;
+
+    #[test]
+    fn test_exit_codes_are_stable() {
+        let table = [
+            (ExitCode::Success, 0, "success"),
+            (ExitCode::Failure, 1, "failure"),
+            (ExitCode::Usage, 2, "usage"),
+            (ExitCode::Parse, 3, "parse"),
+            (ExitCode::Config, 4, "config"),
+            (ExitCode::ClipboardUnavailable, 5, "clipboard-unavailable"),
+            (ExitCode::Network, 6, "network"),
+            (ExitCode::Terminal, 7, "terminal"),
+            (ExitCode::UpdateAvailable, 8, "update-available"),
+        ];
+
+        for (exit_code, code, key) in table {
+            assert_eq!(exit_code.code(), code);
+            assert_eq!(exit_code.key(), key);
+        }
+    }
+
+    #[test]
+    fn test_library_errors_map_to_exit_codes() {
+        assert_eq!(ExitCode::from(&ConfigError::InvalidPath), ExitCode::Config);
+        assert_eq!(ExitCode::from(&ClipboardError::NotAvailable), ExitCode::ClipboardUnavailable);
+        assert_eq!(ExitCode::from(&UpdateError::NetworkError("timeout".into())), ExitCode::Network);
+        assert_eq!(ExitCode::from(&UpdateError::InvalidChannel("nightly".into())), ExitCode::Usage);
+        // An error is never reported with a zero exit code
+        assert_eq!(CliError::from(UpdateError::NotFound).exit_code, ExitCode::Failure);
+        assert_eq!(
+            ExitCode::from(&BatchError::InvalidColor { line: 2, message: "bad".into() }),
+            ExitCode::Parse
+        );
+    }
+
+    #[test]
+    fn test_cli_error_keeps_message() {
+        let error = CliError::from(ClipboardError::InitFailed("no display".into()));
+        assert_eq!(error.exit_code, ExitCode::ClipboardUnavailable);
+        assert_eq!(error.to_string(), "Failed to initialize clipboard: no display");
+    }
+
+    #[test]
+    fn test_json_report() {
+        let report = CliError::parse("Unrecognized color: nope").report();
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["schema_version"], 1);
+        assert_eq!(json["code"], 3);
+        assert_eq!(json["kind"], "parse");
+        assert_eq!(json["message"], "Unrecognized color: nope");
+    }
+
+    #[test]
+    fn test_error_format_from_args() {
+        assert_eq!(ErrorFormat::from_args(["cascolor", "info", "x"]), ErrorFormat::Text);
+        assert_eq!(ErrorFormat::from_args(["cascolor", "--error-format", "json"]), ErrorFormat::Json);
+        assert_eq!(ErrorFormat::from_args(["cascolor", "--error-format=JSON", "bogus"]), ErrorFormat::Json);
+        assert_eq!(ErrorFormat::from_args(["cascolor", "--error-format", "yaml"]), ErrorFormat::Text);
+    }
+}
@@ -0,0 +1,98 @@
+mod common;
+
+#[cfg(test)]
+mod export_tests {
+    use cascolor::color::{CasColor, ColorFormat};
+    use cascolor::export::{export, history_palette, variable_names, ExportFormat, ExportOptions, NameCase};
+    use cascolor::palette::Palette;
+    use cascolor::history::{HistoryEntry, Source};
+    use crate::common::brand;
+
+    #[test]
+    fn test_naming_schemes() {
+        let mut palette = brand();
+        palette.push(CasColor::from_rgb(0, 0, 0), Some("deepNavy".to_string()));
+        palette.push(CasColor::from_rgb(9, 9, 9), None);
+        palette.push(CasColor::from_rgb(1, 1, 1), Some("500".to_string()));
+
+        let mut options = ExportOptions::new(ExportFormat::Css);
+        assert_eq!(
+            variable_names(&palette, &options),
+            ["primary", "deep-navy", "deep-navy-2", "color-4", "color-500"]
+        );
+
+        options.case = NameCase::Camel;
+        options.prefix = "brand".to_string();
+        assert_eq!(
+            variable_names(&palette, &options),
+            ["brandPrimary", "brandDeepNavy", "brandDeepNavy2", "brandColor4", "brand500"]
+        );
+
+        options.case = NameCase::Snake;
+        assert_eq!(variable_names(&palette, &options)[1], "brand_deep_navy");
+    }
+
+    #[test]
+    fn test_stylesheet_exports() {
+        let mut options = ExportOptions::new(ExportFormat::Css);
+        options.prefix = "brand".to_string();
+        assert_eq!(
+            export(&brand(), &options),
+            ":root {\n  --brand-primary: #FF5733;\n  --brand-deep-navy: #123456;\n}\n"
+        );
+
+        options.format = ExportFormat::Scss;
+        options.color_format = ColorFormat::Rgb;
+        assert_eq!(
+            export(&brand(), &options),
+            "$brand-primary: rgb(255, 87, 51);\n$brand-deep-navy: rgb(18, 52, 86);\n"
+        );
+
+        options.format = ExportFormat::Less;
+        assert!(export(&brand(), &options).starts_with("@brand-primary: rgb(255, 87, 51);\n"));
+    }
+
+    #[test]
+    fn test_json_export_is_valid_and_ordered() {
+        let output = export(&brand(), &ExportOptions::new(ExportFormat::Json));
+        assert_eq!(output, "{\n  \"primary\": \"#FF5733\",\n  \"deep-navy\": \"#123456\"\n}\n");
+
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value["deep-navy"], "#123456");
+        assert_eq!(export(&Palette::default(), &ExportOptions::new(ExportFormat::Json)), "{}\n");
+    }
+
+    #[test]
+    fn test_javascript_exports() {
+        let tailwind = export(&brand(), &ExportOptions::new(ExportFormat::Tailwind));
+        assert!(tailwind.starts_with("/** @type {import('tailwindcss').Config} */\nmodule.exports = {"));
+        assert!(tailwind.contains("      colors: {\n        primary: '#FF5733',\n        'deep-navy': '#123456',\n      },\n"));
+
+        let tailwind_ts = export(&brand(), &ExportOptions::new(ExportFormat::TailwindTs));
+        assert!(tailwind_ts.ends_with("} satisfies Partial<Config>;\n"));
+
+        let typescript = export(&brand(), &ExportOptions::new(ExportFormat::Typescript));
+        assert_eq!(
+            typescript,
+            "export const brandColors = {\n  primary: '#FF5733',\n  deepNavy: '#123456',\n} as const;\n\n\
+             export type BrandColorsName = keyof typeof brandColors;\n"
+        );
+    }
+
+    #[test]
+    fn test_history_palette_skips_invalid_entries() {
+        let entry = |color: &str| HistoryEntry {
+            color: color.to_string(),
+            time: "2026-01-01T00:00:00Z".to_string(),
+            source: Source::Input,
+            format: None,
+            pinned: false,
+        };
+        let history = [entry("#ff0000"), entry("not a color"), entry("#0000FF")];
+        let palette = history_palette(&history);
+
+        assert_eq!(palette.len(), 2);
+        assert_eq!(palette.entries[1].color, CasColor::from_rgb(0, 0, 255));
+        assert!(export(&palette, &ExportOptions::new(ExportFormat::Typescript)).starts_with("export const history = {"));
+    }
+}
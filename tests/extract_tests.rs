@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod extract_tests {
+    use cascolor::color::CasColor;
+    use cascolor::export::{ExportFormat, PaletteOutput};
+    use cascolor::extract::{extract, is_image, swatch_palette, ExtractMethod, ExtractOptions, Swatch};
+    use cascolor::palette::PaletteFormat;
+    use image::{Rgba, RgbaImage};
+    use std::path::Path;
+
+    // 100 pixels wide: 50 red, 25 blue, 15 green and 10 white columns,
+    // plus a fully transparent bottom row of magenta
+    fn stripes() -> RgbaImage {
+        RgbaImage::from_fn(100, 11, |x, y| match (x, y) {
+            (_, 10) => Rgba([255, 0, 255, 0]),
+            (0..=49, _) => Rgba([220, 30, 30, 255]),
+            (50..=74, _) => Rgba([20, 40, 200, 255]),
+            (75..=89, _) => Rgba([30, 160, 60, 255]),
+            _ => Rgba([250, 250, 250, 255]),
+        })
+    }
+
+    fn hexes(swatches: &[Swatch]) -> Vec<String> {
+        swatches.iter().map(|s| s.color.to_hex()).collect()
+    }
+
+    #[test]
+    fn test_every_method_finds_the_stripes() {
+        for method in ExtractMethod::ALL {
+            let options = ExtractOptions { count: 4, method, ..ExtractOptions::default() };
+            let swatches = extract(&stripes(), &options).unwrap();
+            assert_eq!(hexes(&swatches), ["#DC1E1E", "#1428C8", "#1EA03C", "#FAFAFA"], "{}", method);
+
+            let shares: Vec<f32> = swatches.iter().map(|s| s.share).collect();
+            for (share, expected) in shares.iter().zip([0.5, 0.25, 0.15, 0.1]) {
+                assert!((share - expected).abs() < 1e-4, "{}: {:?}", method, shares);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fewer_colors_than_requested() {
+        let options = ExtractOptions { count: 2, ..ExtractOptions::default() };
+        let swatches = extract(&stripes(), &options).unwrap();
+        // Green and white join the red cluster, which keeps the combined share
+        assert_eq!(swatches.len(), 2);
+        assert_eq!(swatches[1].color, CasColor::from_rgb(20, 40, 200));
+        assert!((swatches[0].share - 0.75).abs() < 1e-4);
+
+        for method in ExtractMethod::ALL {
+            let options = ExtractOptions { count: 16, method, ..ExtractOptions::default() };
+            assert_eq!(extract(&stripes(), &options).unwrap().len(), 4, "{}", method);
+        }
+    }
+
+    #[test]
+    fn test_transparent_pixels() {
+        let options = ExtractOptions { count: 5, min_alpha: 0, ..ExtractOptions::default() };
+        let swatches = extract(&stripes(), &options).unwrap();
+        assert_eq!(swatches.len(), 5);
+        assert!(swatches.iter().any(|s| s.color == CasColor::from_rgb(255, 0, 255)));
+
+        let clear = RgbaImage::from_pixel(4, 4, Rgba([0, 0, 0, 10]));
+        let error = extract(&clear, &ExtractOptions::default()).unwrap_err();
+        assert_eq!(error.to_string(), "The image has no pixels with an alpha of at least 128");
+    }
+
+    #[test]
+    fn test_near_duplicates_merge() {
+        // Two reds a shade apart land in different histogram bins
+        let image = RgbaImage::from_fn(10, 1, |x, _| {
+            if x < 6 {
+                Rgba([200, 40, 40, 255])
+            } else {
+                Rgba([208, 40, 40, 255])
+            }
+        });
+
+        let merged = extract(&image, &ExtractOptions::default()).unwrap();
+        assert_eq!(merged.len(), 1);
+        assert!((merged[0].share - 1.0).abs() < 1e-4);
+
+        let options = ExtractOptions { min_distance: 0.0, ..ExtractOptions::default() };
+        let kept = extract(&image, &options).unwrap();
+        assert_eq!(hexes(&kept), ["#C82828", "#D02828"]);
+    }
+
+    #[test]
+    fn test_palette_output() {
+        let swatches = extract(&stripes(), &ExtractOptions::default()).unwrap();
+        let palette = swatch_palette("stripes", &swatches);
+        assert_eq!(palette.name.as_deref(), Some("stripes"));
+        assert_eq!(palette.entries[0].color, CasColor::from_rgb(220, 30, 30));
+
+        assert_eq!("gpl".parse::<PaletteOutput>().unwrap(), PaletteOutput::Palette(PaletteFormat::Gpl));
+        assert_eq!("tailwind".parse::<PaletteOutput>().unwrap(), PaletteOutput::Code(ExportFormat::Tailwind));
+        assert!("png".parse::<PaletteOutput>().is_err());
+        assert!(PaletteOutput::keys().contains(&"tokens"));
+
+        // Output files pick palette formats first, then code formats
+        let from_path = |name: &str| PaletteOutput::from_path(Path::new(name)).ok();
+        assert_eq!(from_path("brand.tokens.json"), Some(PaletteOutput::Palette(PaletteFormat::Tokens)));
+        assert_eq!(from_path("colors.css"), Some(PaletteOutput::Code(ExportFormat::Css)));
+        assert_eq!(from_path("tailwind.colors.ts"), Some(PaletteOutput::Code(ExportFormat::TailwindTs)));
+        assert_eq!(from_path("Colors.kt"), Some(PaletteOutput::Code(ExportFormat::Compose)));
+        assert_eq!(from_path("colors.png"), None);
+
+        assert!(is_image(Path::new("photo.JPG")));
+        assert!(!is_image(Path::new("colors.gpl")));
+    }
+}
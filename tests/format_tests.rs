@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod format_tests {
+    use cascolor::color::{parse_color, CasColor, ColorFormat, ColorReport, SCHEMA_VERSION};
+
+    #[test]
+    fn test_every_format_round_trips() {
+        let color = CasColor::from_rgb(255, 87, 51);
+        for format in ColorFormat::ALL {
+            let text = color.format(format);
+            let (r, g, b) = parse_color(&text).unwrap().to_rgb();
+            // HSL/HSV/CMYK are printed with whole-number precision
+            assert!(r.abs_diff(255) <= 1 && g.abs_diff(87) <= 1 && b.abs_diff(51) <= 1, "round trip through {}", text);
+        }
+    }
+
+    #[test]
+    fn test_parse_modern_css_syntax() {
+        assert_eq!(parse_color("rgb(255 87 51 / 50%)").unwrap().to_hex(), "#FF5733");
+        assert_eq!(parse_color("rgb(100%, 0%, 0%)").unwrap().to_hex(), "#FF0000");
+        assert_eq!(parse_color("hsl(120deg 100% 50%)").unwrap().to_hex(), "#00FF00");
+    }
+
+    #[test]
+    fn test_parse_named_and_bare_hex() {
+        assert_eq!(parse_color("tomato").unwrap().to_hex(), "#FF6347");
+        assert_eq!(parse_color("RebeccaPurple").unwrap().to_hex(), "#663399");
+        assert_eq!(parse_color("F53").unwrap().to_hex(), "#FF5533");
+        assert!(parse_color("notacolor").is_err());
+        assert!(parse_color("rgb(1, 2)").is_err());
+    }
+
+    #[test]
+    fn test_format_keys_parse() {
+        for format in ColorFormat::ALL {
+            assert_eq!(format.key().parse::<ColorFormat>().unwrap(), format);
+        }
+        assert!("rgba".parse::<ColorFormat>().is_err());
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        let black = CasColor::from_rgb(0, 0, 0);
+        let white = CasColor::from_rgb(255, 255, 255);
+        assert!((black.contrast_ratio(&white) - 21.0).abs() < 0.01);
+        assert!((white.contrast_ratio(&white) - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_report_json_schema() {
+        let color = parse_color("#ff5733").unwrap();
+        let report = ColorReport::new("#ff5733", &color);
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["schema_version"], SCHEMA_VERSION);
+        assert_eq!(json["hex"], "#FF5733");
+        assert_eq!(json["rgb"]["g"], 87);
+        assert_eq!(json["nearest_name"]["name"], "tomato");
+        assert!((json["contrast"]["white"].as_f64().unwrap() - 3.15).abs() < 1e-4);
+        assert!(json["formats"]["oklch"].as_str().unwrap().starts_with("oklch("));
+    }
+}
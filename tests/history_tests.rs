@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod history_tests {
+    use cascolor::color::{CasColor, ColorFormat};
+    use cascolor::config::{Config, Sources, CONFIG_VERSION};
+    use cascolor::history::{self, History, HistoryEntry, Source};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn entry(hex: &str, source: Source) -> HistoryEntry {
+        HistoryEntry::new(&CasColor::from_hex(hex).unwrap(), source, Some(ColorFormat::Hex))
+    }
+
+    fn colors(history: &History) -> Vec<String> {
+        history.load().unwrap().into_iter().map(|entry| entry.color).collect()
+    }
+
+    #[test]
+    fn test_record_moves_repeats_to_front_and_trims() {
+        let dir = TempDir::new().unwrap();
+        let history = History::new(dir.path().join("data").join("history.jsonl"), 3);
+        assert!(history.load().unwrap().is_empty());
+
+        for hex in ["#111111", "#222222", "#333333", "#111111"] {
+            history.record(entry(hex, Source::Palette)).unwrap();
+        }
+        assert_eq!(colors(&history), ["#111111", "#333333", "#222222"]);
+
+        // The oldest unpinned color goes first; pinned ones don't count
+        assert!(history.set_pinned(&CasColor::from_hex("#222222").unwrap(), true).unwrap());
+        history.record(entry("#444444", Source::Gradient)).unwrap();
+        history.record(entry("#555555", Source::Input)).unwrap();
+        assert_eq!(colors(&history), ["#555555", "#444444", "#111111", "#222222"]);
+
+        // Picking a pinned color again keeps it pinned
+        history.record(entry("#222222", Source::Eyedropper)).unwrap();
+        let newest = &history.load().unwrap()[0];
+        assert_eq!((newest.color.as_str(), newest.source, newest.pinned), ("#222222", Source::Eyedropper, true));
+    }
+
+    #[test]
+    fn test_pin_and_clear() {
+        let dir = TempDir::new().unwrap();
+        let history = History::new(dir.path().join("history.jsonl"), 20);
+        for hex in ["#111111", "#222222", "#333333"] {
+            history.record(entry(hex, Source::Cli)).unwrap();
+        }
+
+        let gray = CasColor::from_hex("#222222").unwrap();
+        assert!(history.set_pinned(&gray, true).unwrap());
+        assert!(!history.set_pinned(&CasColor::from_hex("#ABCDEF").unwrap(), true).unwrap());
+
+        assert_eq!(history.clear(false).unwrap(), 2);
+        assert_eq!(colors(&history), ["#222222"]);
+        assert!(history.set_pinned(&gray, false).unwrap());
+        history.record(entry("#333333", Source::Cli)).unwrap();
+        assert_eq!(history.clear(true).unwrap(), 2);
+        assert!(history.load().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_file_format() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("history.jsonl");
+        let history = History::new(path.clone(), 20);
+        history.record(HistoryEntry::new(&CasColor::from_rgb(255, 87, 51), Source::Input, Some(ColorFormat::Rgb))).unwrap();
+        history.record(HistoryEntry::new(&CasColor::from_rgb(0, 0, 0), Source::Input, None)).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = contents.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["color"], "#FF5733");
+        assert_eq!(lines[0]["source"], "input");
+        assert_eq!(lines[0]["format"], "rgb");
+        assert_eq!(lines[0]["pinned"], false);
+        assert!(lines[0]["time"].as_str().unwrap().ends_with('Z'));
+        assert!(lines[1]["format"].is_null());
+
+        // Lines that don't parse are skipped rather than losing the history
+        fs::write(&path, format!("{}{{\"color\": \"#12\nnot json\n", contents)).unwrap();
+        assert_eq!(colors(&history), ["#000000", "#FF5733"]);
+
+        // ...and are written back untouched, like an entry from a newer build
+        let newer = r##"{"color":"#ABCDEF","time":"2030-01-01T00:00:00Z","source":"scanner","format":null}"##;
+        fs::write(&path, format!("not json\n{}\n{}", newer, contents)).unwrap();
+        history.record(entry("#123456", Source::Cli)).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        assert!(written.starts_with(&format!("not json\n{}\n", newer)));
+        assert_eq!(colors(&history), ["#123456", "#000000", "#FF5733"]);
+        assert_eq!("Eyedropper".parse::<Source>(), Ok(Source::Eyedropper));
+        assert!("mouse".parse::<Source>().is_err());
+    }
+
+    #[test]
+    fn test_upgrade_moves_legacy_history() {
+        let dir = TempDir::new().unwrap();
+        let user = dir.path().join("config.toml");
+        let path = dir.path().join("history.jsonl");
+        fs::write(&user, "config_version = 2\ncolor_history = [\"#FF5733\", \"nope\", \"#33FF57\"]\n").unwrap();
+
+        let history = History::new(path.clone(), 20);
+        history.record(entry("#33ff57", Source::Cli)).unwrap();
+
+        // Loading reads the old file as the current version without writing it
+        let sources = Sources { user: Some(user.clone()), history: Some(path.clone()), ..Sources::default() };
+        let mut config = Config::load_layered(&sources).unwrap().config;
+        assert_eq!(config.config_version, CONFIG_VERSION);
+        assert!(fs::read_to_string(&user).unwrap().contains("color_history"));
+        assert_eq!(history.load().unwrap().len(), 1);
+
+        // Saving upgrades it and moves the history first
+        let note = config.save().unwrap().unwrap();
+        assert!(note.ends_with(&format!("moved 1 history colors to {}", path.display())), "{}", note);
+        assert!(!fs::read_to_string(&user).unwrap().contains("color_history"));
+
+        // Imported colors go behind the existing ones, in their old order
+        let entries = history.load().unwrap();
+        let found: Vec<(&str, Source)> = entries.iter().map(|entry| (entry.color.as_str(), entry.source)).collect();
+        assert_eq!(found, [("#33FF57", Source::Cli), ("#FF5733", Source::Import)]);
+
+        // Importing the backup again adds nothing
+        let backup = dir.path().join("config.toml.v2.bak");
+        assert_eq!(history::import_legacy(&backup, &path).unwrap(), 0);
+        assert_eq!(config.save().unwrap(), None);
+    }
+
+    #[test]
+    fn test_input_format_and_legacy_colors() {
+        assert_eq!(history::input_format("#ff5733"), Some(ColorFormat::Hex));
+        assert_eq!(history::input_format(" rgb(255, 87, 51) "), Some(ColorFormat::Rgb));
+        assert_eq!(history::input_format("oklch(0.7 0.1 40)"), Some(ColorFormat::Oklch));
+        assert_eq!(history::input_format("tomato"), None);
+        assert_eq!(history::input_format("#fff #000"), None);
+
+        let legacy = history::legacy_colors("color_history = [\"#000000\", 7, \"tomato\"]\n");
+        assert_eq!(legacy, [CasColor::from_rgb(0, 0, 0), CasColor::from_rgb(255, 99, 71)]);
+        assert!(history::legacy_colors("[general]\n").is_empty());
+    }
+}
@@ -3,18 +3,20 @@
 
 #[cfg(test)]
 mod integration_tests {
-    use cascolor::color::Color;
-    use cascolor::config::Config;
+    use cascolor::color::{CasColor, ColorFormat};
+    use cascolor::config::{Config, ThemeMode, UpdateChannel};
+    use cascolor::history::{History, HistoryEntry, Source};
+    use tempfile::TempDir;
 
     #[test]
     fn test_color_workflow() {
         // Simulate a typical user workflow
-        let color = Color::from_hex("#FF5733").unwrap();
+        let color = CasColor::from_hex("#FF5733").unwrap();
         
         // User can convert to different formats
         let hex = color.to_hex();
-        let rgb = color.to_rgb_string();
-        let hsl = color.to_hsl_string();
+        let rgb = color.format(ColorFormat::Rgb);
+        let hsl = color.format(ColorFormat::Hsl);
         
         assert_eq!(hex, "#FF5733");
         assert!(rgb.contains("255"));
@@ -23,65 +25,60 @@ mod integration_tests {
 
     #[test]
     fn test_config_and_history() {
-        let mut config = Config::default();
+        let config = Config::default();
+        let dir = TempDir::new().unwrap();
+        let history = History::new(dir.path().join("history.jsonl"), config.general.history_size);
         
         // Simulate adding colors to history
-        config.color_history.push("#FF5733".to_string());
-        config.color_history.push("#33FF57".to_string());
+        for hex in ["#FF5733", "#33FF57"] {
+            history.record(HistoryEntry::new(&CasColor::from_hex(hex).unwrap(), Source::Input, None)).unwrap();
+        }
         
-        assert_eq!(config.color_history.len(), 2);
+        let colors: Vec<String> = history.load().unwrap().into_iter().map(|entry| entry.color).collect();
+        assert_eq!(colors, ["#33FF57", "#FF5733"]);
         
         // Verify history doesn't exceed limit
         for i in 0..25 {
-            config.color_history.push(format!("#00000{:01X}", i % 16));
+            let color = CasColor::from_rgb(0, 0, i);
+            history.record(HistoryEntry::new(&color, Source::Palette, Some(ColorFormat::Hex))).unwrap();
         }
         
-        config.color_history.truncate(config.general.history_size);
-        assert_eq!(config.color_history.len(), 20);
+        assert_eq!(history.load().unwrap().len(), 20);
     }
 
     #[test]
     fn test_color_picker_simulation() {
         // Simulate picking a color and converting it
-        let picked_color = Color::from_rgb(128, 64, 192);
-        
-        // Get all formats
-        let formats = picked_color.to_all_formats();
+        let picked_color = CasColor::from_rgb(128, 64, 192);
         
         // Verify all formats are generated
-        assert!(!formats.hex.is_empty());
-        assert!(!formats.rgb.is_empty());
-        assert!(!formats.hsl.is_empty());
-        assert!(!formats.hsv.is_empty());
-        assert!(!formats.cmyk.is_empty());
-        assert!(!formats.lab.is_empty());
-        assert!(!formats.lch.is_empty());
-        assert!(!formats.oklab.is_empty());
-        assert!(!formats.oklch.is_empty());
+        for format in ColorFormat::ALL {
+            assert!(!picked_color.format(format).is_empty(), "{:?}", format);
+        }
     }
 
     #[test]
     fn test_theme_switching() {
         let mut config = Config::default();
-        assert_eq!(config.general.theme, "dark");
+        assert_eq!(config.general.theme, ThemeMode::Dark);
         
         // Simulate theme toggle
-        config.general.theme = "light".to_string();
-        assert_eq!(config.general.theme, "light");
+        config.general.theme = ThemeMode::Light;
+        assert_eq!(config.general.theme, ThemeMode::Light);
         
-        config.general.theme = "dark".to_string();
-        assert_eq!(config.general.theme, "dark");
+        config.general.theme = ThemeMode::Dark;
+        assert_eq!(config.general.theme, ThemeMode::Dark);
     }
 
     #[test]
     fn test_update_channel_switching() {
         let mut config = Config::default();
-        assert_eq!(config.updates.channel, "stable");
+        assert_eq!(config.updates.channel, UpdateChannel::Stable);
         
-        config.updates.channel = "beta".to_string();
-        assert_eq!(config.updates.channel, "beta");
+        config.updates.channel = UpdateChannel::Beta;
+        assert_eq!(config.updates.channel, UpdateChannel::Beta);
         
-        config.updates.channel = "daily".to_string();
-        assert_eq!(config.updates.channel, "daily");
+        config.updates.channel = UpdateChannel::Daily;
+        assert_eq!(config.updates.channel, UpdateChannel::Daily);
     }
 }
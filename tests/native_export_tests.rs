@@ -0,0 +1,128 @@
+mod common;
+
+#[cfg(test)]
+mod native_export_tests {
+    use cascolor::color::CasColor;
+    use cascolor::export::{export, save, variable_names, ExportFormat, ExportOptions, NameCase};
+    use cascolor::palette::Palette;
+    use std::fs;
+    use crate::common::brand;
+
+    #[test]
+    fn test_android_colors_xml() {
+        let output = export(&brand(), &ExportOptions::new(ExportFormat::Android));
+
+        assert_eq!(
+            output,
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n    <color name=\"primary\">#FF5733</color>\n    <color name=\"deep_navy\">#123456</color>\n</resources>\n"
+        );
+    }
+
+    #[test]
+    fn test_compose_and_flutter() {
+        let compose = export(&brand(), &ExportOptions::new(ExportFormat::Compose));
+        assert!(compose.starts_with("import androidx.compose.ui.graphics.Color\n\nobject BrandColors {\n"));
+        assert!(compose.contains("    val DeepNavy = Color(0xFF123456)\n"));
+
+        let flutter = export(&brand(), &ExportOptions::new(ExportFormat::Flutter));
+        assert!(flutter.contains("class BrandColors {\n  BrandColors._();\n"));
+        assert!(flutter.contains("  static const Color primary = Color(0xFFFF5733);\n"));
+    }
+
+    #[test]
+    fn test_swiftui() {
+        let output = export(&brand(), &ExportOptions::new(ExportFormat::SwiftUi));
+
+        assert!(output.starts_with("import SwiftUI\n\nenum BrandColors {\n"));
+        assert!(output.contains("    static let primary = Color(red: 1.000, green: 0.341, blue: 0.200)\n"));
+        assert!(output.contains("    static let deepNavy = Color(red: 0.071, green: 0.204, blue: 0.337)\n"));
+    }
+
+    fn reserved() -> Palette {
+        let mut palette = Palette::new("Reserved");
+        for name in ["default", "class", "object", "500"] {
+            palette.push(CasColor::from_rgb(0, 0, 0), Some(name.to_string()));
+        }
+        palette
+    }
+
+    #[test]
+    fn test_compose_escapes_keywords() {
+        let mut options = ExportOptions::new(ExportFormat::Compose);
+        options.case = NameCase::Camel;
+        let output = export(&reserved(), &options);
+        assert!(output.contains("    val default = Color(0xFF000000)\n"));
+        assert!(output.contains("    val `class` = Color(0xFF000000)\n"));
+        assert!(output.contains("    val `object` = Color(0xFF000000)\n"));
+        assert!(output.contains("    val color500 = Color(0xFF000000)\n"));
+
+        // A prefix that starts with a digit gets the same treatment
+        options.prefix = "2x".to_string();
+        assert!(export(&reserved(), &options).contains("    val color2xDefault = "));
+    }
+
+    #[test]
+    fn test_swiftui_escapes_keywords() {
+        let output = export(&reserved(), &ExportOptions::new(ExportFormat::SwiftUi));
+        assert!(output.contains("    static let `default` = Color("));
+        assert!(output.contains("    static let `class` = Color("));
+        assert!(output.contains("    static let object = Color("));
+        assert!(output.contains("    static let color500 = Color("));
+    }
+
+    #[test]
+    fn test_flutter_escapes_keywords() {
+        let output = export(&reserved(), &ExportOptions::new(ExportFormat::Flutter));
+        assert!(output.contains("  static const Color default_ = Color(0xFF000000);\n"));
+        assert!(output.contains("  static const Color class_ = Color(0xFF000000);\n"));
+        assert!(output.contains("  static const Color object = Color(0xFF000000);\n"));
+        assert!(output.contains("  static const Color color500 = Color(0xFF000000);\n"));
+    }
+
+    #[test]
+    fn test_android_escapes_keywords() {
+        let output = export(&reserved(), &ExportOptions::new(ExportFormat::Android));
+        assert!(output.contains("<color name=\"default_\">#000000</color>"));
+        assert!(output.contains("<color name=\"class_\">#000000</color>"));
+        assert!(output.contains("<color name=\"object\">#000000</color>"));
+        assert!(output.contains("<color name=\"color_500\">#000000</color>"));
+
+        // Hyphens and capitals aren't allowed in resource names
+        let mut options = ExportOptions::new(ExportFormat::Android);
+        assert_eq!(ExportFormat::Android.cases(), [NameCase::Snake]);
+        options.case = NameCase::Kebab;
+        assert_eq!(variable_names(&brand(), &options), ["primary", "deep_navy"]);
+    }
+
+    #[test]
+    fn test_asset_catalog() {
+        let dir = std::env::temp_dir().join(format!("cascolor-xcassets-{}", std::process::id()));
+        let catalog = dir.join("Colors.xcassets");
+        save(&brand(), &ExportOptions::new(ExportFormat::Xcassets), &catalog).unwrap();
+
+        let root: serde_json::Value = serde_json::from_str(&fs::read_to_string(catalog.join("Contents.json")).unwrap()).unwrap();
+        assert_eq!(root["info"]["version"], 1);
+
+        let colorset = fs::read_to_string(catalog.join("DeepNavy.colorset/Contents.json")).unwrap();
+        let colorset: serde_json::Value = serde_json::from_str(&colorset).unwrap();
+        let components = &colorset["colors"][0]["color"]["components"];
+        assert_eq!(colorset["colors"][0]["color"]["color-space"], "srgb");
+        assert_eq!(components["red"], "0.071");
+        assert_eq!(components["alpha"], "1.000");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_pascal_case_and_defaults() {
+        let mut options = ExportOptions::new(ExportFormat::Css);
+        options.case = NameCase::Pascal;
+        assert_eq!(variable_names(&brand(), &options), ["Primary", "DeepNavy"]);
+
+        assert_eq!("pascal".parse::<NameCase>().unwrap(), NameCase::Pascal);
+        assert_eq!(ExportFormat::Android.default_case(), NameCase::Snake);
+        assert_eq!(ExportFormat::Xcassets.default_file_name(), "Colors.xcassets");
+        assert!(ExportFormat::Xcassets.is_directory());
+        assert!(!ExportFormat::Flutter.is_directory());
+    }
+}
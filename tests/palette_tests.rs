@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod palette_tests {
+    use cascolor::color::CasColor;
+    use cascolor::palette::{read_gpl, write_gpl, Palette, PaletteError, PaletteFormat};
+    use std::path::Path;
+
+    const GPL: &str = "GIMP Palette\nName: Brand\nColumns: 2\n#\n# comment\n255  87  51\tTomato Orange\n  0   0   0\tUntitled\n18 52 86\n";
+
+    #[test]
+    fn test_read_gpl() {
+        let palette = read_gpl(GPL).unwrap();
+
+        assert_eq!(palette.name.as_deref(), Some("Brand"));
+        assert_eq!(palette.columns, Some(2));
+        assert_eq!(palette.len(), 3);
+        assert_eq!(palette.entries[0].color, CasColor::from_rgb(255, 87, 51));
+        assert_eq!(palette.entries[0].name.as_deref(), Some("Tomato Orange"));
+        assert_eq!(palette.entries[1].name, None);
+        assert_eq!(palette.entries[2].color, CasColor::from_rgb(18, 52, 86));
+    }
+
+    #[test]
+    fn test_gpl_round_trip() {
+        let palette = read_gpl(GPL).unwrap();
+        let written = write_gpl(&palette);
+
+        assert!(written.starts_with("GIMP Palette\nName: Brand\nColumns: 2\n"));
+        assert!(written.contains("255  87  51\tTomato Orange\n"));
+        assert_eq!(read_gpl(&written).unwrap(), palette);
+    }
+
+    #[test]
+    fn test_gpl_errors_report_line() {
+        assert!(matches!(read_gpl("Not a palette\n"), Err(PaletteError::ParseError { line: 1, .. })));
+
+        let error = read_gpl("GIMP Palette\n10 20\n").unwrap_err();
+        assert!(matches!(error, PaletteError::ParseError { line: 2, .. }));
+
+        let error = read_gpl("GIMP Palette\n#\n256 0 0 Too bright\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 3: expected a channel value 0-255, found \"256\"");
+    }
+
+    #[test]
+    fn test_grid_layout() {
+        let palette = read_gpl(GPL).unwrap();
+
+        assert_eq!(palette.grid_columns(), 2);
+        assert_eq!(palette.grid_rows(), 2);
+        assert_eq!(palette.get(1, 0).map(|e| e.color), Some(CasColor::from_rgb(18, 52, 86)));
+        assert!(palette.get(1, 1).is_none());
+        assert!(palette.get(0, 2).is_none());
+
+        let builtin = Palette::builtin();
+        assert_eq!((builtin.grid_columns(), builtin.grid_rows()), (10, 30));
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(PaletteFormat::from_path(Path::new("Brand.GPL")).unwrap(), PaletteFormat::Gpl);
+        assert!(matches!(
+            PaletteFormat::from_path(Path::new("colors.txt")),
+            Err(PaletteError::UnknownFormat(_))
+        ));
+        assert_eq!("gpl".parse::<PaletteFormat>(), Ok(PaletteFormat::Gpl));
+    }
+}
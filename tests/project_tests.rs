@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod project_tests {
+    use cascolor::color::CasColor;
+    use cascolor::config::{Config, Sources, ThemeMode};
+    use cascolor::project::{find, FormatTemplate, Project, ProjectError};
+    use std::fs;
+    use tempfile::TempDir;
+
+    const GPL: &str = "GIMP Palette\nName: Brand\n#\n0 85 255\tPrimary\n255 102 0\tAccent\n";
+
+    #[test]
+    fn test_find_walks_up_to_repository_root() {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path().join("repo");
+        let nested = repo.join("src").join("components");
+        fs::create_dir_all(&nested).unwrap();
+        fs::create_dir(repo.join(".git")).unwrap();
+        assert_eq!(find(&nested), None);
+
+        fs::write(repo.join("cascolor.palette.gpl"), GPL).unwrap();
+        assert_eq!(find(&nested), Some(repo.clone()));
+
+        // The nearest project wins, and nothing outside the repository counts
+        fs::write(repo.join("src").join(".cascolor.toml"), "").unwrap();
+        assert_eq!(find(&nested), Some(repo.join("src")));
+        fs::remove_file(repo.join("src").join(".cascolor.toml")).unwrap();
+        fs::remove_file(repo.join("cascolor.palette.gpl")).unwrap();
+        fs::write(dir.path().join(".cascolor.toml"), "").unwrap();
+        assert_eq!(find(&nested), None);
+        assert_eq!(find(dir.path()), Some(dir.path().to_path_buf()));
+    }
+
+    #[test]
+    fn test_palette_file_next_to_project() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("cascolor.palette.gpl"), GPL).unwrap();
+        fs::write(dir.path().join("cascolor.palette.txt"), "not a palette").unwrap();
+
+        let project = Project::load(dir.path()).unwrap();
+        let palette = project.palette.unwrap();
+        assert_eq!(palette.name.as_deref(), Some("Brand"));
+        assert_eq!(palette.entries[1].name.as_deref(), Some("Accent"));
+        assert_eq!(project.palette_file, Some(dir.path().join("cascolor.palette.gpl")));
+        assert!(project.formats.is_empty());
+        assert!(project.contrast.is_none());
+    }
+
+    #[test]
+    fn test_project_file_sections() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("cascolor.palette.gpl"), GPL).unwrap();
+        fs::write(
+            dir.path().join(".cascolor.toml"),
+            r##"
+[general]
+theme = "light"
+
+[palette]
+name = "Acme"
+columns = 2
+colors = { primary = "#0055FF", accent = "#FF6600", ink = "#111827" }
+
+[[formats]]
+name = "Tailwind"
+template = "{r} {g} {b}"
+
+[[formats]]
+name = "Token"
+template = "{{ color: {hex} }}"
+
+[contrast]
+minimum = 3
+backgrounds = ["#FFFFFF"]
+"##,
+        )
+        .unwrap();
+
+        let project = Project::load(dir.path()).unwrap();
+        let palette = project.palette.unwrap();
+        assert_eq!(palette.name.as_deref(), Some("Acme"));
+        assert_eq!(palette.columns, Some(2));
+        let names: Vec<&str> = palette.entries.iter().filter_map(|entry| entry.name.as_deref()).collect();
+        assert_eq!(names, ["primary", "accent", "ink"]);
+        assert_eq!(project.palette_file, None);
+
+        let color = CasColor::from_rgb(0, 85, 255);
+        let rendered: Vec<String> = project.formats.iter().map(|format| format.render(&color)).collect();
+        assert_eq!(rendered, ["0 85 255", "{ color: #0055FF }"]);
+
+        let contrast = project.contrast.unwrap();
+        assert_eq!(contrast.minimum, 3.0);
+        let checks = contrast.check(&color);
+        assert_eq!(checks.len(), 1);
+        assert!(checks[0].2);
+        assert!(!contrast.check(&CasColor::from_rgb(255, 255, 0))[0].2);
+    }
+
+    #[test]
+    fn test_invalid_project_settings() {
+        assert!(FormatTemplate::new("Bad", "{rgba}").unwrap_err().contains("unknown placeholder {rgba}"));
+        assert!(FormatTemplate::new("Bad", "{hex").is_err());
+        assert!(FormatTemplate::new("Bad", "}").is_err());
+
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join(".cascolor.toml");
+        let load = |contents: &str| {
+            fs::write(&file, contents).unwrap();
+            Project::load(dir.path()).unwrap_err()
+        };
+
+        let error = load("[contrast]\nminimum = 30\n");
+        assert!(matches!(&error, ProjectError::InvalidValue(_, key, _) if key == "contrast.minimum"));
+        assert!(error.to_string().ends_with("contrast.minimum: expected a ratio from 1 to 21, got 30"));
+
+        let error = load("[palette]\ncolors = { brand = \"#12345\" }\n");
+        assert!(matches!(&error, ProjectError::InvalidValue(_, key, _) if key == "palette.colors.brand"));
+        let error = load("[[formats]]\nname = \"Tailwind\"\n");
+        assert!(matches!(&error, ProjectError::InvalidValue(_, key, _) if key == "formats[0].template"));
+        assert!(matches!(load("[palette]\nfile = \"missing.gpl\"\n"), ProjectError::PaletteError(..)));
+    }
+
+    #[test]
+    fn test_project_sections_stay_out_of_config() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join(".cascolor.toml");
+        let contents = "[general]\ntheme = \"light\"\n\n[palette]\ncolors = { a = \"#000000\" }\n\n[[formats]]\nname = \"x\"\ntemplate = \"{hex}\"\n";
+        fs::write(&file, contents).unwrap();
+
+        let (config, diagnostics) = Config::parse(contents);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        assert_eq!(config.general.theme, ThemeMode::Light);
+
+        let sources = Sources { project: Some(file), ..Sources::default() };
+        let layered = Config::load_layered(&sources).unwrap();
+        assert!(layered.problems.is_empty());
+        assert_eq!(layered.config.general.theme, ThemeMode::Light);
+    }
+}
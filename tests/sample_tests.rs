@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod sample_tests {
+    use cascolor::color::CasColor;
+    use cascolor::extract::{sample, Averaging, ExtractError, Region};
+    use image::{Rgba, RgbaImage};
+
+    // Black and white checkerboard, with a transparent 2x2 block in the corner
+    fn checkerboard() -> RgbaImage {
+        RgbaImage::from_fn(8, 8, |x, y| match (x, y) {
+            (6.., 6..) => Rgba([255, 0, 0, 0]),
+            _ if (x + y) % 2 == 0 => Rgba([0, 0, 0, 255]),
+            _ => Rgba([255, 255, 255, 255]),
+        })
+    }
+
+    #[test]
+    fn test_pixel() {
+        let image = checkerboard();
+        let black = sample(&image, Region::Pixel { x: 0, y: 0 }, Averaging::Linear).unwrap();
+        let white = sample(&image, Region::Pixel { x: 1, y: 0 }, Averaging::Srgb).unwrap();
+        assert_eq!(black, CasColor::from_rgb(0, 0, 0));
+        assert_eq!(white, CasColor::from_rgb(255, 255, 255));
+    }
+
+    #[test]
+    fn test_rect_linear_and_srgb() {
+        let image = checkerboard();
+        let region = Region::Rect { x: 0, y: 0, width: 4, height: 4 };
+
+        // Half the light of white is much brighter than the sRGB midpoint
+        assert_eq!(sample(&image, region, Averaging::Srgb).unwrap(), CasColor::from_rgb(128, 128, 128));
+        assert_eq!(sample(&image, region, Averaging::Linear).unwrap(), CasColor::from_rgb(188, 188, 188));
+
+        // Clipped at the image edge: only the 2x2 block at 3,3 remains
+        let clipped = Region::Rect { x: 3, y: 3, width: 100, height: 2 };
+        assert_eq!(sample(&image, clipped, Averaging::Srgb).unwrap(), CasColor::from_rgb(128, 128, 128));
+    }
+
+    #[test]
+    fn test_circle() {
+        // Radius 1 covers the center and its 4 neighbours: 1 black, 4 white
+        let image = checkerboard();
+        let region = Region::Circle { x: 2, y: 2, radius: 1 };
+        assert_eq!(sample(&image, region, Averaging::Srgb).unwrap(), CasColor::from_rgb(204, 204, 204));
+
+        let single = Region::Circle { x: 2, y: 2, radius: 0 };
+        assert_eq!(sample(&image, single, Averaging::Srgb).unwrap(), CasColor::from_rgb(0, 0, 0));
+    }
+
+    #[test]
+    fn test_transparency_and_bounds() {
+        let image = checkerboard();
+
+        // Transparent pixels carry no weight
+        let region = Region::Rect { x: 5, y: 6, width: 3, height: 1 };
+        assert_eq!(sample(&image, region, Averaging::Srgb).unwrap(), CasColor::from_rgb(255, 255, 255));
+
+        let clear = Region::Pixel { x: 7, y: 7 };
+        let error = sample(&image, clear, Averaging::Linear).unwrap_err();
+        assert_eq!(error.to_string(), "Every pixel at 7,7 is transparent");
+
+        let outside = sample(&image, Region::Circle { x: 8, y: 0, radius: 4 }, Averaging::Linear).unwrap_err();
+        assert!(matches!(outside, ExtractError::OutOfBounds { x: 8, y: 0, width: 8, height: 8 }));
+        assert_eq!(outside.to_string(), "8,0 is outside the 8x8 image");
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(Region::Pixel { x: 3, y: 4 }.to_string(), "3,4");
+        assert_eq!(Region::Rect { x: 1, y: 2, width: 3, height: 4 }.to_string(), "rect 1,2,3,4");
+        assert_eq!(Region::Circle { x: 5, y: 6, radius: 7 }.to_string(), "circle 5,6,7");
+        assert_eq!("SRGB".parse::<Averaging>().unwrap(), Averaging::Srgb);
+        assert!("gamma".parse::<Averaging>().is_err());
+    }
+}
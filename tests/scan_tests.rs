@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod scan_tests {
+    use cascolor::color::{find_colors, rewrite_colors, ColorFormat, ColorSyntax};
+
+    #[test]
+    fn test_find_colors_in_css() {
+        let css = ".a { color: #ff5733; background: rgb(0 0 255); }";
+        let found = find_colors(css, &ColorSyntax::DEFAULT);
+
+        assert_eq!(found.len(), 2);
+        assert_eq!(&css[found[0].range.clone()], "#ff5733");
+        assert_eq!(found[0].syntax, ColorSyntax::Hex);
+        assert_eq!(&css[found[1].range.clone()], "rgb(0 0 255)");
+        assert_eq!(found[1].color.to_hex(), "#0000FF");
+    }
+
+    #[test]
+    fn test_rewrite_preserves_surrounding_text() {
+        let css = "a{color:#FF0000}\n  b { border: 1px solid rgb(0, 255, 0); }\n";
+        let out = rewrite_colors(css, ColorFormat::Hex, &ColorSyntax::DEFAULT);
+        assert_eq!(out, "a{color:#FF0000}\n  b { border: 1px solid #00FF00; }\n");
+    }
+
+    #[test]
+    fn test_rewrite_keeps_alpha() {
+        let out = rewrite_colors("rgba(255, 0, 0, 0.5)", ColorFormat::Hex, &ColorSyntax::DEFAULT);
+        assert_eq!(out, "#FF000080");
+
+        let out = rewrite_colors("#FF000080", ColorFormat::Oklch, &ColorSyntax::DEFAULT);
+        assert!(out.ends_with(" / 0.502)"), "{}", out);
+    }
+
+    #[test]
+    fn test_match_selects_syntaxes() {
+        let text = "color: red; fill: #00f;";
+        assert_eq!(rewrite_colors(text, ColorFormat::Hex, &ColorSyntax::DEFAULT), text);
+        assert_eq!(
+            rewrite_colors(text, ColorFormat::Rgb, &[ColorSyntax::Named]),
+            "color: rgb(255, 0, 0); fill: #00f;"
+        );
+    }
+
+    #[test]
+    fn test_ignores_non_literals() {
+        let text = "#main { x: 1 } &#123; $red: 1; --red: 2; rgb(var(--r) 0 0) #12345";
+        let all: Vec<ColorSyntax> = ColorSyntax::ALL.to_vec();
+        assert!(find_colors(text, &all).is_empty());
+    }
+}
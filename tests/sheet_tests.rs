@@ -0,0 +1,99 @@
+mod common;
+
+#[cfg(test)]
+mod sheet_tests {
+    use cascolor::color::{scale, CasColor, Harmony};
+    use cascolor::palette::Palette;
+    use cascolor::sheet::{layout, render_png, render_svg, wcag_grade, SheetFormat, SheetOptions};
+    use std::path::Path;
+
+    // The shared palette plus a light swatch without a name
+    fn brand() -> Palette {
+        let mut palette = crate::common::brand();
+        palette.push(CasColor::from_rgb(240, 240, 240), None);
+        palette
+    }
+
+    #[test]
+    fn test_layout_grid() {
+        let options = SheetOptions { columns: Some(2), ..SheetOptions::default() };
+        let sheet = layout(&brand(), &options);
+
+        // 2 columns: margins of 2 gaps, 2 swatches and 1 gap between them
+        assert_eq!(sheet.width, 32 * 2 + 264 * 2 + 16);
+        let swatches: Vec<_> = sheet.rects.iter().filter(|r| r.width == 264).collect();
+        assert_eq!(swatches.len(), 3);
+        assert_eq!(swatches[0].color, CasColor::from_rgb(255, 87, 51));
+        assert_eq!(swatches[1].x, 32 + 264 + 16);
+        assert_eq!(swatches[2].x, swatches[0].x);
+        assert!(swatches[2].y > swatches[0].y);
+
+        let texts: Vec<&str> = sheet.texts.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts[..4], ["Brand Colors", "Primary", "#FF5733", "oklch(68.0% 0.210 34)"]);
+    }
+
+    #[test]
+    fn test_contrast_badges() {
+        let options = SheetOptions { contrast: true, ..SheetOptions::default() };
+        let sheet = layout(&brand(), &options);
+
+        let badges: Vec<&str> =
+            sheet.texts.iter().map(|t| t.text.as_str()).filter(|t| t.starts_with("Aa ")).collect();
+        assert_eq!(badges.len(), 6);
+        assert_eq!(badges[2], "Aa 12.7 AAA");
+        assert_eq!(badges[3], "Aa 1.7 Fail");
+
+        assert_eq!(wcag_grade(7.0), "AAA");
+        assert_eq!(wcag_grade(4.5), "AA");
+        assert_eq!(wcag_grade(3.2), "AA Large");
+        assert_eq!(wcag_grade(2.9), "Fail");
+    }
+
+    #[test]
+    fn test_png_matches_layout() {
+        let sheet = layout(&brand(), &SheetOptions::default());
+        let png = render_png(&sheet).unwrap();
+
+        let image = image::load_from_memory(&png).unwrap().to_rgb8();
+        assert_eq!((image.width(), image.height()), (sheet.width, sheet.height));
+
+        let swatch = sheet.rects.iter().find(|r| r.width == 264).unwrap();
+        assert_eq!(image.get_pixel(swatch.x + 10, swatch.y + 10).0, [255, 87, 51]);
+        assert_eq!(image.get_pixel(0, 0).0, [255, 255, 255]);
+    }
+
+    #[test]
+    fn test_svg_matches_layout() {
+        let sheet = layout(&brand(), &SheetOptions::default());
+        let svg = render_svg(&sheet);
+
+        assert!(svg.starts_with(&format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\"", sheet.width)));
+        assert!(svg.contains("<rect x=\"32\" y=\"78\" width=\"264\" height=\"120\" fill=\"#FF5733\"/>"));
+        assert!(svg.contains(">Deep Navy</text>"));
+        assert_eq!(svg.matches("<text ").count(), sheet.texts.len());
+        assert!(svg.ends_with("</svg>\n"));
+
+        assert_eq!(SheetFormat::from_path(Path::new("sheet.SVG")), Some(SheetFormat::Svg));
+        assert_eq!(SheetFormat::from_path(Path::new("sheet.jpg")), None);
+    }
+
+    #[test]
+    fn test_harmonies_and_scales() {
+        let base = CasColor::from_rgb(255, 87, 51);
+        let triadic = Harmony::Triadic.colors(&base);
+        assert_eq!(triadic.len(), 3);
+        assert_eq!(triadic[0], base);
+        let (_, _, h0) = base.to_oklch();
+        let (_, _, h1) = triadic[1].to_oklch();
+        assert!(((h1 - h0).rem_euclid(360.0) - 120.0).abs() < 5.0);
+        assert_eq!(Harmony::Tetradic.colors(&base).len(), 4);
+        assert_eq!("split-complementary".parse::<Harmony>().unwrap(), Harmony::SplitComplementary);
+
+        let white = CasColor::from_rgb(255, 255, 255);
+        let navy = CasColor::from_rgb(18, 52, 86);
+        let steps = scale(&white, &navy, 5);
+        assert_eq!(steps.len(), 5);
+        assert_eq!((steps[0], steps[4]), (white, navy));
+        assert!(steps.windows(2).all(|w| w[0].relative_luminance() > w[1].relative_luminance()));
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod swatch_tests {
+    use cascolor::color::CasColor;
+    use cascolor::palette::{read_aco, read_ase, write_aco, write_ase, Palette, PaletteEntry, PaletteError, SwatchKind};
+
+    fn be16(out: &mut Vec<u8>, value: u16) {
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn be32(out: &mut Vec<u8>, value: u32) {
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn utf16(out: &mut Vec<u8>, text: &str) -> u16 {
+        let units: Vec<u16> = text.encode_utf16().chain([0]).collect();
+        for unit in &units {
+            be16(out, *unit);
+        }
+        units.len() as u16
+    }
+
+    // One ASE color block: name, model and float channels, swatch type
+    fn ase_color(name: &str, model: &[u8; 4], channels: &[f32], kind: u16) -> Vec<u8> {
+        let mut text = Vec::new();
+        let units = utf16(&mut text, name);
+
+        let mut data = Vec::new();
+        be16(&mut data, units);
+        data.extend_from_slice(&text);
+        data.extend_from_slice(model);
+        for channel in channels {
+            data.extend_from_slice(&channel.to_be_bytes());
+        }
+        be16(&mut data, kind);
+
+        let mut block = Vec::new();
+        be16(&mut block, 0x0001);
+        be32(&mut block, data.len() as u32);
+        block.extend_from_slice(&data);
+        block
+    }
+
+    fn ase_file(blocks: &[Vec<u8>]) -> Vec<u8> {
+        let mut out = b"ASEF".to_vec();
+        be16(&mut out, 1);
+        be16(&mut out, 0);
+        be32(&mut out, blocks.len() as u32);
+        for block in blocks {
+            out.extend_from_slice(block);
+        }
+        out
+    }
+
+    #[test]
+    fn test_read_ase_color_models() {
+        let mut group_start = Vec::new();
+        let mut name = Vec::new();
+        let units = utf16(&mut name, "Brand");
+        be16(&mut group_start, 0xC001);
+        be32(&mut group_start, 2 + name.len() as u32);
+        be16(&mut group_start, units);
+        group_start.extend_from_slice(&name);
+        let group_end = vec![0xC0, 0x02, 0, 0, 0, 0];
+
+        let bytes = ase_file(&[
+            group_start,
+            ase_color("Tomato", b"RGB ", &[1.0, 99.0 / 255.0, 71.0 / 255.0], 0),
+            ase_color("Black", b"CMYK", &[0.0, 0.0, 0.0, 1.0], 1),
+            group_end,
+            ase_color("Mid", b"Gray", &[0.5], 2),
+            ase_color("White", b"LAB ", &[1.0, 0.0, 0.0], 2),
+        ]);
+        let palette = read_ase(&bytes).unwrap();
+
+        assert_eq!(palette.len(), 4);
+        assert_eq!(palette.entries[0].color, CasColor::from_rgb(255, 99, 71));
+        assert_eq!(palette.entries[0].name.as_deref(), Some("Tomato"));
+        assert_eq!(palette.entries[0].group.as_deref(), Some("Brand"));
+        assert_eq!(palette.entries[0].kind, SwatchKind::Global);
+        assert_eq!(palette.entries[1].color, CasColor::from_rgb(0, 0, 0));
+        assert_eq!(palette.entries[1].kind, SwatchKind::Spot);
+        assert_eq!(palette.entries[2].color, CasColor::from_rgb(128, 128, 128));
+        assert_eq!(palette.entries[2].group, None);
+        assert_eq!(palette.entries[3].color, CasColor::from_rgb(255, 255, 255));
+    }
+
+    #[test]
+    fn test_ase_round_trip() {
+        let mut palette = Palette::default();
+        let mut entry = PaletteEntry::new(CasColor::from_rgb(18, 52, 86), Some("Navy ✓".to_string()));
+        entry.group = Some("Brand".to_string());
+        entry.kind = SwatchKind::Spot;
+        palette.entries.push(entry);
+        palette.push(CasColor::from_rgb(255, 87, 51), Some("Tomato".to_string()));
+
+        let read = read_ase(&write_ase(&palette)).unwrap();
+        assert_eq!(read, palette);
+
+        // A name past the u16 length is cut short without splitting the emoji
+        let long = "a".repeat(65533);
+        let mut palette = Palette::default();
+        palette.push(CasColor::from_rgb(0, 0, 0), Some(format!("{}😀", long)));
+        let read = read_ase(&write_ase(&palette)).unwrap();
+        assert_eq!(read.entries[0].name.as_deref(), Some(long.as_str()));
+    }
+
+    #[test]
+    fn test_aco_round_trip_prefers_named_section() {
+        let mut palette = Palette::default();
+        palette.push(CasColor::from_rgb(255, 87, 51), Some("Tomato".to_string()));
+        palette.push(CasColor::from_rgb(0, 128, 255), Some("Azure".to_string()));
+
+        let bytes = write_aco(&palette);
+        assert_eq!(&bytes[..4], &[0, 1, 0, 2]);
+        assert_eq!(read_aco(&bytes).unwrap(), palette);
+    }
+
+    #[test]
+    fn test_read_aco_v1_color_spaces() {
+        let mut bytes = Vec::new();
+        be16(&mut bytes, 1);
+        be16(&mut bytes, 3);
+        // HSB pure red, CMYK with no ink (white), Lab mid grey
+        for values in [[1, 0, 65535, 65535, 0], [2, 65535, 65535, 65535, 65535], [7, 5000, 0, 0, 0]] {
+            for value in values {
+                be16(&mut bytes, value);
+            }
+        }
+        let palette = read_aco(&bytes).unwrap();
+
+        assert_eq!(palette.entries[0].color, CasColor::from_rgb(255, 0, 0));
+        assert_eq!(palette.entries[0].name, None);
+        assert_eq!(palette.entries[1].color, CasColor::from_rgb(255, 255, 255));
+        let (r, g, b) = palette.entries[2].color.to_rgb();
+        assert!(r == g && g == b && (118..=120).contains(&r), "{:?}", (r, g, b));
+    }
+
+    #[test]
+    fn test_malformed_files_are_errors() {
+        assert!(matches!(read_ase(b"GIF89a"), Err(PaletteError::InvalidData { offset: 0, .. })));
+
+        // Truncated in the middle of a color block
+        let bytes = ase_file(&[ase_color("Tomato", b"RGB ", &[1.0, 0.5, 0.2], 2)]);
+        let error = read_ase(&bytes[..bytes.len() - 5]).unwrap_err();
+        assert!(error.to_string().contains("unexpected end of file"), "{}", error);
+
+        let bytes = ase_file(&[ase_color("Odd", b"HSV ", &[0.0, 0.0, 0.0], 2)]);
+        assert!(read_ase(&bytes).unwrap_err().to_string().contains("unknown color model \"HSV \""));
+
+        // Pantone colors cannot be converted
+        let error = read_aco(&[0, 1, 0, 1, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0]).unwrap_err();
+        assert_eq!(error.to_string(), "offset 4: unsupported color space 3 (Pantone)");
+
+        assert!(read_aco(&[0, 9]).is_err());
+        assert!(read_aco(&[0, 1, 0, 5, 0, 0]).is_err());
+    }
+}
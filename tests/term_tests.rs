@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod term_tests {
+    use cascolor::color::CasColor;
+    use cascolor::term::{detect_color_support, swatch, to_ansi16, to_ansi256, ColorSupport};
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> HashMap<String, String> {
+        vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_not_a_tty_is_plain() {
+        let vars = env(&[("COLORTERM", "truecolor"), ("TERM", "xterm-256color")]);
+        assert_eq!(detect_color_support(&vars, false), ColorSupport::None);
+    }
+
+    #[test]
+    fn test_detection_levels() {
+        assert_eq!(detect_color_support(&env(&[("COLORTERM", "truecolor")]), true), ColorSupport::TrueColor);
+        assert_eq!(detect_color_support(&env(&[("COLORTERM", "24bit")]), true), ColorSupport::TrueColor);
+        assert_eq!(detect_color_support(&env(&[("TERM", "xterm-direct")]), true), ColorSupport::TrueColor);
+        assert_eq!(detect_color_support(&env(&[("TERM", "screen-256color")]), true), ColorSupport::Ansi256);
+        assert_eq!(detect_color_support(&env(&[("TERM", "xterm")]), true), ColorSupport::Ansi16);
+        assert_eq!(detect_color_support(&env(&[("TERM", "dumb")]), true), ColorSupport::None);
+    }
+
+    #[test]
+    fn test_no_color_and_force() {
+        let vars = env(&[("COLORTERM", "truecolor"), ("NO_COLOR", "1")]);
+        assert_eq!(detect_color_support(&vars, true), ColorSupport::None);
+
+        let vars = env(&[("TERM", "xterm-256color"), ("CLICOLOR_FORCE", "1")]);
+        assert_eq!(detect_color_support(&vars, false), ColorSupport::Ansi256);
+    }
+
+    #[test]
+    fn test_palette_reduction() {
+        assert_eq!(to_ansi256(255, 0, 0), 196);
+        assert_eq!(to_ansi256(128, 128, 128), 244);
+        assert_eq!(to_ansi16(250, 10, 10), 9);
+        assert_eq!(to_ansi16(10, 10, 10), 0);
+    }
+
+    #[test]
+    fn test_swatch_escapes() {
+        let color = CasColor::from_rgb(255, 87, 51);
+        assert_eq!(swatch(&color, ColorSupport::TrueColor, 2), "\x1b[48;2;255;87;51m  \x1b[0m");
+        assert_eq!(swatch(&color, ColorSupport::Ansi256, 1), "\x1b[48;5;203m \x1b[0m");
+        assert_eq!(swatch(&color, ColorSupport::None, 4), "");
+    }
+}
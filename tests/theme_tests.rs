@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod theme_tests {
+    use cascolor::color::CasColor;
+    use cascolor::palette::Palette;
+    use cascolor::theme::{render, TerminalTheme, ThemeError, ThemeFormat};
+
+    // Tomorrow Night, with its ANSI colors in slot order
+    const ANSI: [&str; 16] = [
+        "#1D1F21", "#CC6666", "#B5BD68", "#F0C674", "#81A2BE", "#B294BB", "#8ABEB7", "#C5C8C6", "#969896", "#D54E53",
+        "#B9CA4A", "#E7C547", "#7AA6DA", "#C397D8", "#70C0B1", "#EAEAEA",
+    ];
+
+    fn tomorrow_night() -> Palette {
+        let mut palette = Palette::new("Tomorrow Night");
+        for hex in ANSI {
+            palette.push(CasColor::from_hex(hex).unwrap(), None);
+        }
+        palette
+    }
+
+    #[test]
+    fn test_slots_by_position_and_name() {
+        let theme = TerminalTheme::from_palette(&tomorrow_night()).unwrap();
+        assert_eq!(theme.background, CasColor::from_hex("#1D1F21").unwrap());
+        assert_eq!(theme.foreground, CasColor::from_hex("#C5C8C6").unwrap());
+        assert_eq!(theme.selection, theme.ansi[8]);
+
+        // Named entries take their slot wherever they appear
+        let mut palette = Palette::new("Named");
+        palette.push(CasColor::from_rgb(40, 40, 40), Some("Background".to_string()));
+        palette.push(CasColor::from_rgb(255, 0, 0), Some("brightRed".to_string()));
+        for hex in &ANSI[..15] {
+            palette.push(CasColor::from_hex(hex).unwrap(), None);
+        }
+        let theme = TerminalTheme::from_palette(&palette).unwrap();
+        assert_eq!(theme.background, CasColor::from_rgb(40, 40, 40));
+        assert_eq!(theme.ansi[9], CasColor::from_rgb(255, 0, 0));
+        assert_eq!(theme.ansi[10], CasColor::from_hex(ANSI[9]).unwrap());
+        assert!(theme.unused.is_empty());
+
+        // Colors past the 16 slots are kept aside
+        let mut palette = tomorrow_night();
+        palette.push(CasColor::from_rgb(1, 2, 3), None);
+        let theme = TerminalTheme::from_palette(&palette).unwrap();
+        assert_eq!(theme.unused, [CasColor::from_rgb(1, 2, 3)]);
+    }
+
+    #[test]
+    fn test_too_few_colors() {
+        let mut palette = tomorrow_night();
+        palette.entries.truncate(12);
+
+        let error = TerminalTheme::from_palette(&palette).unwrap_err();
+        assert!(matches!(error, ThemeError::TooFewColors { found: 12 }));
+        assert_eq!(error.to_string(), "A terminal theme needs 16 ANSI colors, the palette has 12");
+    }
+
+    #[test]
+    fn test_contrast_issues() {
+        let mut theme = TerminalTheme::from_palette(&tomorrow_night()).unwrap();
+        assert!(theme.is_dark());
+        // Black on a black background is expected and not reported
+        assert!(theme.contrast_issues(3.0).is_empty());
+
+        theme.ansi[4] = CasColor::from_rgb(30, 30, 60);
+        theme.foreground = CasColor::from_rgb(90, 90, 90);
+        let slots: Vec<&str> = theme.contrast_issues(3.0).iter().map(|issue| issue.slot).collect();
+        assert_eq!(slots, ["foreground", "blue"]);
+        assert_eq!(theme.contrast_issues(3.0)[0].required, 4.5);
+    }
+
+    #[test]
+    fn test_terminal_formats() {
+        let theme = TerminalTheme::from_palette(&tomorrow_night()).unwrap();
+
+        let alacritty = render(&theme, ThemeFormat::Alacritty);
+        assert!(alacritty.contains("[colors.bright]\nblack = \"#969896\"\nred = \"#d54e53\"\n"));
+        assert!(render(&theme, ThemeFormat::Kitty).contains("\ncolor15 #eaeaea\n"));
+        assert!(render(&theme, ThemeFormat::Foot).contains("\nbright7=eaeaea\n"));
+        assert!(render(&theme, ThemeFormat::Xresources).contains("\n*.color1: #cc6666\n"));
+        assert!(render(&theme, ThemeFormat::Wezterm).contains("\n[metadata]\nname = \"Tomorrow Night\"\n"));
+        assert!(render(&theme, ThemeFormat::Iterm2).contains("<key>Ansi 15 Color</key>"));
+
+        let windows: serde_json::Value = serde_json::from_str(&render(&theme, ThemeFormat::WindowsTerminal)).unwrap();
+        assert_eq!(windows["name"], "Tomorrow Night");
+        assert_eq!(windows["brightPurple"], "#C397D8");
+
+        // The name can't break out of a comment header
+        let mut theme = theme;
+        theme.name = "Tomorrow\nNight".to_string();
+        assert!(render(&theme, ThemeFormat::Kitty).starts_with("# Tomorrow Night\n\n"));
+        assert!(render(&theme, ThemeFormat::Xresources).starts_with("! Tomorrow Night\n\n"));
+    }
+
+    #[test]
+    fn test_base16_and_base24() {
+        let theme = TerminalTheme::from_palette(&tomorrow_night()).unwrap();
+
+        let base16 = render(&theme, ThemeFormat::Base16);
+        assert!(base16.starts_with("system: \"base16\"\nname: \"Tomorrow Night\"\n"));
+        assert!(base16.contains("variant: \"dark\"\n"));
+        assert!(base16.contains("  base00: \"#1d1f21\"\n"));
+        assert!(base16.contains("  base08: \"#cc6666\"\n  base09:"));
+        assert!(base16.contains("  base0D: \"#81a2be\"\n"));
+        assert!(!base16.contains("base10"));
+
+        let base24 = render(&theme, ThemeFormat::Base24);
+        assert!(base24.contains("  base12: \"#d54e53\"\n"));
+        assert!(base24.contains("  base17: \"#c397d8\"\n"));
+    }
+}
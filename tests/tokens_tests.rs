@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tokens_tests {
+    use cascolor::color::CasColor;
+    use cascolor::palette::{read_tokens, write_tokens, Palette, PaletteError, PaletteFormat};
+    use std::path::Path;
+
+    const TOKENS: &str = r##"{
+  "color": {
+    "$type": "color",
+    "brand": {
+      "500": { "$value": "#ff5733" },
+      "600": { "$value": { "colorSpace": "srgb", "components": [0.0706, 0.2039, 0.3373], "hex": "#123456" } }
+    },
+    "action": {
+      "primary": { "$value": "{color.brand.500}" }
+    }
+  },
+  "spacing": {
+    "small": { "$type": "dimension", "$value": { "value": 4, "unit": "px" } }
+  }
+}"##;
+
+    #[test]
+    fn test_read_tokens() {
+        let palette = read_tokens(TOKENS).unwrap();
+
+        assert_eq!(palette.len(), 3);
+        assert_eq!(palette.entries[0].name.as_deref(), Some("500"));
+        assert_eq!(palette.entries[0].group.as_deref(), Some("color.brand"));
+        assert_eq!(palette.entries[0].color, CasColor::from_rgb(255, 87, 51));
+        assert_eq!(palette.entries[1].color, CasColor::from_rgb(18, 52, 86));
+        assert_eq!(palette.entries[0].alias, None);
+    }
+
+    #[test]
+    fn test_aliases_resolve() {
+        let palette = read_tokens(TOKENS).unwrap();
+        let primary = &palette.entries[2];
+
+        assert_eq!(primary.name.as_deref(), Some("primary"));
+        assert_eq!(primary.alias.as_deref(), Some("color.brand.500"));
+        assert_eq!(primary.color, CasColor::from_rgb(255, 87, 51));
+
+        let missing = r#"{ "a": { "$type": "color", "$value": "{b.c}" } }"#;
+        assert_eq!(read_tokens(missing).unwrap_err().to_string(), "token a: alias {b.c} does not refer to a token");
+
+        let cycle = r#"{ "$type": "color", "a": { "$value": "{b}" }, "b": { "$value": "{a}" } }"#;
+        assert!(matches!(read_tokens(cycle), Err(PaletteError::InvalidToken { .. })));
+    }
+
+    #[test]
+    fn test_color_spaces() {
+        let tokens = r##"{
+  "$type": "color",
+  "oklch": { "$value": { "colorSpace": "oklch", "components": [0.628, 0.2577, 29.23] } },
+  "hsl": { "$value": { "colorSpace": "hsl", "components": [120, 100, 25] } },
+  "lab": { "$value": { "colorSpace": "lab", "components": [100, 0, 0] } },
+  "gray": { "$value": { "colorSpace": "srgb", "components": ["none", "none", "none"] } },
+  "p3": { "$value": { "colorSpace": "display-p3", "components": [1, 0, 0], "hex": "#ff0000" } }
+}"##;
+        let palette = read_tokens(tokens).unwrap();
+
+        assert_eq!(palette.entries[0].color, CasColor::from_rgb(255, 0, 0));
+        assert_eq!(palette.entries[1].color, CasColor::from_rgb(0, 127, 0));
+        assert_eq!(palette.entries[2].color, CasColor::from_rgb(255, 255, 255));
+        assert_eq!(palette.entries[3].color, CasColor::from_rgb(0, 0, 0));
+        assert_eq!(palette.entries[4].color, CasColor::from_rgb(255, 0, 0));
+
+        let no_fallback = r#"{ "a": { "$type": "color", "$value": { "colorSpace": "rec2020", "components": [1, 0, 0] } } }"#;
+        assert!(read_tokens(no_fallback).unwrap_err().to_string().starts_with("token a: unsupported color space"));
+    }
+
+    #[test]
+    fn test_tokens_round_trip() {
+        let palette = read_tokens(TOKENS).unwrap();
+        let written = write_tokens(&palette).unwrap();
+
+        assert!(written.contains("\"$value\": \"{color.brand.500}\""));
+        assert!(written.contains("\"hex\": \"#ff5733\""));
+        assert_eq!(read_tokens(&written).unwrap(), palette);
+    }
+
+    #[test]
+    fn test_tokens_swatch_named_like_a_group() {
+        // ASE keeps swatch and group names apart, tokens share one namespace
+        let mut swatches = Palette::new("Clash");
+        for (name, group) in [("Brand", None), ("Primary", Some("Brand")), ("Light", Some("Accent")), ("Accent", None), ("Dark", Some("Brand"))] {
+            swatches.push(CasColor::from_rgb(255, 87, 51), Some(name.to_string()));
+            swatches.entries.last_mut().unwrap().group = group.map(str::to_string);
+        }
+        let palette = PaletteFormat::Ase.read(&PaletteFormat::Ase.write(&swatches).unwrap()).unwrap();
+
+        let written = read_tokens(&write_tokens(&palette).unwrap()).unwrap();
+        let paths: Vec<String> = written
+            .entries
+            .iter()
+            .map(|entry| [entry.group.as_deref(), entry.name.as_deref()].into_iter().flatten().collect::<Vec<_>>().join("."))
+            .collect();
+        assert_eq!(paths, ["Brand", "Brand-2.Primary", "Brand-2.Dark", "Accent.Light", "Accent-2"]);
+    }
+
+    #[test]
+    fn test_tokens_format_detection() {
+        assert_eq!(PaletteFormat::from_path(Path::new("design/Colors.tokens.json")).unwrap(), PaletteFormat::Tokens);
+        assert_eq!(PaletteFormat::from_path(Path::new("colors.tokens")).unwrap(), PaletteFormat::Tokens);
+        assert_eq!(PaletteFormat::from_path(Path::new("brand.gpl")).unwrap(), PaletteFormat::Gpl);
+        assert!(PaletteFormat::from_path(Path::new("colors.json")).is_err());
+
+        let error = read_tokens("{\n  \"a\": \n}").unwrap_err();
+        assert!(matches!(error, PaletteError::ParseError { line: 3, .. }));
+    }
+}